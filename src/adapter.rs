@@ -0,0 +1,372 @@
+use std::{
+    ffi::CStr,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use neli::attr::Attribute;
+use neli_wifi::Nl80211Attr;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use tracing::{info, warn};
+
+use crate::{i18n, nl80211::Nl80211};
+
+pub const HINT: &str = "Tab selects next interface, 'm' for monitoring";
+
+/// Temperature and power state change slowly, sysfs is read not more often than this
+const POWER_REFRESH: Duration = Duration::from_secs(2);
+
+/// Chips get throttled by firmware around this temperature
+pub const HOT: f64 = 80.0;
+
+/// `ETHTOOL_GDRVINFO` from linux/ethtool.h
+const ETHTOOL_GDRVINFO: u32 = 0x3;
+
+/// Bits of `enum nl80211_feature_flags` which matter for bug reports
+const FEATURES: [(u32, &str); 13] = [
+    (1 << 2, "inactivity-timer"),
+    (1 << 5, "SAE"),
+    (1 << 6, "low-priority-scan"),
+    (1 << 7, "scan-flush"),
+    (1 << 8, "AP-scan"),
+    (1 << 9, "vif-txpower"),
+    (1 << 17, "active-monitor"),
+    (1 << 21, "quiet"),
+    (1 << 24, "static-SMPS"),
+    (1 << 25, "dynamic-SMPS"),
+    (1 << 28, "TDLS-channel-switch"),
+    (1 << 29, "scan-random-mac"),
+    (1 << 30, "sched-scan-random-mac"),
+];
+
+/// `struct ethtool_drvinfo`
+#[repr(C)]
+struct DrvInfo {
+    cmd: u32,
+    driver: [u8; 32],
+    version: [u8; 32],
+    fw_version: [u8; 32],
+    bus_info: [u8; 32],
+    erom_version: [u8; 32],
+    reserved2: [u8; 12],
+    n_priv_flags: u32,
+    n_stats: u32,
+    testinfo_len: u32,
+    eedump_len: u32,
+    regdump_len: u32,
+}
+
+/// What is known about driver and hardware of interface
+#[derive(Clone, Debug, Default)]
+pub struct AdapterInfo {
+    pub driver: Option<String>,
+    pub driver_version: Option<String>,
+    pub firmware: Option<String>,
+    pub bus: Option<String>,
+    /// PCI or USB `vendor:device` ids
+    pub ids: Option<String>,
+    pub modalias: Option<String>,
+    pub features: Vec<&'static str>,
+    /// Amount of extended nl80211 features
+    pub ext_features: usize,
+}
+
+/// Live thermal and power readings of adapter, missing parts are not exposed by driver
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Power {
+    /// °C from hwmon of device or wiphy
+    pub temperature: Option<f64>,
+    /// PCI power state like `D0` or `D3hot`
+    pub state: Option<String>,
+    /// Runtime PM status like `active` or `suspended`
+    pub runtime: Option<String>,
+}
+
+impl Power {
+    pub fn is_empty(&self) -> bool {
+        *self == Power::default()
+    }
+}
+
+impl std::fmt::Display for Power {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(t) = self.temperature {
+            parts.push(format!("{:.0} °C", t));
+        }
+        if let Some(s) = &self.state {
+            parts.push(s.clone());
+        }
+        if let Some(r) = &self.runtime {
+            parts.push(r.clone());
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// First `temp*_input` of hwmon directories under `dir`
+fn hwmon_temperature(dir: &Path) -> Option<f64> {
+    let mut hwmons: Vec<PathBuf> = fs::read_dir(dir.join("hwmon"))
+        .or_else(|_| fs::read_dir(dir))
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .is_some_and(|n| n.to_string_lossy().starts_with("hwmon"))
+        })
+        .collect();
+    hwmons.sort();
+    hwmons.iter().find_map(|hwmon| {
+        let millis: i64 = fs::read_to_string(hwmon.join("temp1_input"))
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        Some(millis as f64 / 1000.0)
+    })
+}
+
+/// Reads temperature from hwmon of device or wiphy and power state of PCI or USB device
+pub fn power(iface: &str, phy: Option<u32>) -> Power {
+    let device = Path::new("/sys/class/net").join(iface).join("device");
+    let read = |path: PathBuf| {
+        fs::read_to_string(path)
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    // iwlwifi registers hwmon under wiphy, most other drivers under device
+    let temperature = hwmon_temperature(&device).or_else(|| {
+        phy.and_then(|phy| {
+            hwmon_temperature(&Path::new("/sys/class/ieee80211").join(format!("phy{}", phy)))
+        })
+    });
+    Power {
+        temperature,
+        state: read(device.join("power_state")),
+        // USB interfaces have runtime PM on their device one directory up
+        runtime: read(device.join("power/runtime_status"))
+            .filter(|s| s != "unsupported")
+            .or_else(|| read(device.join("../power/runtime_status")))
+            .filter(|s| s != "unsupported"),
+    }
+}
+
+/// Power readings of one interface, refreshed not more often than `POWER_REFRESH`
+pub struct PowerMonitor {
+    iface: String,
+    power: Power,
+    updated: Option<Instant>,
+}
+
+impl PowerMonitor {
+    pub fn new() -> Self {
+        Self {
+            iface: String::new(),
+            power: Power::default(),
+            updated: None,
+        }
+    }
+
+    pub fn update(&mut self, iface: &str, phy: Option<u32>) -> &Power {
+        if self.iface != iface || self.updated.is_none_or(|t| t.elapsed() >= POWER_REFRESH) {
+            self.iface = iface.to_string();
+            self.power = power(iface, phy);
+            self.updated = Some(Instant::now());
+        }
+        &self.power
+    }
+}
+
+/// Collects adapter info from ethtool, sysfs and wiphy dump, missing parts are left empty
+pub fn read(nl: Option<&mut Nl80211>, iface: &str, phy: Option<u32>) -> AdapterInfo {
+    let mut info = AdapterInfo::default();
+    match drvinfo(iface) {
+        Ok(d) => {
+            info.driver = field(&d.driver);
+            info.driver_version = field(&d.version);
+            info.firmware = field(&d.fw_version);
+            info.bus = field(&d.bus_info);
+        }
+        Err(e) => warn!(error = %e, iface, "ethtool driver info failed"),
+    }
+
+    let device = Path::new("/sys/class/net").join(iface).join("device");
+    if info.driver.is_none() {
+        info.driver = fs::read_link(device.join("driver"))
+            .ok()
+            .and_then(|p| Some(p.file_name()?.to_string_lossy().to_string()));
+    }
+    let read = |name: &str| {
+        fs::read_to_string(device.join(name))
+            .ok()
+            .map(|v| v.trim().to_string())
+    };
+    // PCI devices have vendor and device files, USB interfaces have them one directory up
+    info.ids = match (read("vendor"), read("device")) {
+        (Some(v), Some(d)) => Some(format!("{}:{}", v, d)),
+        _ => match (read("../idVendor"), read("../idProduct")) {
+            (Some(v), Some(d)) => Some(format!("0x{}:0x{}", v, d)),
+            _ => None,
+        },
+    };
+    info.modalias = read("modalias");
+
+    if let (Some(nl), Some(phy)) = (nl, phy) {
+        match wiphy_features(nl, phy) {
+            Ok((flags, ext)) => {
+                info.features = FEATURES
+                    .iter()
+                    .filter(|(bit, _)| flags & bit != 0)
+                    .map(|(_, name)| *name)
+                    .collect();
+                info.ext_features = ext;
+            }
+            Err(e) => warn!(error = %e, phy, "failed to dump wiphy features"),
+        }
+    }
+    info!(iface, ?info, "adapter info");
+    info
+}
+
+/// Asks driver for its name and firmware with `SIOCETHTOOL`
+fn drvinfo(iface: &str) -> io::Result<DrvInfo> {
+    let mut drvinfo: DrvInfo = unsafe { std::mem::zeroed() };
+    drvinfo.cmd = ETHTOOL_GDRVINFO;
+    let mut ifr: libc::ifreq = unsafe { std::mem::zeroed() };
+    if iface.len() >= ifr.ifr_name.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "interface name is too long",
+        ));
+    }
+    for (dst, src) in ifr.ifr_name.iter_mut().zip(iface.bytes()) {
+        *dst = src as libc::c_char;
+    }
+    ifr.ifr_ifru.ifru_data = &mut drvinfo as *mut DrvInfo as *mut libc::c_char;
+
+    let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let res = unsafe { libc::ioctl(fd, libc::SIOCETHTOOL as _, &mut ifr) };
+    let err = io::Error::last_os_error();
+    unsafe { libc::close(fd) };
+    if res < 0 {
+        return Err(err);
+    }
+    Ok(drvinfo)
+}
+
+/// Null terminated string field of ethtool struct, `None` if empty
+fn field(bytes: &[u8]) -> Option<String> {
+    let s = CStr::from_bytes_until_nul(bytes).ok()?.to_string_lossy();
+    (!s.is_empty()).then(|| s.to_string())
+}
+
+/// Returns feature flags and amount of extended features of wiphy
+fn wiphy_features(nl: &mut Nl80211, phy: u32) -> io::Result<(u32, usize)> {
+    let mut flags = 0;
+    let mut ext = 0;
+    for msg in nl.wiphy(phy)? {
+        let attrs = msg.get_attr_handle();
+        if let Some(f) = attrs
+            .get_attribute(Nl80211Attr::AttrFeatureFlags)
+            .and_then(|a| a.get_payload_as::<u32>().ok())
+        {
+            flags |= f;
+        }
+        if let Some(a) = attrs.get_attribute(Nl80211Attr::AttrExtFeatures) {
+            ext = a
+                .nla_payload
+                .as_ref()
+                .iter()
+                .map(|b| b.count_ones() as usize)
+                .sum();
+        }
+    }
+    Ok((flags, ext))
+}
+
+/// Adapter view state which lives between frames, info is read once per interface
+pub struct AdapterView {
+    nl: Option<Nl80211>,
+    iface: String,
+    info: Option<AdapterInfo>,
+    power: PowerMonitor,
+}
+
+impl AdapterView {
+    pub fn new() -> Self {
+        Self {
+            nl: None,
+            iface: String::new(),
+            info: None,
+            power: PowerMonitor::new(),
+        }
+    }
+
+    pub fn update(&mut self, iface: &str, phy: Option<u32>) {
+        self.power.update(iface, phy);
+        if self.info.is_some() && self.iface == iface {
+            return;
+        }
+        if self.nl.is_none() {
+            self.nl = Nl80211::connect()
+                .inspect_err(|e| warn!(error = %e, "failed to open nl80211 socket"))
+                .ok();
+        }
+        self.iface = iface.to_string();
+        self.info = Some(read(self.nl.as_mut(), iface, phy));
+    }
+
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let info = self.info.clone().unwrap_or_default();
+        let line = |name: &'static str, value: Option<String>| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{}: ", name),
+                    Style::default().add_modifier(Modifier::ITALIC),
+                ),
+                Span::raw(value.unwrap_or_else(|| "-".to_string())),
+            ])
+        };
+        let features = if info.features.is_empty() {
+            None
+        } else {
+            Some(info.features.join(" "))
+        };
+        let text = vec![
+            line("driver", info.driver),
+            line("driver version", info.driver_version),
+            line("firmware", info.firmware),
+            line("bus", info.bus),
+            line("ids", info.ids),
+            line("modalias", info.modalias),
+            line("features", features),
+            line("extended features", Some(info.ext_features.to_string())),
+            line(
+                "temperature",
+                self.power.power.temperature.map(|t| format!("{:.0} °C", t)),
+            ),
+            line("power state", self.power.power.state.clone()),
+            line("runtime PM", self.power.power.runtime.clone()),
+        ];
+        f.render_widget(
+            Paragraph::new(text).wrap(Wrap { trim: false }).block(
+                Block::default()
+                    .title(i18n::fill(i18n::tr("adapter of {}"), &[&self.iface]))
+                    .borders(Borders::ALL),
+            ),
+            area,
+        );
+    }
+}
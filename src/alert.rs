@@ -0,0 +1,170 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
+use rhai::{AST, Dynamic, Engine, Scope};
+use serde::Deserialize;
+use tracing::{debug, warn};
+
+use crate::{
+    events::{self, Kind},
+    sampler::Sample,
+};
+
+/// Alert rule from `[[alert]]` section of config
+#[derive(Clone, Debug, Deserialize)]
+pub struct AlertRule {
+    pub name: String,
+    /// Rule fires when signal is lower than this value in dBm
+    pub below: Option<i32>,
+    /// Rhai expression which fires rule when true, e.g. `signal < -78 && bitrate < 50`,
+    /// sees `signal`, `frequency`, `bitrate`, `score`, `connected`, `iface` and `bssid`
+    pub script: Option<String>,
+    /// Seconds condition must hold before rule fires
+    #[serde(default)]
+    pub for_secs: u64,
+    /// Interface name, rule is checked for every interface if not set
+    pub iface: Option<String>,
+}
+
+/// Rule which started or stopped firing
+#[derive(Clone, Debug)]
+pub struct AlertChange {
+    pub rule: String,
+    pub fired: bool,
+}
+
+/// Checks samples against rules and reports only changes of rule state
+pub struct Alerts {
+    rules: Vec<AlertRule>,
+    engine: Engine,
+    /// Compiled scripts by rule index, rule with broken script never fires
+    scripts: HashMap<usize, AST>,
+    /// (rule index, interface) pairs whose condition holds, with time it started
+    pending: HashMap<(usize, String), Instant>,
+    /// (rule index, interface) pairs which are firing now
+    firing: HashSet<(usize, String)>,
+}
+
+impl Alerts {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        let engine = Engine::new();
+        let mut scripts = HashMap::new();
+        for (i, rule) in rules.iter().enumerate() {
+            match (&rule.script, rule.below) {
+                (Some(script), _) => match engine.compile_expression(script) {
+                    Ok(ast) => {
+                        scripts.insert(i, ast);
+                    }
+                    Err(e) => {
+                        warn!(rule = rule.name, error = %e, "alert script does not compile");
+                        events::push(
+                            Kind::Alert,
+                            format!("script of {} does not compile: {}", rule.name, e),
+                        );
+                    }
+                },
+                (None, None) => warn!(rule = rule.name, "alert has neither below nor script"),
+                (None, Some(_)) => {}
+            }
+        }
+        Self {
+            rules,
+            engine,
+            scripts,
+            pending: HashMap::new(),
+            firing: HashSet::new(),
+        }
+    }
+
+    /// Condition of rule for sample, script wins over `below`
+    fn matches(&self, i: usize, rule: &AlertRule, sample: &Sample) -> bool {
+        if rule.script.is_none() {
+            return rule.below.is_some_and(|below| sample.signal < below);
+        }
+        let Some(ast) = self.scripts.get(&i) else {
+            return false;
+        };
+        let mut scope = Scope::new();
+        scope.push("signal", i64::from(sample.signal));
+        scope.push("frequency", sample.frequency.map_or(0, i64::from));
+        // unknown bitrate and score are unit, so comparing them fails and rule doesn't fire
+        scope.push(
+            "bitrate",
+            sample
+                .bitrate
+                .map_or(Dynamic::UNIT, |b| i64::from(b).into()),
+        );
+        scope.push(
+            "score",
+            sample.score.map_or(Dynamic::UNIT, |s| i64::from(s).into()),
+        );
+        scope.push("connected", sample.connected);
+        scope.push("iface", sample.iface.clone());
+        scope.push("bssid", sample.bssid.clone().unwrap_or_default());
+        match self.engine.eval_ast_with_scope::<bool>(&mut scope, ast) {
+            Ok(res) => res,
+            Err(e) => {
+                debug!(rule = rule.name, error = %e, "alert script failed");
+                false
+            }
+        }
+    }
+
+    /// Evaluates rules for sample, logs and returns rules which started or stopped firing
+    pub fn evaluate(&mut self, sample: &Sample) -> Vec<AlertChange> {
+        let mut changes = Vec::new();
+        for (i, rule) in self.rules.iter().enumerate() {
+            if rule.iface.as_ref().is_some_and(|n| *n != sample.iface) {
+                continue;
+            }
+            let key = (i, sample.iface.clone());
+            if self.matches(i, rule, sample) {
+                let since = *self.pending.entry(key.clone()).or_insert_with(Instant::now);
+                if since.elapsed() >= Duration::from_secs(rule.for_secs) && self.firing.insert(key)
+                {
+                    events::push(
+                        Kind::Alert,
+                        format!(
+                            "{} fired on {}: {} dBm",
+                            rule.name, sample.iface, sample.signal
+                        ),
+                    );
+                    warn!(
+                        rule = rule.name,
+                        iface = sample.iface,
+                        signal = sample.signal,
+                        "alert fired"
+                    );
+                    changes.push(AlertChange {
+                        rule: rule.name.clone(),
+                        fired: true,
+                    });
+                }
+            } else {
+                self.pending.remove(&key);
+                if self.firing.remove(&key) {
+                    events::push(
+                        Kind::Alert,
+                        format!(
+                            "{} resolved on {}: {} dBm",
+                            rule.name, sample.iface, sample.signal
+                        ),
+                    );
+                    warn!(
+                        rule = rule.name,
+                        iface = sample.iface,
+                        signal = sample.signal,
+                        "alert resolved"
+                    );
+                    changes.push(AlertChange {
+                        rule: rule.name.clone(),
+                        fired: false,
+                    });
+                }
+            }
+        }
+        changes
+    }
+}
@@ -0,0 +1,224 @@
+use std::{io, time::Duration};
+
+use macaddr::MacAddr6;
+use neli::attr::Attribute;
+use neli_wifi::{Nl80211Attr, Nl80211Cmd, Nl80211Iftype, Nl80211RateInfo, Nl80211StaInfo};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Paragraph, Row, Table},
+};
+use tracing::warn;
+
+use crate::{
+    display, get_security_info, i18n,
+    nl80211::{self, Message, Nl80211},
+};
+
+pub const HINT: &str = "Tab selects next interface, 'm' for monitoring";
+
+/// Station associated with our access point
+#[derive(Clone, Debug)]
+pub struct StationRow {
+    pub mac: String,
+    /// Signal of last received frame in dBm
+    pub signal: Option<i8>,
+    /// Bitrates in 100 kbit/s
+    pub tx_bitrate: Option<u32>,
+    pub rx_bitrate: Option<u32>,
+    /// Time since last activity
+    pub inactive: Option<Duration>,
+    pub connected: Option<Duration>,
+}
+
+/// Returns mode of interface
+pub fn iftype(nl: &mut Nl80211, index: i32) -> io::Result<Option<Nl80211Iftype>> {
+    let replies = nl.send(
+        Nl80211Cmd::CmdGetInterface,
+        nl80211::attrs(&[(Nl80211Attr::AttrIfindex, index as u32)])?,
+        false,
+    )?;
+    Ok(replies.iter().find_map(|msg| {
+        let kind: u32 = msg
+            .get_attr_handle()
+            .get_attribute(Nl80211Attr::AttrIftype)?
+            .get_payload_as()
+            .ok()?;
+        Some(Nl80211Iftype::from(kind as u16))
+    }))
+}
+
+/// Dumps stations of interface, in AP mode they are associated clients
+pub fn stations(nl: &mut Nl80211, index: i32) -> io::Result<Vec<StationRow>> {
+    let replies = nl.send(
+        Nl80211Cmd::CmdGetStation,
+        nl80211::attrs(&[(Nl80211Attr::AttrIfindex, index as u32)])?,
+        true,
+    )?;
+    Ok(replies.iter().filter_map(station).collect())
+}
+
+fn station(msg: &Message) -> Option<StationRow> {
+    let attrs = msg.get_attr_handle();
+    let mac: [u8; 6] = attrs
+        .get_attribute(Nl80211Attr::AttrMac)?
+        .nla_payload
+        .as_ref()
+        .try_into()
+        .ok()?;
+    let mut row = StationRow {
+        mac: MacAddr6::from(mac).to_string(),
+        signal: None,
+        tx_bitrate: None,
+        rx_bitrate: None,
+        inactive: None,
+        connected: None,
+    };
+    let info = attrs
+        .get_attribute(Nl80211Attr::AttrStaInfo)?
+        .get_attr_handle::<Nl80211StaInfo>()
+        .ok()?;
+    for attr in info.iter() {
+        match attr.nla_type.nla_type {
+            Nl80211StaInfo::StaInfoSignal => row.signal = attr.get_payload_as().ok(),
+            Nl80211StaInfo::StaInfoInactiveTime => {
+                row.inactive = attr
+                    .get_payload_as::<u32>()
+                    .ok()
+                    .map(|ms| Duration::from_millis(ms.into()))
+            }
+            Nl80211StaInfo::StaInfoConnectedTime => {
+                row.connected = attr
+                    .get_payload_as::<u32>()
+                    .ok()
+                    .map(|s| Duration::from_secs(s.into()))
+            }
+            Nl80211StaInfo::StaInfoTxBitrate | Nl80211StaInfo::StaInfoRxBitrate => {
+                let rate = attr
+                    .get_attr_handle::<Nl80211RateInfo>()
+                    .ok()
+                    .and_then(|r| {
+                        r.get_attribute(Nl80211RateInfo::RateInfoBitrate32)?
+                            .get_payload_as::<u32>()
+                            .ok()
+                    });
+                if attr.nla_type.nla_type == Nl80211StaInfo::StaInfoTxBitrate {
+                    row.tx_bitrate = rate;
+                } else {
+                    row.rx_bitrate = rate;
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(row)
+}
+
+/// AP view state which lives between frames
+pub struct StationsView {
+    nl: Option<Nl80211>,
+}
+
+impl StationsView {
+    pub fn new() -> Self {
+        Self { nl: None }
+    }
+
+    /// Returns stations if interface runs in AP mode, `None` for other modes
+    pub fn update(&mut self, index: i32) -> Option<Vec<StationRow>> {
+        if self.nl.is_none() {
+            self.nl = Nl80211::connect()
+                .inspect_err(|e| warn!(error = %e, "failed to open nl80211 socket"))
+                .ok();
+        }
+        let nl = self.nl.as_mut()?;
+        let res = iftype(nl, index).and_then(|kind| match kind {
+            Some(Nl80211Iftype::IftypeAp | Nl80211Iftype::IftypeP2pGo) => {
+                stations(nl, index).map(Some)
+            }
+            _ => Ok(None),
+        });
+        match res {
+            Ok(rows) => rows,
+            Err(e) => {
+                warn!(error = %e, "failed to dump stations");
+                self.nl = None;
+                None
+            }
+        }
+    }
+
+    pub fn draw(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        iface: &str,
+        rows: Option<&[StationRow]>,
+        hide_info: bool,
+    ) {
+        let title = i18n::fill(i18n::tr("stations of {}"), &[iface]);
+        match rows {
+            Some(rows) => {
+                let table_rows = rows.iter().map(|r| {
+                    Row::new(vec![
+                        Span::raw(get_security_info(&r.mac, hide_info)),
+                        match r.signal {
+                            Some(s) => Span::styled(
+                                display::signal_value(i32::from(s)).to_string(),
+                                Style::default().fg(display::signal_color(i32::from(s))),
+                            ),
+                            None => Span::raw("-"),
+                        },
+                        Span::raw(format_bitrate(r.tx_bitrate)),
+                        Span::raw(format_bitrate(r.rx_bitrate)),
+                        Span::raw(
+                            r.inactive
+                                .map_or("-".to_string(), |d| format!("{:.1} s", d.as_secs_f64())),
+                        ),
+                        Span::raw(
+                            r.connected
+                                .map_or("-".to_string(), |d| format!("{} s", d.as_secs())),
+                        ),
+                    ])
+                });
+                let table = Table::new(
+                    table_rows,
+                    [
+                        Constraint::Percentage(30),
+                        Constraint::Percentage(10),
+                        Constraint::Percentage(15),
+                        Constraint::Percentage(15),
+                        Constraint::Percentage(15),
+                        Constraint::Percentage(15),
+                    ],
+                )
+                .header(
+                    Row::new(vec![
+                        "MAC".to_string(),
+                        display::unit().to_string(),
+                        "TX".to_string(),
+                        "RX".to_string(),
+                        i18n::tr("idle").to_string(),
+                        i18n::tr("connected").to_string(),
+                    ])
+                    .style(Style::default().add_modifier(Modifier::ITALIC)),
+                )
+                .block(Block::default().title(title).borders(Borders::ALL));
+                f.render_widget(table, area);
+            }
+            None => f.render_widget(
+                Paragraph::new(i18n::tr("Selected interface is not running in AP mode"))
+                    .block(Block::default().title(title).borders(Borders::ALL)),
+                area,
+            ),
+        }
+    }
+}
+
+fn format_bitrate(rate: Option<u32>) -> String {
+    rate.map_or("-".to_string(), |r| {
+        format!("{:.1} Mbit/s", f64::from(r) / 10.0)
+    })
+}
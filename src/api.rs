@@ -0,0 +1,143 @@
+use std::{
+    ffi::CString,
+    fs,
+    io::{self, BufRead, BufReader, Write},
+    os::unix::fs::PermissionsExt,
+    os::unix::net::{UnixListener, UnixStream},
+    path::{Path, PathBuf},
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tracing::{debug, info, warn};
+
+use crate::{radio, sampler::Sample};
+
+/// Request sent as one JSON line, for example `{"cmd":"set_interval","interval":5}`
+#[derive(Debug, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+enum Request {
+    /// Latest samples of all interfaces
+    Status,
+    /// Scan on interface, connected one if not given
+    Scan { iface: Option<String> },
+    /// Seconds between samples, until config is reloaded
+    SetInterval { interval: u64 },
+}
+
+/// What daemon shares with socket clients
+#[derive(Clone)]
+pub struct Shared {
+    pub latest: Arc<RwLock<Vec<Sample>>>,
+    pub interval: Arc<AtomicU64>,
+}
+
+/// Client which sends nothing for this long is dropped
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Socket of running daemon in config directory
+pub fn socket_path(dir: &Path) -> PathBuf {
+    dir.join("daemon.sock")
+}
+
+/// Starts thread which answers JSON requests on unix socket, socket left by previous run is
+/// replaced
+pub fn spawn(path: &Path, shared: Shared) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    let listener = UnixListener::bind(path)?;
+    // samples show BSSIDs, so only owner may ask
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    info!(path = %path.display(), "api listening");
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let shared = shared.clone();
+            // stuck client must not hold others back
+            std::thread::spawn(move || {
+                if let Err(e) = serve(stream, &shared) {
+                    debug!(error = %e, "api client failed");
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+/// Answers every request line of one client until it closes connection
+fn serve(stream: UnixStream, shared: &Shared) -> io::Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let reply = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => {
+                info!(?request, "api request");
+                handle(request, shared).unwrap_or_else(|e| json!({"ok": false, "error": e}))
+            }
+            Err(e) => json!({"ok": false, "error": format!("bad request: {}", e)}),
+        };
+        writeln!(writer, "{}", reply)?;
+    }
+    Ok(())
+}
+
+fn handle(request: Request, shared: &Shared) -> Result<Value, String> {
+    match request {
+        Request::Status => {
+            let latest = shared.latest.read().unwrap();
+            Ok(json!({
+                "ok": true,
+                "interval": shared.interval.load(Ordering::Relaxed),
+                "samples": latest.iter().map(Sample::json).collect::<Vec<_>>(),
+            }))
+        }
+        Request::Scan { iface } => {
+            let iface = iface
+                .or_else(|| {
+                    let latest = shared.latest.read().unwrap();
+                    latest.iter().find(|s| s.connected).map(|s| s.iface.clone())
+                })
+                .ok_or("no connected interface, name one with \"iface\"")?;
+            let name = CString::new(iface.as_str()).map_err(|e| e.to_string())?;
+            let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+            if index == 0 {
+                return Err(format!("unknown interface {}", iface));
+            }
+            radio::trigger_scan(index).map_err(|e| {
+                warn!(error = %e, iface, "api scan failed");
+                e.to_string()
+            })?;
+            Ok(json!({"ok": true, "iface": iface}))
+        }
+        Request::SetInterval { interval } => {
+            if !(1..=3600).contains(&interval) {
+                return Err("interval must be from 1 to 3600 seconds".to_string());
+            }
+            shared.interval.store(interval, Ordering::Relaxed);
+            Ok(json!({"ok": true, "interval": interval}))
+        }
+    }
+}
+
+/// Sends one request to running daemon and returns its reply line, waits for it `timeout`
+pub fn query(path: &Path, request: &str, timeout: Duration) -> io::Result<String> {
+    let mut stream = UnixStream::connect(path)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    writeln!(stream, "{}", request.trim())?;
+    stream.shutdown(std::net::Shutdown::Write)?;
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    Ok(reply.trim_end().to_string())
+}
@@ -1,38 +1,735 @@
-#[derive(Copy, Clone, Debug)]
-pub enum AppState<'a> {
+use tracing::debug;
+
+use crate::{
+    error::AppError,
+    events::{self, Kind},
+    panels::PanelsConfig,
+    scan::{Filter, Sort},
+    timeline,
+};
+
+/// Amount of views remembered for back navigation
+const HISTORY_LIMIT: usize = 32;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AppState {
     Monitoring,
     Main,
-    Error { h: &'a str, d: &'a str },
+    /// Table of networks seen by selected interface
+    Scan,
+    /// Clients of selected interface running as access point
+    Stations,
+    /// Management frames counted on monitor interface
+    Frames,
+    /// Regulatory domain and allowed channels of selected interface
+    Regdomain,
+    /// Driver, firmware and features of selected interface
+    Adapter,
+    /// Capability tree of wiphy of selected interface
+    Phy,
+    /// Recent events with timestamps
+    Log,
+    /// Interval, signal thresholds, units, theme and privacy, saved to config file
+    Settings,
+    /// Interfaces of agents given with `--connect`
+    Remote,
+    /// Chart of stored history over selectable time range
+    History,
+    /// Popup shown over the previous view until dismissed
+    Error(AppError),
+    /// Asks user to confirm action before it is executed
+    Confirm {
+        action: Action,
+    },
+    /// QR code for joining network of selected interface
+    QrCode,
+    /// Text entry, typed text is kept in `ProgramState::input`
+    Input {
+        purpose: Input,
+    },
+}
+
+/// What text typed in `AppState::Input` is used for
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Input {
+    WifiPassword,
+    /// TX power in dBm of selected interface
+    TxPower,
+    /// Note attached to current time in history
+    Note,
+}
+
+impl Input {
+    pub fn prompt(&self) -> &'static str {
+        match self {
+            Input::WifiPassword => "Password of current network",
+            Input::TxPower => "TX power in dBm, empty or 'auto' for automatic",
+            Input::Note => "Note for current time, e.g. 'moved router to shelf'",
+        }
+    }
+
+    /// true if typed text must not be shown on screen
+    pub fn secret(&self) -> bool {
+        matches!(self, Input::WifiPassword)
+    }
+
+    /// Action executed with typed text after Enter
+    pub fn action(&self) -> Action {
+        match self {
+            Input::WifiPassword => Action::SetPassword,
+            Input::TxPower => Action::SetTxPower,
+            Input::Note => Action::AddNote,
+        }
+    }
+
+    /// true if action must be confirmed after Enter
+    pub fn confirm(&self) -> bool {
+        matches!(self, Input::TxPower)
+    }
 }
 
-impl<'a> std::fmt::Display for AppState<'a> {
+impl AppState {
+    /// Input, confirmation and error are shown on top of views and never kept in history
+    pub fn is_dialog(&self) -> bool {
+        matches!(
+            self,
+            AppState::Input { .. } | AppState::Confirm { .. } | AppState::Error(_)
+        )
+    }
+
+    /// Short name of state without details
+    pub fn name(&self) -> &'static str {
+        match self {
+            AppState::Monitoring => "Monitoring",
+            AppState::Main => "Main",
+            AppState::Scan => "Scan",
+            AppState::Stations => "Stations",
+            AppState::Frames => "Frames",
+            AppState::Regdomain => "Regdomain",
+            AppState::Adapter => "Adapter",
+            AppState::Phy => "Phy",
+            AppState::Log => "Log",
+            AppState::Settings => "Settings",
+            AppState::Remote => "Remote",
+            AppState::History => "History",
+            AppState::Error(_) => "Error",
+            AppState::Confirm { .. } => "Confirm",
+            AppState::QrCode => "QrCode",
+            AppState::Input { .. } => "Input",
+        }
+    }
+}
+
+impl std::fmt::Display for AppState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             AppState::Monitoring => write!(f, "Monitoring"),
             AppState::Main => write!(f, "Main"),
-            AppState::Error { h, d } => write!(f, "Error header {}; description {}", h, d),
+            AppState::Scan => write!(f, "Scan"),
+            AppState::Stations => write!(f, "Stations"),
+            AppState::Frames => write!(f, "Frames"),
+            AppState::Regdomain => write!(f, "Regdomain"),
+            AppState::Adapter => write!(f, "Adapter"),
+            AppState::Phy => write!(f, "Phy"),
+            AppState::Log => write!(f, "Log"),
+            AppState::Settings => write!(f, "Settings"),
+            AppState::Remote => write!(f, "Remote"),
+            AppState::History => write!(f, "History"),
+            AppState::Error(e) => write!(f, "Error {}: {}", e.header(), e),
+            AppState::Confirm { action } => write!(f, "Confirm {:?}", action),
+            AppState::QrCode => write!(f, "QrCode"),
+            AppState::Input { purpose } => write!(f, "Input {:?}", purpose),
+        }
+    }
+}
+
+/// Action requested by input thread and executed by render loop
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    ToggleRfkill,
+    /// Brings selected interface up if it is down and down if it is up
+    ToggleLink,
+    /// Connects selected interface to network selected in scan table
+    Connect,
+    /// Disconnects selected interface from current network
+    Disconnect,
+    /// Asks connection manager to scan on selected interface
+    Scan,
+    /// Uses typed text as password of current network in QR code
+    SetPassword,
+    /// Measures throughput against configured server
+    SpeedTest,
+    /// Measures gateway latency while speed test saturates the link
+    Bufferbloat,
+    /// Uses typed text as TX power of selected interface
+    SetTxPower,
+    /// Enables 802.11 power save if it is disabled and disables if it is enabled
+    TogglePowerSave,
+    /// Expands or collapses selected node of capability tree
+    ToggleNode,
+    /// Expands or collapses selected network with several BSSIDs in scan table
+    ToggleGroup,
+    /// Quits although measurement is still running
+    Quit,
+    /// Changes selected setting one step up or cycles to next value
+    IncreaseSetting,
+    /// Changes selected setting one step down or cycles to next value
+    DecreaseSetting,
+    /// Writes session report into config directory
+    Report,
+    /// Gives charts row of monitoring more height, its start is the current layout
+    GrowCharts,
+    /// Gives charts row of monitoring less height
+    ShrinkCharts,
+    /// Saves typed text as note in history
+    AddNote,
+    /// Copies IP address or BSSID of selected interface in monitoring
+    Copy,
+    /// Copies selected BSSID or SSID in scan table
+    CopyScan,
+}
+
+impl Action {
+    /// Question shown before action is executed
+    pub fn question(&self) -> &'static str {
+        match self {
+            Action::ToggleRfkill => "Toggle rfkill soft block of selected interface?",
+            Action::ToggleLink => "Bring selected interface up if it is down or down if it is up?",
+            Action::Connect => "Connect to selected network?",
+            Action::Disconnect => "Disconnect selected interface from current network?",
+            Action::Scan => "Start new scan on selected interface?",
+            Action::SetPassword => "Use typed password for QR code?",
+            Action::SpeedTest => "Run speed test? It will saturate the link for a few seconds",
+            Action::Bufferbloat => {
+                "Run bufferbloat test? It pings gateway while speed test saturates the link"
+            }
+            Action::TogglePowerSave => "Toggle power save of selected interface?",
+            Action::ToggleNode => "Expand or collapse selected node?",
+            Action::ToggleGroup => "Expand or collapse selected network?",
+            Action::Quit => "Quit while measurement is running? Its data will be lost",
+            Action::IncreaseSetting | Action::DecreaseSetting => "Change selected setting?",
+            Action::Report => "Write session report?",
+            Action::GrowCharts | Action::ShrinkCharts => "Resize charts?",
+            Action::AddNote => "Add note to history?",
+            Action::Copy | Action::CopyScan => "Copy selected value to clipboard?",
+            Action::SetTxPower => {
+                "Change TX power of selected interface? Too low power may drop the connection"
+            }
+        }
+    }
+
+    /// State where action is executed
+    pub fn view(&self) -> AppState {
+        match self {
+            Action::ToggleRfkill
+            | Action::ToggleLink
+            | Action::SpeedTest
+            | Action::Bufferbloat
+            | Action::SetTxPower
+            | Action::TogglePowerSave
+            | Action::Report
+            | Action::GrowCharts
+            | Action::ShrinkCharts
+            | Action::Copy => AppState::Monitoring,
+            Action::Connect
+            | Action::Disconnect
+            | Action::Scan
+            | Action::ToggleGroup
+            | Action::CopyScan => AppState::Scan,
+            Action::SetPassword => AppState::QrCode,
+            Action::ToggleNode => AppState::Phy,
+            Action::AddNote => AppState::History,
+            Action::Quit => AppState::Main,
+            Action::IncreaseSetting | Action::DecreaseSetting => AppState::Settings,
         }
     }
 }
 
-#[derive(Clone, Copy, Debug)]
-pub struct ProgramState<'a> {
+/// What user asked for, [`ProgramState::transition`] decides where it leads
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// Switches to view, allowed only from views
+    Open(AppState),
+    /// Returns to previous view, quits when there is none
+    Back,
+    Quit,
+    /// Asks to confirm action
+    Ask(Action),
+    /// Asks for text, allowed only in view where its action is executed
+    Prompt(Input),
+    /// Enter in input
+    Submit,
+    /// 'y' in confirmation
+    Accept,
+    /// Leaves input or confirmation without executing action
+    Cancel,
+    /// Closes error popup
+    Dismiss,
+}
+
+#[derive(Clone, Debug)]
+pub struct ProgramState {
     pub hide_info: bool,
     pub running: bool,
-    pub state: AppState<'a>,
+    pub state: AppState,
+    /// Index of selected interface, wraps around amount of interfaces
+    pub selected: usize,
+    /// Index of selected row in scan table, wraps around amount of rows
+    pub scan_selected: usize,
+    /// Rows of scan table which are shown
+    pub scan_filter: Filter,
+    /// '/' was pressed in scan table, keys go into filter text until Enter or Esc
+    pub scan_typing: bool,
+    pub scan_sort: Sort,
+    /// Index of selected line in capability tree, wraps around amount of lines
+    pub phy_selected: usize,
+    /// Index of selected line in event log, 0 is the newest event
+    pub log_selected: usize,
+    /// Index of selected line in settings, wraps around amount of settings
+    pub settings_selected: usize,
+    pub pending: Option<Action>,
+    /// Text typed in `AppState::Input`
+    pub input: String,
+    /// Views visited before the current one, Esc returns to the last of them
+    pub history: Vec<AppState>,
+    /// Measurement which would be lost on quit, set by render loop
+    pub busy: Option<&'static str>,
+    /// Monitoring is drawn as one or two lines
+    pub compact: bool,
+    /// Index of layout preset of monitoring, wraps around amount of presets
+    pub layout: usize,
+    /// Panels toggled and resized by keys, render loop saves them to config
+    pub panels: PanelsConfig,
+    /// Time range of history chart, index into `timeline::SPANS`
+    pub history_zoom: usize,
+    /// Half-ranges history chart is moved into the past
+    pub history_pan: u32,
 }
 
-impl<'a> ProgramState<'a> {
-    /// Changes state for ProgramState
-    pub fn change_state(&mut self, s: AppState<'a>) {
+impl ProgramState {
+    pub fn new() -> Self {
+        Self {
+            hide_info: true,
+            running: true,
+            state: AppState::Main,
+            selected: 0,
+            scan_selected: 0,
+            scan_filter: Filter::default(),
+            scan_typing: false,
+            scan_sort: Sort::default(),
+            phy_selected: 0,
+            log_selected: 0,
+            settings_selected: 0,
+            pending: None,
+            input: String::new(),
+            history: Vec::new(),
+            busy: None,
+            compact: false,
+            layout: 0,
+            panels: PanelsConfig::default(),
+            history_zoom: 1,
+            history_pan: 0,
+        }
+    }
+
+    /// Applies event and returns new state, event which makes no sense in current state is ignored
+    pub fn transition(&mut self, event: Event) -> AppState {
+        match (event, self.state.clone()) {
+            (Event::Open(s), current) if !current.is_dialog() && !s.is_dialog() => self.navigate(s),
+            (Event::Back, current) if !current.is_dialog() => {
+                if !self.back() {
+                    self.quit();
+                }
+            }
+            (Event::Quit, _) => self.quit(),
+            (Event::Ask(action), current) if !current.is_dialog() => {
+                self.change_state(AppState::Confirm { action })
+            }
+            (Event::Prompt(purpose), current) if current == purpose.action().view() => {
+                self.change_state(AppState::Input { purpose })
+            }
+            (Event::Submit, AppState::Input { purpose }) if purpose.confirm() => {
+                self.change_state(AppState::Confirm {
+                    action: purpose.action(),
+                })
+            }
+            (Event::Submit, AppState::Input { purpose }) => {
+                self.pending = Some(purpose.action());
+                self.change_state(purpose.action().view());
+            }
+            (
+                Event::Accept,
+                AppState::Confirm {
+                    action: Action::Quit,
+                },
+            ) => self.running = false,
+            (Event::Accept, AppState::Confirm { action }) => {
+                self.pending = Some(action);
+                self.change_state(action.view());
+            }
+            // quit dialog is opened from any view, so it returns there
+            (
+                Event::Cancel,
+                AppState::Confirm {
+                    action: Action::Quit,
+                },
+            ) => {
+                self.back();
+            }
+            (Event::Cancel, AppState::Confirm { action }) => {
+                self.take_input();
+                self.change_state(action.view());
+            }
+            (Event::Cancel, AppState::Input { purpose }) => {
+                self.take_input();
+                self.change_state(purpose.action().view());
+            }
+            (Event::Dismiss, AppState::Error(_)) => {
+                if !self.back() {
+                    self.change_state(AppState::Main);
+                }
+            }
+            (event, state) => debug!(%state, ?event, "transition ignored"),
+        }
+        self.state.clone()
+    }
+
+    fn change_state(&mut self, s: AppState) {
         self.state = s;
     }
 
-    pub fn change_running(&mut self) {
-        self.running = !self.running;
+    /// Shows error popup over current view, Esc returns to the view.
+    /// Error raised while popup is open is only logged
+    pub fn fail(&mut self, e: AppError) {
+        events::push(Kind::Error, format!("{}: {}", e.header(), e));
+        if !matches!(self.state, AppState::Error(_)) {
+            self.navigate(AppState::Error(e));
+        }
+    }
+
+    /// View drawn on screen, for error popup it is the view under it
+    pub fn view(&self) -> AppState {
+        match &self.state {
+            AppState::Error(_) => self.history.last().cloned().unwrap_or(AppState::Main),
+            s => s.clone(),
+        }
+    }
+
+    /// Opens view and remembers current one for [`ProgramState::back`]
+    fn navigate(&mut self, s: AppState) {
+        if self.state == s {
+            return;
+        }
+        // dialogs are not worth returning to
+        if !self.state.is_dialog() {
+            if self.history.len() == HISTORY_LIMIT {
+                self.history.remove(0);
+            }
+            self.history.push(self.state.clone());
+        }
+        self.change_state(s);
+    }
+
+    /// Returns to previous view, false if there is none
+    fn back(&mut self) -> bool {
+        match self.history.pop() {
+            Some(s) => {
+                self.change_state(s);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Quits right away or asks first when something is running
+    fn quit(&mut self) {
+        if self.busy.is_some() {
+            self.navigate(AppState::Confirm {
+                action: Action::Quit,
+            });
+        } else {
+            self.running = false;
+        }
     }
 
     pub fn toggle_hide_info(&mut self) {
         self.hide_info = !self.hide_info;
     }
+
+    pub fn toggle_compact(&mut self) {
+        self.compact = !self.compact;
+    }
+
+    pub fn history_zoom_in(&mut self) {
+        self.history_zoom = self.history_zoom.saturating_sub(1);
+    }
+
+    pub fn history_zoom_out(&mut self) {
+        self.history_zoom = (self.history_zoom + 1).min(timeline::SPANS.len() - 1);
+    }
+
+    pub fn history_older(&mut self) {
+        self.history_pan = self.history_pan.saturating_add(1);
+    }
+
+    pub fn history_newer(&mut self) {
+        self.history_pan = self.history_pan.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = self.selected.wrapping_add(1);
+    }
+
+    pub fn scan_up(&mut self) {
+        self.scan_selected = self.scan_selected.saturating_sub(1);
+    }
+
+    pub fn scan_down(&mut self) {
+        self.scan_selected = self.scan_selected.saturating_add(1);
+    }
+
+    pub fn phy_up(&mut self) {
+        self.phy_selected = self.phy_selected.saturating_sub(1);
+    }
+
+    pub fn phy_down(&mut self) {
+        self.phy_selected = self.phy_selected.saturating_add(1);
+    }
+
+    pub fn log_up(&mut self) {
+        self.log_selected = self.log_selected.saturating_sub(1);
+    }
+
+    pub fn log_down(&mut self) {
+        self.log_selected = self.log_selected.saturating_add(1);
+    }
+
+    pub fn settings_up(&mut self) {
+        self.settings_selected = self.settings_selected.saturating_sub(1);
+    }
+
+    pub fn settings_down(&mut self) {
+        self.settings_selected = self.settings_selected.saturating_add(1);
+    }
+
+    /// Returns typed text and clears input
+    pub fn take_input(&mut self) -> String {
+        std::mem::take(&mut self.input)
+    }
+
+    /// Returns pending action and removes it
+    pub fn take_action(&mut self) -> Option<Action> {
+        self.pending.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitoring() -> ProgramState {
+        let mut state = ProgramState::new();
+        state.transition(Event::Open(AppState::Monitoring));
+        state
+    }
+
+    #[test]
+    fn open_remembers_previous_view() {
+        let mut state = monitoring();
+        assert_eq!(
+            state.transition(Event::Open(AppState::Scan)),
+            AppState::Scan
+        );
+        assert_eq!(state.history, vec![AppState::Main, AppState::Monitoring]);
+        assert_eq!(state.transition(Event::Back), AppState::Monitoring);
+        assert_eq!(state.transition(Event::Back), AppState::Main);
+    }
+
+    #[test]
+    fn open_same_view_keeps_history() {
+        let mut state = monitoring();
+        state.transition(Event::Open(AppState::Monitoring));
+        assert_eq!(state.history, vec![AppState::Main]);
+    }
+
+    #[test]
+    fn refresh_in_main_opens_monitoring() {
+        let mut state = ProgramState::new();
+        assert_eq!(
+            state.transition(Event::Open(AppState::Monitoring)),
+            AppState::Monitoring
+        );
+    }
+
+    #[test]
+    fn history_is_limited() {
+        let mut state = ProgramState::new();
+        for _ in 0..HISTORY_LIMIT {
+            state.transition(Event::Open(AppState::Scan));
+            state.transition(Event::Open(AppState::Log));
+        }
+        assert_eq!(state.history.len(), HISTORY_LIMIT);
+    }
+
+    #[test]
+    fn back_without_history_quits() {
+        let mut state = ProgramState::new();
+        state.transition(Event::Back);
+        assert!(!state.running);
+    }
+
+    #[test]
+    fn quit_asks_when_busy() {
+        let mut state = monitoring();
+        state.busy = Some("speed test");
+        assert_eq!(
+            state.transition(Event::Quit),
+            AppState::Confirm {
+                action: Action::Quit
+            }
+        );
+        assert_eq!(state.transition(Event::Cancel), AppState::Monitoring);
+        assert!(state.running);
+        state.transition(Event::Quit);
+        state.transition(Event::Accept);
+        assert!(!state.running);
+    }
+
+    #[test]
+    fn dialogs_ignore_view_events() {
+        let mut state = monitoring();
+        state.transition(Event::Ask(Action::ToggleLink));
+        let confirm = AppState::Confirm {
+            action: Action::ToggleLink,
+        };
+        assert_eq!(state.transition(Event::Open(AppState::Scan)), confirm);
+        assert_eq!(state.transition(Event::Back), confirm);
+        assert_eq!(state.transition(Event::Submit), confirm);
+        assert_eq!(state.transition(Event::Dismiss), confirm);
+    }
+
+    #[test]
+    fn dialogs_can_not_be_opened_as_views() {
+        let mut state = ProgramState::new();
+        state.transition(Event::Open(AppState::Error(AppError::NoInterface)));
+        assert_eq!(state.state, AppState::Main);
+    }
+
+    #[test]
+    fn accept_runs_action_in_its_view() {
+        let mut state = ProgramState::new();
+        state.transition(Event::Ask(Action::ToggleLink));
+        assert_eq!(state.transition(Event::Accept), AppState::Monitoring);
+        assert_eq!(state.take_action(), Some(Action::ToggleLink));
+    }
+
+    #[test]
+    fn copy_in_scan_stays_in_scan() {
+        let mut state = ProgramState::new();
+        state.transition(Event::Open(AppState::Scan));
+        state.transition(Event::Ask(Action::CopyScan));
+        assert_eq!(state.transition(Event::Accept), AppState::Scan);
+        assert_eq!(state.take_action(), Some(Action::CopyScan));
+    }
+
+    #[test]
+    fn cancel_drops_action() {
+        let mut state = ProgramState::new();
+        state.transition(Event::Ask(Action::Disconnect));
+        assert_eq!(state.transition(Event::Cancel), AppState::Scan);
+        assert_eq!(state.take_action(), None);
+    }
+
+    #[test]
+    fn prompt_only_in_view_of_action() {
+        let mut state = ProgramState::new();
+        assert_eq!(
+            state.transition(Event::Prompt(Input::TxPower)),
+            AppState::Main
+        );
+        let mut state = monitoring();
+        assert_eq!(
+            state.transition(Event::Prompt(Input::TxPower)),
+            AppState::Input {
+                purpose: Input::TxPower
+            }
+        );
+    }
+
+    #[test]
+    fn submit_asks_for_confirmation_when_needed() {
+        let mut state = monitoring();
+        state.transition(Event::Prompt(Input::TxPower));
+        state.input = "10".to_string();
+        assert_eq!(
+            state.transition(Event::Submit),
+            AppState::Confirm {
+                action: Action::SetTxPower
+            }
+        );
+        assert_eq!(state.transition(Event::Accept), AppState::Monitoring);
+        assert_eq!(state.take_action(), Some(Action::SetTxPower));
+        assert_eq!(state.take_input(), "10");
+    }
+
+    #[test]
+    fn submit_runs_action_without_confirmation() {
+        let mut state = ProgramState::new();
+        state.transition(Event::Open(AppState::QrCode));
+        state.transition(Event::Prompt(Input::WifiPassword));
+        assert_eq!(state.transition(Event::Submit), AppState::QrCode);
+        assert_eq!(state.take_action(), Some(Action::SetPassword));
+    }
+
+    #[test]
+    fn cancel_clears_input() {
+        let mut state = monitoring();
+        state.transition(Event::Prompt(Input::TxPower));
+        state.input = "10".to_string();
+        assert_eq!(state.transition(Event::Cancel), AppState::Monitoring);
+        assert!(state.input.is_empty());
+    }
+
+    #[test]
+    fn error_popup_keeps_view_under_it() {
+        let mut state = monitoring();
+        state.fail(AppError::NoInterface);
+        assert_eq!(state.state, AppState::Error(AppError::NoInterface));
+        assert_eq!(state.view(), AppState::Monitoring);
+        assert_eq!(
+            state.transition(Event::Open(AppState::Scan)),
+            AppState::Error(AppError::NoInterface)
+        );
+        assert_eq!(state.transition(Event::Dismiss), AppState::Monitoring);
+    }
+
+    #[test]
+    fn first_error_stays_on_screen() {
+        let mut state = monitoring();
+        state.fail(AppError::NoInterface);
+        state.fail(AppError::InvalidTxPower("abc".to_string()));
+        assert_eq!(state.state, AppState::Error(AppError::NoInterface));
+        assert_eq!(state.transition(Event::Dismiss), AppState::Monitoring);
+    }
+
+    #[test]
+    fn dismiss_without_history_opens_main() {
+        let mut state = ProgramState::new();
+        state.fail(AppError::NoInterface);
+        state.history.clear();
+        assert_eq!(state.transition(Event::Dismiss), AppState::Main);
+    }
+
+    #[test]
+    fn history_zoom_stays_in_spans() {
+        let mut state = ProgramState::new();
+        for _ in 0..timeline::SPANS.len() + 1 {
+            state.history_zoom_out();
+        }
+        assert_eq!(state.history_zoom, timeline::SPANS.len() - 1);
+        for _ in 0..timeline::SPANS.len() + 1 {
+            state.history_zoom_in();
+        }
+        assert_eq!(state.history_zoom, 0);
+    }
 }
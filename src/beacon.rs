@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use neli_wifi::Bss;
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::{
+    events::{self, Kind},
+    sampler,
+};
+
+/// `[beacon]` section of config, missed beacons of connected AP
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct BeaconConfig {
+    pub enabled: bool,
+    /// Beacon losses detected by driver between two samples which are reported,
+    /// each one is several beacons in a row
+    pub losses: u32,
+}
+
+impl Default for BeaconConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            losses: 1,
+        }
+    }
+}
+
+/// What was seen of one BSS so far
+#[derive(Clone, Debug, Default)]
+struct Beacon {
+    /// TU, 1.024 ms
+    interval: Option<u16>,
+    /// Beacon loss counter of station at previous sample
+    losses: Option<u32>,
+    /// Losses of gap currently being reported
+    gap: Option<u32>,
+}
+
+/// Beacon interval and beacon losses of connected APs
+pub struct Beacons {
+    config: BeaconConfig,
+    seen: HashMap<String, Beacon>,
+}
+
+impl Beacons {
+    pub fn new(config: BeaconConfig) -> Self {
+        Self {
+            config,
+            seen: HashMap::new(),
+        }
+    }
+
+    /// false if station info is not worth reading for beacons
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Compares connected BSS and beacon loss counter of its station with previous state,
+    /// anomalies go to event log. Age of BSS entry is not used, drivers filtering beacons in
+    /// firmware leave it old while nothing is lost.
+    pub fn observe(&mut self, iface: &str, bss: &Bss, beacon_loss: Option<u32>) {
+        if !self.config.enabled {
+            return;
+        }
+        let Some(bssid) = sampler::format_bssid(bss) else {
+            return;
+        };
+        let beacon = self.seen.entry(bssid.clone()).or_default();
+
+        if let Some(interval) = bss.beacon_interval {
+            match beacon.interval.replace(interval) {
+                Some(old) if old != interval => {
+                    warn!(iface, bssid, old, interval, "beacon interval changed");
+                    events::push(
+                        Kind::Beacon,
+                        format!(
+                            "{} beacon interval of {} changed from {} TU to {} TU",
+                            iface, bssid, old, interval
+                        ),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        let Some(total) = beacon_loss else {
+            return;
+        };
+        // counter starts again with new association
+        let lost = total.saturating_sub(beacon.losses.replace(total).unwrap_or(total));
+        if lost >= self.config.losses.max(1) {
+            let gap = beacon.gap.get_or_insert(0);
+            if *gap == 0 {
+                warn!(iface, bssid, lost, "beacons lost");
+                events::push(
+                    Kind::Beacon,
+                    format!(
+                        "{} lost beacons from {} {} times, interference or overloaded AP",
+                        iface, bssid, lost
+                    ),
+                );
+            }
+            *gap += lost;
+        } else if let Some(gap) = beacon.gap.take() {
+            events::push(
+                Kind::Beacon,
+                format!(
+                    "{} receives beacons from {} again after {} beacon losses",
+                    iface, bssid, gap
+                ),
+            );
+        }
+    }
+}
@@ -0,0 +1,163 @@
+#[cfg(feature = "bench")]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::{
+    fmt,
+    sync::{
+        RwLock, RwLockReadGuard, RwLockWriteGuard,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static FRAMES: AtomicU64 = AtomicU64::new(0);
+static LOCK_WAIT: AtomicU64 = AtomicU64::new(0);
+
+/// System allocator which counts allocations of the whole process for `--bench`,
+/// installed only in builds with `bench` feature
+#[cfg(feature = "bench")]
+pub struct Counting;
+
+#[cfg(feature = "bench")]
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(new_size as u64, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+/// Counted by render loop once per frame
+pub fn frame() {
+    FRAMES.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Reads lock, time is counted only when other thread holds it
+#[cfg(feature = "bench")]
+pub fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    if let Ok(guard) = lock.try_read() {
+        return guard;
+    }
+    let start = Instant::now();
+    let guard = lock.read().unwrap();
+    waited(start);
+    guard
+}
+
+/// Writes lock, time is counted only when other thread holds it
+#[cfg(feature = "bench")]
+pub fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    if let Ok(guard) = lock.try_write() {
+        return guard;
+    }
+    let start = Instant::now();
+    let guard = lock.write().unwrap();
+    waited(start);
+    guard
+}
+
+#[cfg(feature = "bench")]
+fn waited(start: Instant) {
+    LOCK_WAIT.fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+}
+
+/// Plain read without `bench` feature
+#[cfg(not(feature = "bench"))]
+#[inline]
+pub fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap()
+}
+
+/// Plain write without `bench` feature
+#[cfg(not(feature = "bench"))]
+#[inline]
+pub fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap()
+}
+
+/// Counters since process start
+#[derive(Clone, Copy)]
+pub struct Counters {
+    at: Instant,
+    frames: u64,
+    lock_wait: u64,
+    allocations: u64,
+    allocated: u64,
+}
+
+impl Counters {
+    pub fn now() -> Self {
+        Self {
+            at: Instant::now(),
+            frames: FRAMES.load(Ordering::Relaxed),
+            lock_wait: LOCK_WAIT.load(Ordering::Relaxed),
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            allocated: ALLOCATED.load(Ordering::Relaxed),
+        }
+    }
+
+    /// What happened between `start` and now
+    pub fn since(start: Counters) -> Report {
+        let end = Counters::now();
+        Report {
+            elapsed: end.at - start.at,
+            frames: end.frames - start.frames,
+            lock_wait: Duration::from_nanos(end.lock_wait - start.lock_wait),
+            allocations: end.allocations - start.allocations,
+            allocated: end.allocated - start.allocated,
+        }
+    }
+}
+
+/// Result of `--bench`, printed to stdout
+pub struct Report {
+    elapsed: Duration,
+    frames: u64,
+    lock_wait: Duration,
+    allocations: u64,
+    allocated: u64,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frames = self.frames.max(1);
+        writeln!(
+            f,
+            "frames: {} in {:.1} s, {:.1} frames/s",
+            self.frames,
+            self.elapsed.as_secs_f64(),
+            self.frames as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+        )?;
+        if cfg!(not(feature = "bench")) {
+            return write!(
+                f,
+                "lock wait and allocations are counted with `bench` feature only"
+            );
+        }
+        writeln!(
+            f,
+            "lock wait: {:.3} ms, {:.1} µs per frame",
+            self.lock_wait.as_secs_f64() * 1000.0,
+            self.lock_wait.as_secs_f64() * 1_000_000.0 / frames as f64
+        )?;
+        // background threads allocate too, so it is the whole process
+        write!(
+            f,
+            "allocations: {}, {} per frame, {} KiB per frame",
+            self.allocations,
+            self.allocations / frames,
+            self.allocated / frames / 1024
+        )
+    }
+}
@@ -0,0 +1,89 @@
+use std::{
+    collections::HashMap,
+    io,
+    sync::{Arc, RwLock},
+    thread::sleep,
+    time::Duration,
+};
+
+use tracing::{debug, info};
+use zbus::{
+    blocking::{Connection, Proxy},
+    zvariant::{OwnedObjectPath, OwnedValue},
+};
+
+const DEST: &str = "org.bluez";
+
+/// Connected devices change rarely, bluez is asked once per this time
+const REFRESH: Duration = Duration::from_secs(30);
+
+/// 16-bit service classes of A2DP, HFP and HSP, audio streams are what hurts 2.4 GHz wifi
+const AUDIO_SERVICES: [&str; 6] = ["110a", "110b", "111e", "111f", "1108", "1112"];
+
+type Objects = HashMap<OwnedObjectPath, HashMap<String, HashMap<String, OwnedValue>>>;
+
+/// Names of connected Bluetooth audio devices
+fn audio_devices(conn: &Connection) -> io::Result<Vec<String>> {
+    let objects: Objects = Proxy::new(conn, DEST, "/", "org.freedesktop.DBus.ObjectManager")
+        .and_then(|p| p.call("GetManagedObjects", &()))
+        .map_err(io::Error::other)?;
+    let mut names: Vec<String> = objects
+        .values()
+        .filter_map(|interfaces| interfaces.get("org.bluez.Device1"))
+        .filter(|device| {
+            device
+                .get("Connected")
+                .and_then(|v| bool::try_from(v).ok())
+                .unwrap_or(false)
+        })
+        .filter(|device| {
+            device
+                .get("UUIDs")
+                .and_then(|v| Vec::<String>::try_from(v.try_clone().ok()?).ok())
+                .is_some_and(|uuids| {
+                    // base UUID is 0000xxxx-0000-1000-8000-00805f9b34fb
+                    uuids
+                        .iter()
+                        .any(|u| AUDIO_SERVICES.iter().any(|s| u.get(4..8) == Some(*s)))
+                })
+        })
+        .map(|device| {
+            device
+                .get("Alias")
+                .or_else(|| device.get("Name"))
+                .and_then(|v| String::try_from(v.try_clone().ok()?).ok())
+                .unwrap_or_else(|| "unnamed device".to_string())
+        })
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Starts thread which lists connected Bluetooth audio devices, empty without bluez
+pub fn spawn() -> Arc<RwLock<Vec<String>>> {
+    let devices = Arc::new(RwLock::new(Vec::new()));
+    let shared = devices.clone();
+    std::thread::spawn(move || {
+        let conn = match Connection::system() {
+            Ok(conn) => conn,
+            Err(e) => {
+                debug!(error = %e, "no system bus for bluez");
+                return;
+            }
+        };
+        loop {
+            match audio_devices(&conn) {
+                Ok(names) => {
+                    if *shared.read().unwrap() != names {
+                        info!(?names, "connected bluetooth audio devices changed");
+                    }
+                    *shared.write().unwrap() = names;
+                }
+                // bluez may be not installed or bluetooth turned off
+                Err(e) => debug!(error = %e, "failed to list bluetooth devices"),
+            }
+            sleep(REFRESH);
+        }
+    });
+    devices
+}
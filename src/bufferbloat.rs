@@ -0,0 +1,196 @@
+use std::{
+    fs::OpenOptions,
+    io::{self, Write},
+    net::Ipv4Addr,
+    path::Path,
+    sync::{Arc, RwLock},
+    thread::sleep,
+    time::Duration,
+};
+
+use tracing::{info, warn};
+
+use crate::{
+    CONFIGURATION, history, ipconfig, ping,
+    sampler::Sample,
+    speedtest::{self, SpeedTestConfig},
+};
+
+const HEADER: &str =
+    "time,iface,bssid,signal,frequency,connected,idle_ms,loaded_ms,bits_per_second,grade";
+
+/// Pings of idle link before load starts
+const IDLE_PINGS: usize = 5;
+
+/// Gap between pings, shorter than speed test, so load gets enough of them
+const PING_GAP: Duration = Duration::from_millis(200);
+
+/// Gateway latency without and with load
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bufferbloat {
+    pub idle: Duration,
+    pub loaded: Duration,
+    pub bps: f64,
+}
+
+impl Bufferbloat {
+    /// Latency added by queues while link is saturated
+    pub fn added(&self) -> Duration {
+        self.loaded.saturating_sub(self.idle)
+    }
+
+    /// Letter grade of added latency, same steps as common online tests
+    pub fn grade(&self) -> &'static str {
+        match self.added().as_millis() {
+            ..5 => "A+",
+            5..30 => "A",
+            30..60 => "B",
+            60..200 => "C",
+            200..400 => "D",
+            _ => "F",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BufferbloatStatus {
+    Idle,
+    Running,
+    Done(Bufferbloat),
+    Failed(String),
+}
+
+impl std::fmt::Display for BufferbloatStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BufferbloatStatus::Idle => write!(f, "press 'B' to run"),
+            BufferbloatStatus::Running => write!(f, "running…"),
+            BufferbloatStatus::Done(b) => write!(
+                f,
+                "grade {}, {:.0} ms idle, +{:.0} ms under load",
+                b.grade(),
+                b.idle.as_secs_f64() * 1000.0,
+                b.added().as_secs_f64() * 1000.0
+            ),
+            BufferbloatStatus::Failed(e) => write!(f, "failed: {}", e),
+        }
+    }
+}
+
+/// On-demand latency under load test, load is the configured speed test
+pub struct BufferbloatTest {
+    config: SpeedTestConfig,
+    status: Arc<RwLock<BufferbloatStatus>>,
+}
+
+impl BufferbloatTest {
+    pub fn new(config: SpeedTestConfig) -> Self {
+        Self {
+            config,
+            status: Arc::new(RwLock::new(BufferbloatStatus::Idle)),
+        }
+    }
+
+    pub fn status(&self) -> BufferbloatStatus {
+        self.status.read().unwrap().clone()
+    }
+
+    /// Starts test if none is running, result is written to `bufferbloat.csv` together with `sample`
+    pub fn start(&self, sample: Option<Sample>) {
+        let mut status = self.status.write().unwrap();
+        if *status == BufferbloatStatus::Running {
+            return;
+        }
+        let Some(server) = self.config.server.clone() else {
+            *status = BufferbloatStatus::Failed("speed test server is not configured".to_string());
+            return;
+        };
+        let Some(gateway) = ipconfig::default_gateway(None) else {
+            *status = BufferbloatStatus::Failed("no default gateway".to_string());
+            return;
+        };
+        *status = BufferbloatStatus::Running;
+        drop(status);
+
+        let config = self.config.clone();
+        let shared = self.status.clone();
+        std::thread::spawn(move || {
+            info!(server, %gateway, "starting bufferbloat test");
+            let res = match measure(&server, &config, gateway) {
+                Ok(b) => {
+                    info!(
+                        idle_ms = b.idle.as_millis(),
+                        loaded_ms = b.loaded.as_millis(),
+                        grade = b.grade(),
+                        "bufferbloat test finished"
+                    );
+                    if let Some(sample) = sample
+                        && let Err(e) = record(&sample, &b)
+                    {
+                        warn!(error = %e, "failed to write bufferbloat result");
+                    }
+                    BufferbloatStatus::Done(b)
+                }
+                Err(e) => {
+                    warn!(error = %e, "bufferbloat test failed");
+                    BufferbloatStatus::Failed(e.to_string())
+                }
+            };
+            *shared.write().unwrap() = res;
+        });
+    }
+}
+
+/// Median gateway latency before and during speed test
+fn measure(server: &str, config: &SpeedTestConfig, gateway: Ipv4Addr) -> io::Result<Bufferbloat> {
+    let mut seq: u16 = 0;
+    let mut idle = Vec::new();
+    for _ in 0..IDLE_PINGS {
+        seq = seq.wrapping_add(1);
+        idle.extend(ping::ping(gateway, seq)?);
+        sleep(PING_GAP);
+    }
+    let load = {
+        let server = server.to_string();
+        let config = config.clone();
+        std::thread::spawn(move || speedtest::run(&server, &config))
+    };
+    let mut loaded = Vec::new();
+    while !load.is_finished() {
+        seq = seq.wrapping_add(1);
+        // lost reply under load is the worst case, count it as full timeout
+        loaded.push(ping::ping(gateway, seq)?.unwrap_or(Duration::from_secs(1)));
+        sleep(PING_GAP);
+    }
+    let bps = load
+        .join()
+        .map_err(|_| io::Error::other("speed test panicked"))??;
+    Ok(Bufferbloat {
+        idle: median(idle).ok_or_else(|| io::Error::other("gateway does not reply"))?,
+        loaded: median(loaded).ok_or_else(|| io::Error::other("load finished too early"))?,
+        bps,
+    })
+}
+
+fn median(mut values: Vec<Duration>) -> Option<Duration> {
+    values.sort();
+    values.get(values.len() / 2).copied()
+}
+
+fn record(sample: &Sample, b: &Bufferbloat) -> io::Result<()> {
+    let path = Path::new(CONFIGURATION.as_str()).join("bufferbloat.csv");
+    let new = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if new {
+        writeln!(file, "{}", HEADER)?;
+    }
+    writeln!(
+        file,
+        "{},{:.1},{:.1},{:.0},{}",
+        history::row(sample),
+        b.idle.as_secs_f64() * 1000.0,
+        b.loaded.as_secs_f64() * 1000.0,
+        b.bps,
+        b.grade()
+    )
+}
@@ -0,0 +1,86 @@
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    style::{Color, Style},
+    widgets::{Block, Borders, Sparkline},
+};
+
+use crate::i18n;
+
+/// Amount of points kept for chart
+const CAPACITY: usize = 300;
+
+/// Amount of previous points trend is compared with
+const TREND: usize = 5;
+
+/// Recent signal levels of connected interface
+pub struct SignalChart {
+    points: Vec<u64>,
+    last: Option<Instant>,
+}
+
+impl SignalChart {
+    pub fn new() -> Self {
+        Self {
+            points: Vec::with_capacity(CAPACITY),
+            last: None,
+        }
+    }
+
+    /// Adds signal in dBm, but not more often than once per `interval`
+    pub fn push(&mut self, signal: i32, interval: Duration) {
+        if self.last.is_some_and(|l| l.elapsed() < interval) {
+            return;
+        }
+        self.last = Some(Instant::now());
+        if self.points.len() == CAPACITY {
+            self.points.remove(0);
+        }
+        // -100 dBm and lower are drawn as empty bar
+        self.points.push((signal + 100).max(0) as u64);
+    }
+
+    /// Removes all points, so values from before a gap are not joined with new ones
+    pub fn clear(&mut self) {
+        self.points.clear();
+        self.last = None;
+    }
+
+    /// Points in dBm, the oldest first
+    pub fn levels(&self) -> Vec<i32> {
+        self.points.iter().map(|p| *p as i32 - 100).collect()
+    }
+
+    /// Arrow showing whether signal rises or falls compared to a few samples before
+    pub fn trend(&self) -> &'static str {
+        let Some(last) = self.points.last() else {
+            return " ";
+        };
+        let before =
+            &self.points[self.points.len().saturating_sub(TREND + 1)..self.points.len() - 1];
+        if before.is_empty() {
+            return "→";
+        }
+        let average = before.iter().sum::<u64>() as f64 / before.len() as f64;
+        match *last as f64 - average {
+            d if d >= 2.0 => "↑",
+            d if d <= -2.0 => "↓",
+            _ => "→",
+        }
+    }
+
+    /// Returns sparkline with the newest points which fit into `width`
+    pub fn widget(&self, width: u16) -> Sparkline<'_> {
+        let visible = usize::from(width.saturating_sub(2));
+        let start = self.points.len().saturating_sub(visible);
+        Sparkline::default()
+            .block(
+                Block::default()
+                    .title(i18n::tr("signal"))
+                    .borders(Borders::ALL),
+            )
+            .data(&self.points[start..])
+            .max(100)
+            .style(Style::default().fg(Color::Green))
+    }
+}
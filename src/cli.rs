@@ -0,0 +1,147 @@
+use std::{net::SocketAddr, path::PathBuf};
+
+use clap::Parser;
+
+use crate::{
+    config::{Config, LogLevel, LogOutput},
+    indicator::StatusFormat,
+};
+
+/// Terminal monitor of wifi signal level
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Most verbose log level which is written
+    #[arg(long, value_enum)]
+    pub log_level: Option<LogLevel>,
+
+    /// Write log to this file instead of rotated files in config directory, '-' means stderr
+    #[arg(long, conflicts_with = "no_log")]
+    pub log_file: Option<PathBuf>,
+
+    /// Disable logging
+    #[arg(long)]
+    pub no_log: bool,
+
+    /// Run without TUI, sampling continuously into history
+    #[arg(long)]
+    pub daemon: bool,
+
+    /// Write samples to stdout as JSON, one object per line, without TUI
+    #[arg(long, conflicts_with_all = ["daemon", "status_format", "tmux"])]
+    pub stream: bool,
+
+    /// Send JSON request to running daemon, e.g. '{"cmd":"status"}', and print its reply
+    #[arg(long, value_name = "JSON", conflicts_with_all = ["daemon", "stream"])]
+    pub query: Option<String>,
+
+    /// Serve Prometheus metrics on this address in daemon mode
+    #[arg(long, requires = "daemon")]
+    pub exporter: Option<SocketAddr>,
+
+    /// Serve status, history and scan as JSON on this address in daemon mode,
+    /// address other than loopback needs `token` in [http] section of config
+    #[arg(long, requires = "daemon")]
+    pub http: Option<SocketAddr>,
+
+    /// Show interfaces of these agents (`host:port` of daemons with --http), comma separated
+    #[arg(long, value_delimiter = ',', conflicts_with = "daemon")]
+    pub connect: Vec<String>,
+
+    /// Skip setup questions shown on first launch
+    #[arg(long)]
+    pub no_wizard: bool,
+
+    /// Print one line status for desktop bar and exit
+    #[arg(long, value_enum, conflicts_with = "daemon")]
+    pub status_format: Option<StatusFormat>,
+
+    /// Print nothing, exit with 0 if connected and healthy, 1 if signal or link quality is
+    /// poor, 2 if not associated, 3 if there is no wifi interface and 4 if config or --replay
+    /// file can't be read
+    #[arg(long, short, conflicts_with_all = ["daemon", "stream", "query", "status_format", "plain", "tmux", "keys", "bench"])]
+    pub quiet: bool,
+
+    /// Print Nagios/Icinga plugin line with perfdata and exit with 0 OK, 1 WARNING, 2 CRITICAL
+    /// or 3 UNKNOWN
+    #[arg(long, conflicts_with_all = ["daemon", "stream", "query", "status_format", "plain", "tmux", "keys", "bench", "quiet"])]
+    pub check: bool,
+
+    /// Signal in dBm below which --check warns
+    #[arg(long, value_name = "DBM", default_value_t = -70, allow_negative_numbers = true, requires = "check")]
+    pub warn: i32,
+
+    /// Signal in dBm below which --check is critical, also when not associated
+    #[arg(long, value_name = "DBM", default_value_t = -80, allow_negative_numbers = true, requires = "check")]
+    pub crit: i32,
+
+    /// Keep printing status every interval instead of exiting
+    #[arg(long, requires = "status_format")]
+    pub follow: bool,
+
+    /// Print changes of state as plain lines to stdout instead of TUI, for screen readers
+    #[arg(long, conflicts_with_all = ["daemon", "stream", "status_format", "tmux"])]
+    pub plain: bool,
+
+    /// Print colored summary for tmux `status-right` and exit, nothing is logged
+    #[arg(long, conflicts_with_all = ["daemon", "status_format"])]
+    pub tmux: bool,
+
+    /// Answer wifi queries with frames recorded by --record instead of netlink
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["daemon", "stream", "status_format", "tmux", "plain"])]
+    pub replay: Option<PathBuf>,
+
+    /// Append wifi interfaces and BSS lists of every frame to this file for --replay
+    #[arg(long, value_name = "FILE", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Draw into memory pressing these keys one per frame, e.g. 'm<Tab><Esc>', then print the
+    /// last screen and exit
+    #[arg(long, value_name = "KEYS")]
+    pub keys: Option<String>,
+
+    /// Draw monitoring view into memory without pause for this many seconds, then print frames
+    /// per second, lock wait and allocations of builds with `bench` feature, --keys opens
+    /// another view
+    #[arg(long, value_name = "SECONDS", conflicts_with_all = ["daemon", "stream", "status_format", "tmux", "plain"])]
+    pub bench: Option<u64>,
+
+    /// Print shell completions ('completions bash', also zsh, fish, elvish and powershell) or
+    /// man page ('man') and exit
+    #[arg(long, num_args = 1..=2, value_names = ["WHAT", "SHELL"])]
+    pub generate: Vec<String>,
+}
+
+impl Cli {
+    /// true if no TUI is started, so stderr is free for log
+    pub fn headless(&self) -> bool {
+        self.daemon
+            || self.stream
+            || self.status_format.is_some()
+            || self.tmux
+            || self.plain
+            || self.keys.is_some()
+            || self.bench.is_some()
+    }
+
+    /// Overrides values from config file with command line options
+    pub fn apply(&self, config: &mut Config) {
+        if let Some(level) = self.log_level {
+            config.log.level = level;
+        }
+        if let Some(file) = &self.log_file {
+            if file.as_os_str() == "-" {
+                config.log.output = LogOutput::Stderr;
+            } else {
+                config.log.output = LogOutput::File;
+                config.log.file = Some(file.clone());
+            }
+        }
+        if self.no_log {
+            config.log.output = LogOutput::Off;
+        }
+        if self.plain {
+            config.display.plain = true;
+        }
+    }
+}
@@ -0,0 +1,55 @@
+#[cfg(feature = "clipboard")]
+use std::sync::Mutex;
+use std::{
+    env,
+    io::{self, Write},
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use tracing::debug;
+
+/// X11 clipboard keeps its content only while owner lives, so it is kept until exit
+#[cfg(feature = "clipboard")]
+static CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
+
+/// Copies `text` into system clipboard, returns how it was done.
+/// Over SSH the local terminal gets OSC 52 escape sequence, remote clipboard would be useless
+pub fn copy(text: &str) -> io::Result<&'static str> {
+    #[cfg(feature = "clipboard")]
+    if env::var_os("SSH_CONNECTION").is_none() {
+        match system(text) {
+            Ok(()) => return Ok("clipboard"),
+            // no display server, terminal may still support OSC 52
+            Err(e) => debug!(error = %e, "failed to use system clipboard"),
+        }
+    }
+    osc52(text)?;
+    Ok("terminal")
+}
+
+#[cfg(feature = "clipboard")]
+fn system(text: &str) -> io::Result<()> {
+    let mut clipboard = CLIPBOARD.lock().unwrap();
+    if clipboard.is_none() {
+        *clipboard = Some(arboard::Clipboard::new().map_err(io::Error::other)?);
+    }
+    clipboard
+        .as_mut()
+        .expect("clipboard is opened above")
+        .set_text(text)
+        .map_err(io::Error::other)
+}
+
+/// Asks terminal to set clipboard, tmux passes it through only when wrapped
+fn osc52(text: &str) -> io::Result<()> {
+    let sequence = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+    let sequence = if env::var_os("TMUX").is_some() {
+        debug!("wrapping OSC 52 for tmux");
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    };
+    let mut stdout = io::stdout();
+    stdout.write_all(sequence.as_bytes())?;
+    stdout.flush()
+}
@@ -0,0 +1,177 @@
+use std::{
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use parquet::{
+    basic::Compression,
+    data_type::{BoolType, ByteArray, ByteArrayType, Int32Type, Int64Type},
+    errors::ParquetError,
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    schema::parser::parse_message_type,
+};
+use serde::Deserialize;
+use tracing::info;
+
+use crate::sampler::Sample;
+
+const SCHEMA: &str = "
+message sample {
+    required int64 time (TIMESTAMP(MILLIS, true));
+    required binary iface (STRING);
+    optional binary bssid (STRING);
+    required int32 signal;
+    optional int32 frequency (INTEGER(32, false));
+    required boolean connected;
+    optional int32 bitrate (INTEGER(32, false));
+    optional int32 score (INTEGER(8, false));
+}";
+
+/// `[parquet]` section of config, used in daemon mode
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ParquetConfig {
+    /// Writes samples into `parquet/` in the configuration directory
+    pub enabled: bool,
+    /// Samples in one file, unfinished batch is lost when daemon is killed
+    pub batch: usize,
+}
+
+impl Default for ParquetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            batch: 3600,
+        }
+    }
+}
+
+/// Collects samples and writes every full batch as separate Parquet file
+pub struct ParquetWriter {
+    dir: PathBuf,
+    batch: usize,
+    samples: Vec<Sample>,
+}
+
+impl ParquetWriter {
+    pub fn new(dir: &Path, config: &ParquetConfig) -> io::Result<Self> {
+        let dir = dir.join("parquet");
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            batch: config.batch.max(1),
+            samples: Vec::new(),
+        })
+    }
+
+    pub fn append(&mut self, sample: &Sample) -> io::Result<()> {
+        self.samples.push(sample.clone());
+        if self.samples.len() >= self.batch {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Writes collected samples into `history-<time of first sample>.parquet`
+    pub fn flush(&mut self) -> io::Result<()> {
+        let Some(first) = self.samples.first() else {
+            return Ok(());
+        };
+        let path = self.dir.join(format!(
+            "history-{}.parquet",
+            first.time.format("%Y%m%d-%H%M%S")
+        ));
+        write(&path, &self.samples).map_err(io::Error::other)?;
+        info!(path = %path.display(), samples = self.samples.len(), "parquet batch written");
+        self.samples.clear();
+        Ok(())
+    }
+}
+
+/// Writes samples as one row group
+fn write(path: &Path, samples: &[Sample]) -> Result<(), ParquetError> {
+    let schema = Arc::new(parse_message_type(SCHEMA)?);
+    let props = Arc::new(
+        WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build(),
+    );
+    let mut writer = SerializedFileWriter::new(File::create(path)?, schema, props)?;
+    let mut group = writer.next_row_group()?;
+    let mut i = 0;
+    while let Some(mut column) = group.next_column()? {
+        match i {
+            0 => {
+                let time: Vec<i64> = samples.iter().map(|s| s.time.timestamp_millis()).collect();
+                column.typed::<Int64Type>().write_batch(&time, None, None)?;
+            }
+            1 => {
+                let iface: Vec<ByteArray> =
+                    samples.iter().map(|s| s.iface.as_str().into()).collect();
+                column
+                    .typed::<ByteArrayType>()
+                    .write_batch(&iface, None, None)?;
+            }
+            2 => {
+                let (bssid, levels) =
+                    optional(samples, |s| s.bssid.as_deref().map(ByteArray::from));
+                column
+                    .typed::<ByteArrayType>()
+                    .write_batch(&bssid, Some(&levels), None)?;
+            }
+            3 => {
+                let signal: Vec<i32> = samples.iter().map(|s| s.signal).collect();
+                column
+                    .typed::<Int32Type>()
+                    .write_batch(&signal, None, None)?;
+            }
+            4 => {
+                let (frequency, levels) = optional(samples, |s| s.frequency.map(|f| f as i32));
+                column
+                    .typed::<Int32Type>()
+                    .write_batch(&frequency, Some(&levels), None)?;
+            }
+            5 => {
+                let connected: Vec<bool> = samples.iter().map(|s| s.connected).collect();
+                column
+                    .typed::<BoolType>()
+                    .write_batch(&connected, None, None)?;
+            }
+            6 => {
+                let (bitrate, levels) = optional(samples, |s| s.bitrate.map(|b| b as i32));
+                column
+                    .typed::<Int32Type>()
+                    .write_batch(&bitrate, Some(&levels), None)?;
+            }
+            _ => {
+                let (score, levels) = optional(samples, |s| s.score.map(i32::from));
+                column
+                    .typed::<Int32Type>()
+                    .write_batch(&score, Some(&levels), None)?;
+            }
+        }
+        column.close()?;
+        i += 1;
+    }
+    group.close()?;
+    writer.close()?;
+    Ok(())
+}
+
+/// Present values and definition levels of optional column
+fn optional<T>(samples: &[Sample], value: impl Fn(&Sample) -> Option<T>) -> (Vec<T>, Vec<i16>) {
+    let mut values = Vec::new();
+    let mut levels = Vec::with_capacity(samples.len());
+    for s in samples {
+        match value(s) {
+            Some(v) => {
+                values.push(v);
+                levels.push(1);
+            }
+            None => levels.push(0),
+        }
+    }
+    (values, levels)
+}
@@ -0,0 +1,56 @@
+use ratatui::{
+    Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{display, snapshot::Snapshot};
+
+/// What compact monitoring shows about selected interface
+pub struct Compact<'a> {
+    pub iface: &'a str,
+    pub ssid: Option<String>,
+    /// Signal in dBm, None if not connected
+    pub signal: Option<i32>,
+    pub trend: &'static str,
+    /// Header of open error popup
+    pub error: Option<&'a str>,
+}
+
+/// Draws SSID with signal on the first line and interface or error on the second one,
+/// second line is dropped if there is no room for it
+pub fn draw(f: &mut Frame, c: &Compact) {
+    let signal = match c.signal {
+        Some(dbm) => Span::styled(
+            format!(
+                "{} {} {}",
+                display::signal_value(dbm),
+                display::unit(),
+                c.trend
+            ),
+            Style::default()
+                .fg(display::signal_color(dbm))
+                .add_modifier(Modifier::BOLD),
+        ),
+        None => Span::styled("not connected", Style::default().fg(Color::Gray)),
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::raw(c.ssid.as_deref().unwrap_or("-").to_string()),
+        Span::raw(" "),
+        signal,
+    ])];
+    lines.push(match c.error {
+        Some(e) => Line::from(Span::styled(
+            format!("{}, Esc closes", e),
+            Style::default().fg(Color::Red),
+        )),
+        None => Line::from(Span::styled(
+            format!("{} 'z' full view", c.iface),
+            Style::default().fg(Color::Gray),
+        )),
+    });
+    f.render_widget(Paragraph::new(lines), f.area());
+    f.render_widget(display::AsciiOnly, f.area());
+    f.render_widget(Snapshot, f.area());
+}
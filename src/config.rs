@@ -0,0 +1,300 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+use serde::Deserialize;
+use toml_edit::{DocumentMut, TableLike, table, value};
+
+use crate::{
+    alert::AlertRule,
+    beacon::BeaconConfig,
+    connectivity::ConnectivityConfig,
+    deauth::DeauthConfig,
+    display::DisplayConfig,
+    distance::DistanceConfig,
+    dns::DnsConfig,
+    frames::FramesConfig,
+    history::RetentionConfig,
+    hooks::HooksConfig,
+    http::HttpConfig,
+    panels::{LayoutPreset, PanelsConfig},
+    profile::Profile,
+    report::ReportConfig,
+    scan::ScanConfig,
+    speedtest::SpeedTestConfig,
+    statsd::StatsdConfig,
+    wakeup::EventsConfig,
+};
+
+/// User configuration, read from `config.toml` inside the configuration directory
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Hides mac addresses in the log file the same way as 'h' hides them on screen
+    pub privacy: bool,
+    /// Seconds between two samples in daemon mode and on signal chart
+    pub interval: u64,
+    /// Asks before quitting while speed test or frame capture is running
+    pub confirm_quit: bool,
+    /// Appends samples to `history.csv` in the configuration directory,
+    /// if not set daemon records them and TUI does not
+    pub history: Option<bool>,
+    pub retention: RetentionConfig,
+    pub display: DisplayConfig,
+    pub log: LogConfig,
+    pub interfaces: InterfacesConfig,
+    pub connectivity: ConnectivityConfig,
+    pub dns: DnsConfig,
+    pub scan: ScanConfig,
+    pub speedtest: SpeedTestConfig,
+    pub frames: FramesConfig,
+    pub deauth: DeauthConfig,
+    pub events: EventsConfig,
+    /// `[[alert]]` rules checked for every sample
+    pub alert: Vec<AlertRule>,
+    pub hooks: HooksConfig,
+    pub distance: DistanceConfig,
+    pub beacon: BeaconConfig,
+    /// `[[profile]]` expected quality of known networks
+    pub profile: Vec<Profile>,
+    pub report: ReportConfig,
+    /// `[[layout]]` presets of monitoring panels
+    pub layout: Vec<LayoutPreset>,
+    pub panels: PanelsConfig,
+    pub statsd: StatsdConfig,
+    pub http: HttpConfig,
+    #[cfg(feature = "parquet")]
+    pub parquet: crate::columnar::ParquetConfig,
+    #[cfg(feature = "email")]
+    pub email: crate::email::EmailConfig,
+    #[cfg(feature = "otlp")]
+    pub otlp: crate::otlp::OtlpConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            privacy: false,
+            interval: 1,
+            confirm_quit: true,
+            history: None,
+            retention: RetentionConfig::default(),
+            display: DisplayConfig::default(),
+            log: LogConfig::default(),
+            interfaces: InterfacesConfig::default(),
+            connectivity: ConnectivityConfig::default(),
+            dns: DnsConfig::default(),
+            scan: ScanConfig::default(),
+            speedtest: SpeedTestConfig::default(),
+            frames: FramesConfig::default(),
+            deauth: DeauthConfig::default(),
+            events: EventsConfig::default(),
+            alert: Vec::new(),
+            hooks: HooksConfig::default(),
+            distance: DistanceConfig::default(),
+            beacon: BeaconConfig::default(),
+            profile: Vec::new(),
+            report: ReportConfig::default(),
+            layout: Vec::new(),
+            panels: PanelsConfig::default(),
+            statsd: StatsdConfig::default(),
+            http: HttpConfig::default(),
+            #[cfg(feature = "parquet")]
+            parquet: crate::columnar::ParquetConfig::default(),
+            #[cfg(feature = "email")]
+            email: crate::email::EmailConfig::default(),
+            #[cfg(feature = "otlp")]
+            otlp: crate::otlp::OtlpConfig::default(),
+        }
+    }
+}
+
+/// `[log]` section of config
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct LogConfig {
+    /// Where log is written
+    pub output: LogOutput,
+    /// Most verbose level which is written
+    pub level: LogLevel,
+    /// Fixed log file instead of rotated `run-<timestamp>.log` files
+    pub file: Option<PathBuf>,
+    /// Log file is rotated after reaching this size in bytes
+    pub max_size: u64,
+    /// Log file is rotated after this amount of hours
+    pub max_age_hours: u64,
+    /// Log files older than this amount of days are removed on startup
+    pub retention_days: u64,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            output: LogOutput::File,
+            level: LogLevel::Info,
+            file: None,
+            max_size: 10 * 1024 * 1024,
+            max_age_hours: 24,
+            retention_days: 7,
+        }
+    }
+}
+
+/// `[interfaces]` section of config
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct InterfacesConfig {
+    /// Hides `p2p-dev-*` devices of wifi direct
+    pub hide_p2p: bool,
+    /// Hides interfaces in monitor mode
+    pub hide_monitor: bool,
+    /// Interface selected at start
+    pub preferred: Option<String>,
+}
+
+impl Default for InterfacesConfig {
+    fn default() -> Self {
+        Self {
+            hide_p2p: true,
+            hide_monitor: true,
+            preferred: None,
+        }
+    }
+}
+
+/// Destination of log records
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogOutput {
+    /// `run-<timestamp>.log` files in the configuration directory
+    File,
+    /// systemd journal
+    Journald,
+    /// syslog daemon listening on `/dev/log`
+    Syslog,
+    /// standard error, not available while TUI is drawn
+    Stderr,
+    /// logging is disabled
+    Off,
+}
+
+/// Verbosity of log
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevel> for tracing::Level {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => tracing::Level::ERROR,
+            LogLevel::Warn => tracing::Level::WARN,
+            LogLevel::Info => tracing::Level::INFO,
+            LogLevel::Debug => tracing::Level::DEBUG,
+            LogLevel::Trace => tracing::Level::TRACE,
+        }
+    }
+}
+
+impl Config {
+    /// true if daemon appends samples to history
+    pub fn daemon_history(&self) -> bool {
+        self.history.unwrap_or(true)
+    }
+
+    /// true if TUI appends samples to history while it runs
+    pub fn tui_history(&self) -> bool {
+        self.history.unwrap_or(false)
+    }
+
+    /// Reads config from `dir/config.toml`, returns default config if file not exists
+    pub fn load(dir: &Path) -> Result<Config, io::Error> {
+        let path = dir.join("config.toml");
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+        let raw = fs::read_to_string(path)?;
+        toml::from_str(&raw).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Writes values editable in settings view to `dir/config.toml`, other keys of file are kept
+    pub fn save_settings(&self, dir: &Path) -> Result<(), io::Error> {
+        edit(dir, |doc| {
+            doc["privacy"] = value(self.privacy);
+            doc["interval"] = value(self.interval as i64);
+            let display = section(doc, "display")?;
+            display.insert("good_signal", value(i64::from(self.display.good_signal)));
+            display.insert("fair_signal", value(i64::from(self.display.fair_signal)));
+            display.insert("units", value(self.display.units.key()));
+            display.insert("theme", value(self.display.theme.key()));
+            Ok(())
+        })
+    }
+
+    /// Writes `[panels]` changed with keys in monitoring view to `dir/config.toml`
+    pub fn save_panels(&self, dir: &Path) -> Result<(), io::Error> {
+        edit(dir, |doc| {
+            let panels = section(doc, "panels")?;
+            panels.insert("charts", value(i64::from(self.panels.charts)));
+            panels.insert("status", value(self.panels.status));
+            panels.insert("events", value(self.panels.events));
+            panels.insert("hint", value(self.panels.hint));
+            panels.insert("horizontal", value(self.panels.horizontal));
+            Ok(())
+        })
+    }
+
+    /// Writes answers of first run wizard to `dir/config.toml`
+    pub fn save_wizard(&self, dir: &Path) -> Result<(), io::Error> {
+        edit(dir, |doc| {
+            doc["privacy"] = value(self.privacy);
+            doc["interval"] = value(self.interval as i64);
+            if let Some(history) = self.history {
+                doc["history"] = value(history);
+            }
+            if let Some(iface) = &self.interfaces.preferred {
+                section(doc, "interfaces")?.insert("preferred", value(iface.as_str()));
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Changes keys of `dir/config.toml` keeping the rest of file with its comments and layout,
+/// creates file if needed
+fn edit(
+    dir: &Path,
+    f: impl FnOnce(&mut DocumentMut) -> Result<(), io::Error>,
+) -> Result<(), io::Error> {
+    let path = dir.join("config.toml");
+    let mut doc = if path.exists() {
+        fs::read_to_string(&path)?
+            .parse::<DocumentMut>()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+    } else {
+        DocumentMut::new()
+    };
+    f(&mut doc)?;
+    fs::create_dir_all(dir)?;
+    fs::write(path, doc.to_string())
+}
+
+/// `[name]` section of config, created if missing, inline table is edited in place
+fn section<'d>(doc: &'d mut DocumentMut, name: &str) -> Result<&'d mut dyn TableLike, io::Error> {
+    doc.entry(name)
+        .or_insert_with(table)
+        .as_table_like_mut()
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} key of config is not a section", name),
+            )
+        })
+}
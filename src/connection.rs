@@ -0,0 +1,268 @@
+use std::{
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    sync::{
+        Arc, Once,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use neli::err::NlError;
+use neli_wifi::{Interface, Socket};
+use tracing::{debug, info, warn};
+
+use crate::{
+    config::InterfacesConfig,
+    link,
+    replay::{self, Devices, Replay},
+    sampler,
+};
+
+const FIRST_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Interfaces are read again at least this often, TX power changes without link event
+const INTERFACES_MAX_AGE: Duration = Duration::from_secs(10);
+
+/// Wifi interfaces allowed by filter, read once and reused while nothing changes
+struct Interfaces {
+    list: Vec<Interface>,
+    filter: InterfacesConfig,
+    at: Instant,
+}
+
+/// Netlink socket which is reconnected with exponential backoff after failures
+pub struct Connection {
+    socket: Option<Socket>,
+    backoff: Duration,
+    retry_at: Instant,
+    attempts: u32,
+    /// Recorded frames answer instead of socket, nothing is sent to kernel
+    replay: Option<Replay>,
+    /// Fixture which gets devices of every frame
+    record: Option<PathBuf>,
+    /// Devices queried in current frame, written to `record` when frame ends
+    frame: Option<Devices>,
+    interfaces: Option<Interfaces>,
+    /// Set on link events, None if they can't be watched
+    links: Option<Arc<AtomicBool>>,
+}
+
+impl Connection {
+    /// Tries to connect right away, on failure connection starts in reconnecting state
+    pub fn new() -> Self {
+        let mut conn = Self {
+            socket: None,
+            backoff: FIRST_BACKOFF,
+            retry_at: Instant::now(),
+            attempts: 0,
+            replay: None,
+            record: None,
+            frame: None,
+            interfaces: None,
+            links: link::watch()
+                .inspect_err(|e| warn!(error = %e, "link events are not watched"))
+                .ok(),
+        };
+        conn.try_connect();
+        conn
+    }
+
+    /// Connection which only plays back `replay`, socket is never opened
+    pub fn replay(replay: Replay) -> Self {
+        Self {
+            socket: None,
+            backoff: FIRST_BACKOFF,
+            retry_at: Instant::now(),
+            attempts: 0,
+            replay: Some(replay),
+            record: None,
+            frame: None,
+            interfaces: None,
+            links: None,
+        }
+    }
+
+    /// Frames come from recording, nothing around them is real
+    pub fn is_replay(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    /// Appends devices of every frame to `path`, so session can be replayed later
+    pub fn record(&mut self, path: PathBuf) {
+        info!(path = %path.display(), "recording netlink answers");
+        self.record = Some(path);
+    }
+
+    /// Wifi interfaces with their BSS lists, from replay if there is one
+    pub fn devices(&mut self, filter: &InterfacesConfig) -> Option<Devices> {
+        if let Some(replay) = &self.replay {
+            let mut devices = replay.devices();
+            devices.retain(|(interface, _)| sampler::is_shown(interface, filter));
+            return Some(devices);
+        }
+        let refreshed = self.refresh_interfaces(filter)?;
+        let mut devices = self.bss_lists()?;
+        // association changed after interfaces were read, e.g. roam to another channel
+        if !refreshed
+            && !devices
+                .iter()
+                .all(|(i, bss_list)| sampler::is_current(i, bss_list))
+        {
+            self.interfaces = None;
+            self.refresh_interfaces(filter)?;
+            devices = self.bss_lists()?;
+        }
+        if self.record.is_some() && self.frame.is_none() {
+            self.frame = Some(devices.clone());
+        }
+        Some(devices)
+    }
+
+    /// Reads interfaces again after link event, filter change or when they are old,
+    /// true if they were read
+    fn refresh_interfaces(&mut self, filter: &InterfacesConfig) -> Option<bool> {
+        // without link events interfaces are read every time
+        let changed = self
+            .links
+            .as_ref()
+            .is_none_or(|l| l.swap(false, Ordering::Relaxed));
+        let stale = changed
+            || self
+                .interfaces
+                .as_ref()
+                .is_none_or(|i| i.filter != *filter || i.at.elapsed() >= INTERFACES_MAX_AGE);
+        if !stale {
+            return Some(false);
+        }
+        let list = self.with(|s| sampler::interfaces(s, filter))?;
+        self.interfaces = Some(Interfaces {
+            list,
+            filter: filter.clone(),
+            at: Instant::now(),
+        });
+        Some(true)
+    }
+
+    /// BSS lists of known interfaces, station data changes every frame
+    fn bss_lists(&mut self) -> Option<Devices> {
+        let interfaces = self.interfaces.as_ref()?.list.clone();
+        self.with(|s| sampler::bss_lists(s, interfaces))
+    }
+
+    /// Ends frame, replay moves to the next one and recording writes the current one
+    pub fn advance(&mut self) {
+        if let Some(replay) = self.replay.as_mut() {
+            replay.advance();
+        }
+        if let (Some(path), Some(frame)) = (&self.record, self.frame.take())
+            && let Err(e) = replay::append(path, &frame)
+        {
+            warn!(error = %e, "failed to record frame, recording stopped");
+            self.record = None;
+        }
+    }
+
+    /// Number of failed reconnect attempts since socket was lost
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Time left until next reconnect attempt
+    pub fn retry_in(&self) -> Duration {
+        self.retry_at.saturating_duration_since(Instant::now())
+    }
+
+    /// Runs query on socket, returns None if socket is not connected or query failed.
+    /// Socket is reconnected only when the socket itself broke, not when kernel refused query
+    pub fn with<T>(&mut self, query: impl FnOnce(&mut Socket) -> Result<T, NlError>) -> Option<T> {
+        // queries other than devices are not recorded
+        if self.replay.is_some() {
+            return None;
+        }
+        if self.socket.is_none() && Instant::now() >= self.retry_at {
+            self.try_connect();
+        }
+        let socket = self.socket.as_mut()?;
+        // neli-wifi panics on netlink error messages, so panic is failure too
+        QUIET_PANICS.call_once(quiet_panics);
+        QUERYING.set(true);
+        let res = panic::catch_unwind(AssertUnwindSafe(|| query(socket)));
+        QUERYING.set(false);
+        match res {
+            Ok(Ok(res)) => Some(res),
+            Ok(Err(e @ (NlError::Nlmsgerr(_) | NlError::Msg(_)))) => {
+                debug!(error = %e, "netlink query refused");
+                None
+            }
+            Ok(Err(e)) => {
+                warn!(error = %e, "netlink query failed");
+                self.lost();
+                None
+            }
+            Err(_) => {
+                // unread answers may be left in socket, new one starts clean
+                debug!("netlink query panicked");
+                self.socket = None;
+                self.retry_at = Instant::now();
+                None
+            }
+        }
+    }
+
+    /// Drops socket, so it is created again on the next query
+    pub fn reset(&mut self) {
+        self.socket = None;
+        self.interfaces = None;
+        self.backoff = FIRST_BACKOFF;
+        self.retry_at = Instant::now();
+    }
+
+    fn try_connect(&mut self) {
+        match Socket::connect() {
+            Ok(socket) => {
+                info!(attempts = self.attempts, "netlink socket connected");
+                self.socket = Some(socket);
+                self.backoff = FIRST_BACKOFF;
+                self.attempts = 0;
+            }
+            Err(e) => {
+                self.attempts += 1;
+                warn!(error = %e, attempts = self.attempts, "netlink connect failed");
+                self.schedule_retry();
+            }
+        }
+    }
+
+    fn lost(&mut self) {
+        self.socket = None;
+        self.interfaces = None;
+        self.schedule_retry();
+    }
+
+    fn schedule_retry(&mut self) {
+        self.retry_at = Instant::now() + self.backoff;
+        self.backoff = (self.backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+static QUIET_PANICS: Once = Once::new();
+
+thread_local! {
+    /// Set while netlink query runs, its panics are expected and must not print over TUI
+    static QUERYING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Wraps panic hook, so panics of netlink queries are logged instead of printed
+fn quiet_panics() {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if QUERYING.get() {
+            debug!(%info, "netlink query panic");
+        } else {
+            hook(info);
+        }
+    }));
+}
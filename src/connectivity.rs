@@ -0,0 +1,149 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpStream, ToSocketAddrs},
+    sync::{Arc, RwLock},
+    thread::sleep,
+    time::Duration,
+};
+
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::events::{self, Kind};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// `[connectivity]` section of config
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ConnectivityConfig {
+    /// Probes `hostname` and `url` from time to time, off as it contacts outside hosts
+    pub enabled: bool,
+    /// Name resolved through system resolver before `url` is requested
+    pub hostname: String,
+    /// Plain http url which answers with 204 No Content
+    pub url: String,
+    /// Seconds between two probes
+    pub interval: u64,
+}
+
+impl Default for ConnectivityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hostname: "connectivitycheck.gstatic.com".to_string(),
+            url: "http://connectivitycheck.gstatic.com/generate_204".to_string(),
+            interval: 30,
+        }
+    }
+}
+
+/// Result of reachability probe
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+    /// `[connectivity]` is not enabled
+    Off,
+    Unknown,
+    Online,
+    /// Probe was answered by something else, usually by captive portal login page
+    CaptivePortal,
+    /// DNS answers nothing, so internet may be there but names don't work
+    NoDns,
+    /// Probe host is not reachable
+    NoInternet,
+}
+
+impl std::fmt::Display for Connectivity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Connectivity::Off => write!(f, "not checked"),
+            Connectivity::Unknown => write!(f, "checking…"),
+            Connectivity::Online => write!(f, "online"),
+            Connectivity::CaptivePortal => write!(f, "captive portal"),
+            Connectivity::NoDns => write!(f, "associated, but DNS does not resolve"),
+            Connectivity::NoInternet => write!(f, "associated, but no internet"),
+        }
+    }
+}
+
+/// Starts thread which probes url periodically, returns shared latest result
+pub fn spawn(config: ConnectivityConfig) -> Arc<RwLock<Connectivity>> {
+    if !config.enabled {
+        return Arc::new(RwLock::new(Connectivity::Off));
+    }
+    let status = Arc::new(RwLock::new(Connectivity::Unknown));
+    let shared = status.clone();
+    std::thread::spawn(move || {
+        loop {
+            let res = match resolve(&config.hostname).and_then(|_| probe(&config.url)) {
+                Ok(204) => Connectivity::Online,
+                Ok(_) => Connectivity::CaptivePortal,
+                Err(e) if e.kind() == io::ErrorKind::HostUnreachable => {
+                    warn!(error = %e, hostname = config.hostname, "dns probe failed");
+                    Connectivity::NoDns
+                }
+                Err(e) => {
+                    warn!(error = %e, url = config.url, "connectivity probe failed");
+                    Connectivity::NoInternet
+                }
+            };
+            let mut current = shared.write().unwrap();
+            if *current != res {
+                info!(connectivity = %res, "connectivity changed");
+                events::push(Kind::Network, format!("connectivity: {}", res));
+                *current = res;
+            }
+            drop(current);
+            sleep(Duration::from_secs(config.interval));
+        }
+    });
+    status
+}
+
+/// Resolves name through system resolver, any failure is reported as unreachable host
+fn resolve(hostname: &str) -> io::Result<()> {
+    match (hostname, 80)
+        .to_socket_addrs()
+        .map(|mut addrs| addrs.next())
+    {
+        Ok(Some(_)) => Ok(()),
+        Ok(None) => Err(io::Error::new(
+            io::ErrorKind::HostUnreachable,
+            format!("{} has no addresses", hostname),
+        )),
+        Err(e) => Err(io::Error::new(io::ErrorKind::HostUnreachable, e)),
+    }
+}
+
+/// Sends GET request and returns http status code
+fn probe(url: &str) -> io::Result<u16> {
+    let rest = url.strip_prefix("http://").ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, "only http urls are supported")
+    })?;
+    let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let path = if path.is_empty() { "/" } else { path };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((h, p)) => (h, p.parse().map_err(io::Error::other)?),
+        None => (authority, 80),
+    };
+
+    let addr = (host, port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "host is not resolved"))?;
+    let mut stream = TcpStream::connect_timeout(&addr, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    write!(
+        stream,
+        "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        path, authority
+    )?;
+    let mut status_line = String::new();
+    BufReader::new(stream).read_line(&mut status_line)?;
+    status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|c| c.parse().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "bad http status line"))
+}
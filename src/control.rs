@@ -0,0 +1,32 @@
+use std::io;
+
+/// Service which manages wifi connections (NetworkManager, iwd)
+pub trait ControlBackend {
+    /// Name shown in UI
+    fn name(&self) -> &'static str;
+    /// Returns names of saved networks/profiles
+    fn known_networks(&self) -> io::Result<Vec<String>>;
+    /// Asks service to start new scan on interface
+    fn scan(&self, iface: &str) -> io::Result<()>;
+    /// Connects interface to network with SSID
+    fn connect(&self, iface: &str, ssid: &str) -> io::Result<()>;
+    /// Disconnects interface from current network
+    fn disconnect(&self, iface: &str) -> io::Result<()>;
+    /// Returns saved password of network, if backend is allowed to read it
+    fn psk(&self, _ssid: &str) -> io::Result<Option<String>> {
+        Ok(None)
+    }
+}
+
+/// Returns first backend which is compiled in and running on this system
+pub fn backend() -> Option<Box<dyn ControlBackend>> {
+    #[cfg(feature = "networkmanager")]
+    if let Ok(nm) = crate::nm::NetworkManager::connect() {
+        return Some(Box::new(nm));
+    }
+    #[cfg(feature = "iwd")]
+    if let Ok(iwd) = crate::iwd::Iwd::connect() {
+        return Some(Box::new(iwd));
+    }
+    None
+}
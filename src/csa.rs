@@ -0,0 +1,139 @@
+use std::{
+    fs,
+    path::Path,
+    sync::{Arc, RwLock},
+};
+
+use chrono::{DateTime, Local};
+use neli::attr::Attribute;
+use neli_wifi::{Nl80211Attr, Nl80211Cmd};
+use tracing::{info, warn};
+
+use crate::{
+    CONFIGURATION,
+    events::{self, Kind},
+    nl80211::Nl80211,
+    notes, regdomain,
+};
+
+/// Channel switch of BSS one of our interfaces uses
+#[derive(Clone, Debug)]
+pub struct ChannelSwitch {
+    pub time: DateTime<Local>,
+    pub iface: String,
+    /// MHz of new channel
+    pub frequency: u32,
+}
+
+impl std::fmt::Display for ChannelSwitch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} moved to {} at {}",
+            self.iface,
+            describe(self.frequency),
+            self.time.format("%H:%M:%S")
+        )
+    }
+}
+
+/// `ch 100 (5500 MHz)`
+fn describe(frequency: u32) -> String {
+    match regdomain::channel_number(frequency) {
+        Some(channel) => format!("ch {} ({} MHz)", channel, frequency),
+        None => format!("{} MHz", frequency),
+    }
+}
+
+/// Name of interface from sysfs, index is kept when interface is gone already
+fn iface_name(index: u32) -> String {
+    fs::read_dir("/sys/class/net")
+        .ok()
+        .and_then(|dir| {
+            dir.flatten().find(|entry| {
+                fs::read_to_string(entry.path().join("ifindex"))
+                    .is_ok_and(|i| i.trim() == index.to_string())
+            })
+        })
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .unwrap_or_else(|| format!("interface {}", index))
+}
+
+/// Starts thread which listens for channel switch announcements, returns the latest switch
+pub fn spawn() -> Arc<RwLock<Option<ChannelSwitch>>> {
+    let latest = Arc::new(RwLock::new(None));
+    let shared = latest.clone();
+    std::thread::spawn(move || {
+        let mut nl = match Nl80211::connect().and_then(|mut nl| {
+            nl.subscribe("mlme")?;
+            Ok(nl)
+        }) {
+            Ok(nl) => nl,
+            Err(e) => {
+                warn!(error = %e, "failed to subscribe to nl80211 channel switch events");
+                return;
+            }
+        };
+        loop {
+            let msg = match nl.event() {
+                Ok(msg) => msg,
+                Err(e) => {
+                    warn!(error = %e, "failed to read nl80211 event");
+                    return;
+                }
+            };
+            if !matches!(
+                msg.cmd,
+                Nl80211Cmd::CmdChSwitchStartedNotify | Nl80211Cmd::CmdChSwitchNotify
+            ) {
+                continue;
+            }
+            let attrs = msg.get_attr_handle();
+            let ifindex: Option<u32> = attrs
+                .get_attribute(Nl80211Attr::AttrIfindex)
+                .and_then(|a| a.get_payload_as().ok());
+            let frequency: Option<u32> = attrs
+                .get_attribute(Nl80211Attr::AttrWiphyFreq)
+                .and_then(|a| a.get_payload_as().ok());
+            let (Some(ifindex), Some(frequency)) = (ifindex, frequency) else {
+                continue;
+            };
+            let iface = iface_name(ifindex);
+            if msg.cmd == Nl80211Cmd::CmdChSwitchStartedNotify {
+                // beacons before switch count down, the switch itself is reported separately
+                let count: Option<u32> = attrs
+                    .get_attribute(Nl80211Attr::AttrChSwitchCount)
+                    .and_then(|a| a.get_payload_as().ok());
+                info!(iface, frequency, count, "channel switch announced");
+                events::push(
+                    Kind::Roam,
+                    format!(
+                        "{} AP announced move to {}, often after radar detection",
+                        iface,
+                        describe(frequency)
+                    ),
+                );
+                continue;
+            }
+            info!(iface, frequency, "channel switched");
+            let switch = ChannelSwitch {
+                time: Local::now(),
+                iface,
+                frequency,
+            };
+            events::push(Kind::Roam, format!("channel switch, {}", switch));
+            // note makes the switch visible in history chart and reports
+            let path = notes::path(Path::new(CONFIGURATION.as_str()));
+            let text = format!(
+                "channel switch of {} to {}",
+                switch.iface,
+                describe(frequency)
+            );
+            if let Err(e) = notes::add(&path, &text) {
+                warn!(error = %e, "failed to annotate channel switch");
+            }
+            *shared.write().unwrap() = Some(switch);
+        }
+    });
+    latest
+}
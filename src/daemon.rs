@@ -0,0 +1,208 @@
+use std::{
+    fs, io,
+    path::Path,
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::Instant,
+};
+
+use signal_hook::consts::SIGHUP;
+use tracing::{error, info, warn};
+
+use crate::{
+    CONFIGURATION, alert::Alerts, api, cli::Cli, config::Config, connection::Connection, deauth,
+    exporter, history::History, hooks::Hooks, http, sampler, statsd::Statsd,
+    suspend::SuspendDetector, systemd, wakeup::Wakeup,
+};
+
+/// Samples wifi interfaces without TUI until the process is stopped
+///
+/// Config is reloaded on SIGHUP, it changes interval, interfaces, alert rules, hooks, StatsD,
+/// email and history retention. Log, deauth watcher, wakeup events, HTTP, parquet, OTLP and
+/// D-Bus keep settings from startup until restart.
+pub fn run(mut config: Config, cli: &Cli, connection: &mut Connection) -> io::Result<()> {
+    let dir = Path::new(CONFIGURATION.as_str());
+    fs::create_dir_all(dir)?;
+    let mut history = if config.daemon_history() {
+        Some(History::open(
+            &dir.join("history.csv"),
+            config.retention.clone(),
+        )?)
+    } else {
+        None
+    };
+    #[cfg(feature = "parquet")]
+    let mut parquet = if config.parquet.enabled {
+        Some(crate::columnar::ParquetWriter::new(dir, &config.parquet)?)
+    } else {
+        None
+    };
+    let mut alerts = Alerts::new(config.alert.clone());
+    let mut hooks = Hooks::new(config.hooks.clone());
+    let mut statsd = udp_metrics(&config);
+    #[cfg(feature = "email")]
+    let mut mailer = crate::email::mailer(&config.email);
+    // logs and runs alert command by itself
+    let _deauth = deauth::spawn(config.deauth.clone());
+    let latest = Arc::new(RwLock::new(Vec::new()));
+    if let Some(addr) = cli.exporter {
+        exporter::spawn(addr, latest.clone())?;
+    }
+    #[cfg(feature = "otlp")]
+    if config.otlp.enabled {
+        crate::otlp::spawn(config.otlp.clone(), latest.clone())?;
+    }
+    if let Some(addr) = cli.http {
+        let shared = http::Shared {
+            latest: latest.clone(),
+            history: config.daemon_history().then(|| dir.join("history.csv")),
+            interfaces: config.interfaces.clone(),
+            privacy: config.privacy,
+            token: config.http.token.clone(),
+        };
+        http::spawn(addr, shared, config.http.clients)?;
+    }
+    // set_interval of api overrides config until next reload
+    let interval = Arc::new(AtomicU64::new(config.interval));
+    let shared = api::Shared {
+        latest: latest.clone(),
+        interval: interval.clone(),
+    };
+    if let Err(e) = api::spawn(&api::socket_path(dir), shared) {
+        warn!(error = %e, "failed to open api socket");
+    }
+    let reload = Arc::new(AtomicBool::new(false));
+    signal_hook::flag::register(SIGHUP, reload.clone())?;
+    let watchdog = systemd::watchdog_interval();
+    let mut suspend = SuspendDetector::new();
+    let wakeup = Wakeup::spawn(&config);
+
+    #[cfg(feature = "dbus")]
+    let dbus = crate::dbus::Service::start()
+        .inspect_err(|e| warn!(error = %e, "status is not exported on session bus"))
+        .ok();
+
+    info!(interval = config.interval, "daemon started");
+    notify("READY=1");
+    loop {
+        if reload.swap(false, Ordering::Relaxed) {
+            notify("RELOADING=1");
+            match Config::load(dir) {
+                Ok(mut new_config) => {
+                    cli.apply(&mut new_config);
+                    alerts = Alerts::new(new_config.alert.clone());
+                    hooks = Hooks::new(new_config.hooks.clone());
+                    statsd = udp_metrics(&new_config);
+                    #[cfg(feature = "email")]
+                    {
+                        mailer = crate::email::mailer(&new_config.email);
+                    }
+                    if let Some(history) = history.as_mut() {
+                        history.set_retention(new_config.retention.clone());
+                    }
+                    config = new_config;
+                    interval.store(config.interval, Ordering::Relaxed);
+                    info!(interval = config.interval, "config reloaded");
+                }
+                Err(e) => error!(error = %e, "config reload failed, keeping old config"),
+            }
+            notify("READY=1");
+        }
+
+        if let Some(slept) = suspend.check() {
+            info!(slept_secs = slept.as_secs(), "resumed from suspend");
+            connection.reset();
+        }
+
+        match connection.with(|s| sampler::collect(s, &config.interfaces)) {
+            Some(samples) => {
+                for sample in &samples {
+                    info!(
+                        iface = sample.iface,
+                        bssid = sample.bssid,
+                        signal = sample.signal,
+                        "sample"
+                    );
+                    if let Some(history) = history.as_mut() {
+                        // full disk must not stop alerts and metrics
+                        if let Err(e) = history.append(sample) {
+                            warn!(error = %e, "failed to append sample to history");
+                        }
+                    }
+                    #[cfg(feature = "parquet")]
+                    if let Some(parquet) = parquet.as_mut()
+                        && let Err(e) = parquet.append(sample)
+                    {
+                        warn!(error = %e, "failed to write parquet batch");
+                    }
+                    hooks.observe(sample);
+                    #[cfg(feature = "email")]
+                    if let Some(mailer) = mailer.as_mut() {
+                        mailer.observe(sample);
+                    }
+                    // the same as TUI, rules are about link of associated interface
+                    if !sample.connected {
+                        continue;
+                    }
+                    for change in alerts.evaluate(sample) {
+                        hooks.threshold(&change, sample);
+                        #[cfg(feature = "email")]
+                        if let Some(mailer) = mailer.as_mut() {
+                            mailer.alert(&change, sample);
+                        }
+                    }
+                }
+                if let Some(statsd) = statsd.as_mut() {
+                    statsd.emit(&samples);
+                }
+                *latest.write().unwrap() = samples;
+            }
+            None => warn!(
+                attempts = connection.attempts(),
+                "netlink socket is not available, reconnecting"
+            ),
+        }
+        #[cfg(feature = "dbus")]
+        if let Some(service) = &dbus
+            && let Err(e) = crate::indicator::Status::read(connection, &config.interfaces)
+                .and_then(|status| service.update(&status))
+        {
+            warn!(error = %e, "failed to update D-Bus status");
+        }
+
+        // watchdog can require pings more often than samples are taken
+        let next_sample = Instant::now() + wakeup.interval(interval.load(Ordering::Relaxed));
+        while let Some(left) = next_sample.checked_duration_since(Instant::now()) {
+            if watchdog.is_some() {
+                notify("WATCHDOG=1");
+            }
+            // kernel event is sampled right away
+            if let Some(wake) = wakeup.sleep(watchdog.map_or(left, |w| w.min(left))) {
+                info!(?wake, "sampling after nl80211 event");
+                break;
+            }
+            if reload.load(Ordering::Relaxed) {
+                break;
+            }
+        }
+    }
+}
+
+/// Tells systemd about state, failure is logged, so daemon runs on without supervisor
+fn notify(state: &str) {
+    if let Err(e) = systemd::notify(state) {
+        warn!(error = %e, state, "failed to notify systemd");
+    }
+}
+
+/// UDP emitter if `[statsd]` is enabled, daemon runs on without it if socket fails
+fn udp_metrics(config: &Config) -> Option<Statsd> {
+    if !config.statsd.enabled {
+        return None;
+    }
+    Statsd::new(config.statsd.clone())
+        .inspect_err(|e| warn!(error = %e, "metrics are not sent over udp"))
+        .ok()
+}
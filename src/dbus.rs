@@ -0,0 +1,132 @@
+use std::io;
+
+use zbus::{
+    blocking::{Connection, connection::Builder},
+    interface,
+};
+
+use crate::{display, indicator::Status};
+
+const NAME: &str = "io.github.slepming.WifiSignalCheck";
+const PATH: &str = "/io/github/slepming/WifiSignalCheck";
+
+/// Properties of selected interface, changes are announced with PropertiesChanged
+#[derive(Default)]
+struct Properties {
+    iface: String,
+    ssid: String,
+    signal: i32,
+    frequency: u32,
+    connected: bool,
+    score: u8,
+}
+
+#[interface(name = "io.github.slepming.WifiSignalCheck")]
+impl Properties {
+    #[zbus(property)]
+    fn iface(&self) -> String {
+        self.iface.clone()
+    }
+
+    /// Empty if not connected or hidden
+    #[zbus(property)]
+    fn ssid(&self) -> String {
+        self.ssid.clone()
+    }
+
+    /// dBm, 0 if not connected
+    #[zbus(property)]
+    fn signal(&self) -> i32 {
+        self.signal
+    }
+
+    /// MHz, 0 if not connected
+    #[zbus(property)]
+    fn frequency(&self) -> u32 {
+        self.frequency
+    }
+
+    #[zbus(property)]
+    fn connected(&self) -> bool {
+        self.connected
+    }
+
+    /// Link quality 0–100, 0 if not connected
+    #[zbus(property)]
+    fn score(&self) -> u8 {
+        self.score
+    }
+
+    /// good, fair, poor or disconnected by thresholds of `[display]`
+    #[zbus(property)]
+    fn quality(&self) -> String {
+        if self.connected {
+            display::quality(self.signal).to_string()
+        } else {
+            "disconnected".to_string()
+        }
+    }
+}
+
+/// Status exported on session bus
+pub struct Service {
+    conn: Connection,
+}
+
+impl Service {
+    /// Connects to session bus and takes well known name
+    pub fn start() -> io::Result<Self> {
+        let conn = Builder::session()
+            .and_then(|b| b.name(NAME))
+            .and_then(|b| b.serve_at(PATH, Properties::default()))
+            .and_then(|b| b.build())
+            .map_err(io::Error::other)?;
+        Ok(Self { conn })
+    }
+
+    /// Replaces properties, only changed ones are announced
+    pub fn update(&self, status: &Status) -> io::Result<()> {
+        let iface_ref = self
+            .conn
+            .object_server()
+            .interface::<_, Properties>(PATH)
+            .map_err(io::Error::other)?;
+        let mut props = iface_ref.get_mut();
+        let emitter = iface_ref.signal_emitter();
+        let iface = status.iface.clone().unwrap_or_default();
+        let ssid = status.ssid.clone().unwrap_or_default();
+        let signal = status.signal.unwrap_or_default();
+        let frequency = status.frequency.unwrap_or_default();
+        let connected = status.signal.is_some();
+        let score = status.score.unwrap_or_default();
+        zbus::block_on(async {
+            if props.iface != iface {
+                props.iface = iface;
+                props.iface_changed(emitter).await?;
+            }
+            if props.ssid != ssid {
+                props.ssid = ssid;
+                props.ssid_changed(emitter).await?;
+            }
+            if props.frequency != frequency {
+                props.frequency = frequency;
+                props.frequency_changed(emitter).await?;
+            }
+            if props.connected != connected {
+                props.connected = connected;
+                props.connected_changed(emitter).await?;
+            }
+            if props.score != score {
+                props.score = score;
+                props.score_changed(emitter).await?;
+            }
+            if props.signal != signal {
+                props.signal = signal;
+                props.signal_changed(emitter).await?;
+                props.quality_changed(emitter).await?;
+            }
+            Ok::<_, zbus::Error>(())
+        })
+        .map_err(io::Error::other)
+    }
+}
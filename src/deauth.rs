@@ -0,0 +1,184 @@
+use std::{
+    collections::VecDeque,
+    fs,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use neli::attr::Attribute;
+use neli_wifi::{Nl80211Attr, Nl80211Cmd};
+use serde::Deserialize;
+use tracing::{debug, info, warn};
+
+use crate::{
+    events::{self, Kind},
+    hooks,
+    nl80211::Nl80211,
+};
+
+/// `[deauth]` section of config
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct DeauthConfig {
+    /// Watches deauthentication frames sent by access points, off as some drivers report
+    /// roaming ones too
+    pub enabled: bool,
+    /// Amount of deauthentication and disassociation events inside `window` which is reported as attack
+    pub threshold: usize,
+    /// Seconds
+    pub window: u64,
+    /// Shell command run when attack is detected, gets `WIFI_ALERT` and `WIFI_IFINDEX` variables
+    pub command: Option<String>,
+}
+
+impl Default for DeauthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: 3,
+            window: 60,
+            command: None,
+        }
+    }
+}
+
+/// Recent deauthentication events of our interfaces
+#[derive(Default)]
+pub struct DeauthMonitor {
+    events: VecDeque<Instant>,
+    /// Reason code of the latest event
+    pub reason: Option<u16>,
+    window: Duration,
+    threshold: usize,
+}
+
+impl DeauthMonitor {
+    /// true while amount of events inside window is over threshold
+    pub fn attack(&self) -> bool {
+        self.count() >= self.threshold
+    }
+
+    /// Events inside window, older ones may be left until the next event prunes them
+    pub fn count(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|t| t.elapsed() < self.window)
+            .count()
+    }
+}
+
+/// Starts thread which listens for nl80211 MLME events
+pub fn spawn(config: DeauthConfig) -> Arc<RwLock<DeauthMonitor>> {
+    let monitor = Arc::new(RwLock::new(DeauthMonitor {
+        window: Duration::from_secs(config.window),
+        threshold: config.threshold.max(1),
+        ..DeauthMonitor::default()
+    }));
+    if !config.enabled {
+        return monitor;
+    }
+    let shared = monitor.clone();
+    std::thread::spawn(move || {
+        let mut nl = match Nl80211::connect().and_then(|mut nl| {
+            nl.subscribe("mlme")?;
+            Ok(nl)
+        }) {
+            Ok(nl) => nl,
+            Err(e) => {
+                warn!(error = %e, "failed to subscribe to nl80211 mlme events");
+                return;
+            }
+        };
+        loop {
+            let msg = match nl.event() {
+                Ok(msg) => msg,
+                Err(e) => {
+                    warn!(error = %e, "failed to read nl80211 event");
+                    return;
+                }
+            };
+            if !matches!(
+                msg.cmd,
+                Nl80211Cmd::CmdDeauthenticate
+                    | Nl80211Cmd::CmdDisassociate
+                    | Nl80211Cmd::CmdUnprotDeauthenticate
+                    | Nl80211Cmd::CmdUnprotDisassociate
+            ) {
+                continue;
+            }
+            let attrs = msg.get_attr_handle();
+            let ifindex: Option<u32> = attrs
+                .get_attribute(Nl80211Attr::AttrIfindex)
+                .and_then(|a| a.get_payload_as().ok());
+            let frame = attrs
+                .get_attribute(Nl80211Attr::AttrFrame)
+                .map(|a| a.nla_payload.as_ref());
+            // frame sent by ourselves is roaming, suspend or user disconnect
+            let transmitter = frame.and_then(|f| f.get(10..16));
+            if transmitter.is_some() && transmitter == ifindex.and_then(address).as_deref() {
+                debug!(cmd = ?msg.cmd, ifindex, "local deauthentication");
+                continue;
+            }
+            // reason code follows 24 bytes of management header
+            let reason = frame
+                .and_then(|f| f.get(24..26).map(|r| [r[0], r[1]]))
+                .map(u16::from_le_bytes);
+            info!(cmd = ?msg.cmd, ifindex, reason, "deauthentication event");
+
+            let mut monitor = shared.write().unwrap();
+            let window = monitor.window;
+            while monitor
+                .events
+                .front()
+                .is_some_and(|t| t.elapsed() >= window)
+            {
+                monitor.events.pop_front();
+            }
+            let was_attack = monitor.attack();
+            monitor.events.push_back(Instant::now());
+            monitor.reason = reason;
+            if monitor.attack() && !was_attack {
+                events::push(
+                    Kind::Alert,
+                    format!(
+                        "possible deauthentication attack, {} events",
+                        monitor.count()
+                    ),
+                );
+                warn!(
+                    events = monitor.count(),
+                    ifindex, "possible deauthentication attack"
+                );
+                if let Some(command) = &config.command {
+                    hooks::run(
+                        command,
+                        vec![
+                            ("WIFI_ALERT", "deauth".to_string()),
+                            (
+                                "WIFI_IFINDEX",
+                                ifindex.map(|i| i.to_string()).unwrap_or_default(),
+                            ),
+                        ],
+                    );
+                }
+            }
+        }
+    });
+    monitor
+}
+
+/// MAC address of interface from sysfs
+fn address(ifindex: u32) -> Option<Vec<u8>> {
+    let entry = fs::read_dir("/sys/class/net")
+        .ok()?
+        .flatten()
+        .find(|entry| {
+            fs::read_to_string(entry.path().join("ifindex"))
+                .is_ok_and(|i| i.trim() == ifindex.to_string())
+        })?;
+    let text = fs::read_to_string(entry.path().join("address")).ok()?;
+    text.trim()
+        .split(':')
+        .map(|b| u8::from_str_radix(b, 16).ok())
+        .collect()
+}
@@ -0,0 +1,281 @@
+use std::sync::{
+    RwLock,
+    atomic::{AtomicU64, Ordering},
+};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
+};
+use serde::Deserialize;
+
+const DEFAULT: DisplayConfig = DisplayConfig {
+    good_signal: -60,
+    fair_signal: -100,
+    units: SignalUnit::Dbm,
+    theme: Theme::Dark,
+    ascii: false,
+    icons: false,
+    bell: false,
+    flash: true,
+    language: None,
+    plain: false,
+};
+
+/// Display settings used by all views, changed from settings view
+static DISPLAY: RwLock<DisplayConfig> = RwLock::new(DEFAULT);
+
+/// Bumped by every `set`, so text cached by views is built again
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// `[display]` section of config
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// Signal in dBm from which connection is shown as good
+    pub good_signal: i32,
+    /// Signal in dBm from which connection is shown as fair, weaker one is poor
+    pub fair_signal: i32,
+    pub units: SignalUnit,
+    pub theme: Theme,
+    /// Borders, charts and arrows are drawn with ASCII only, for fonts without box drawing
+    pub ascii: bool,
+    /// Nerd Font glyphs are shown next to networks and states, needs patched font
+    pub icons: bool,
+    /// Rings terminal bell when alert rule fires, urgent hint makes unfocused window noticeable
+    pub bell: bool,
+    /// Shows blinking banner above monitoring when alert rule fires
+    pub flash: bool,
+    /// Language code of UI like `ru`, taken from LANG and LC_* variables if not set
+    pub language: Option<String>,
+    /// Plain lines on stdout instead of TUI, the same as --plain
+    pub plain: bool,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        DEFAULT
+    }
+}
+
+/// How signal level is shown
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SignalUnit {
+    Dbm,
+    /// Quality from 0 to 100, -100 dBm and weaker is 0, -50 dBm and stronger is 100
+    Percent,
+}
+
+impl SignalUnit {
+    pub fn next(self) -> Self {
+        match self {
+            SignalUnit::Dbm => SignalUnit::Percent,
+            SignalUnit::Percent => SignalUnit::Dbm,
+        }
+    }
+
+    /// Name used in config file
+    pub fn key(self) -> &'static str {
+        match self {
+            SignalUnit::Dbm => "dbm",
+            SignalUnit::Percent => "percent",
+        }
+    }
+}
+
+impl std::fmt::Display for SignalUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignalUnit::Dbm => write!(f, "dBm"),
+            SignalUnit::Percent => write!(f, "%"),
+        }
+    }
+}
+
+/// Colors of signal levels, status bar and selection
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    Dark,
+    /// Darker colors readable on light terminal background
+    Light,
+    /// No colors, only bold and reversed text
+    Mono,
+}
+
+impl Theme {
+    pub fn next(self) -> Self {
+        match self {
+            Theme::Dark => Theme::Light,
+            Theme::Light => Theme::Mono,
+            Theme::Mono => Theme::Dark,
+        }
+    }
+
+    /// Name used in config file
+    pub fn key(self) -> &'static str {
+        match self {
+            Theme::Dark => "dark",
+            Theme::Light => "light",
+            Theme::Mono => "mono",
+        }
+    }
+}
+
+/// Replaces display settings of all views
+pub fn set(config: &DisplayConfig) {
+    *DISPLAY.write().unwrap() = config.clone();
+    GENERATION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Changes whenever display settings may have changed
+pub fn generation() -> u64 {
+    GENERATION.load(Ordering::Relaxed)
+}
+
+/// 0 for good, 1 for fair and 2 for poor signal by configured thresholds
+fn level(display: &DisplayConfig, dbm: i32) -> usize {
+    if dbm >= display.good_signal {
+        0
+    } else if dbm >= display.fair_signal {
+        1
+    } else {
+        2
+    }
+}
+
+/// Name of signal level in dBm by configured thresholds
+pub fn quality(dbm: i32) -> &'static str {
+    ["good", "fair", "poor"][level(&DISPLAY.read().unwrap(), dbm)]
+}
+
+/// Color of signal level in dBm by configured thresholds
+pub fn signal_color(dbm: i32) -> Color {
+    let display = DISPLAY.read().unwrap();
+    let level = level(&display, dbm);
+    match display.theme {
+        Theme::Dark => [Color::Green, Color::Yellow, Color::Red][level],
+        Theme::Light => [Color::Green, Color::Magenta, Color::Red][level],
+        Theme::Mono => Color::Reset,
+    }
+}
+
+/// Signal in dBm converted to configured unit, without unit name
+pub fn signal_value(dbm: i32) -> i32 {
+    match DISPLAY.read().unwrap().units {
+        SignalUnit::Dbm => dbm,
+        SignalUnit::Percent => percent(dbm),
+    }
+}
+
+/// Signal quality from 0 to 100
+pub fn percent(dbm: i32) -> i32 {
+    (2 * (dbm + 100)).clamp(0, 100)
+}
+
+pub fn unit() -> SignalUnit {
+    DISPLAY.read().unwrap().units
+}
+
+/// Style of status line
+pub fn bar_style() -> Style {
+    match DISPLAY.read().unwrap().theme {
+        Theme::Dark => Style::default().fg(Color::Black).bg(Color::Cyan),
+        Theme::Light => Style::default().fg(Color::White).bg(Color::Blue),
+        Theme::Mono => Style::default().add_modifier(Modifier::REVERSED),
+    }
+}
+
+/// Border of selected interface
+pub fn selected_style() -> Style {
+    match DISPLAY.read().unwrap().theme {
+        Theme::Dark => Style::default().fg(Color::Yellow),
+        Theme::Light => Style::default().fg(Color::Blue),
+        Theme::Mono => Style::default().add_modifier(Modifier::BOLD),
+    }
+}
+
+/// Glyphs shown when `icons` is enabled
+#[derive(Clone, Copy, Debug)]
+pub enum Icon {
+    Wifi,
+    Lock,
+    Unlock,
+    Warning,
+    Phone,
+    Cast,
+}
+
+/// Nerd Font glyph followed by space, empty if icons are disabled or only ASCII is allowed
+pub fn icon(icon: Icon) -> &'static str {
+    let display = DISPLAY.read().unwrap();
+    if !display.icons || display.ascii {
+        return "";
+    }
+    match icon {
+        Icon::Wifi => "\u{f05a9} ",
+        Icon::Lock => "\u{f023} ",
+        Icon::Unlock => "\u{f09c} ",
+        Icon::Warning => "\u{f071} ",
+        Icon::Phone => "\u{f011c} ",
+        Icon::Cast => "\u{f0118} ",
+    }
+}
+
+pub fn ascii() -> bool {
+    DISPLAY.read().unwrap().ascii
+}
+
+pub fn bell() -> bool {
+    DISPLAY.read().unwrap().bell
+}
+
+pub fn flash() -> bool {
+    DISPLAY.read().unwrap().flash
+}
+
+/// Rendered last over whole frame, replaces box drawing, block and arrow characters with ASCII
+/// if it is enabled in config
+pub struct AsciiOnly;
+
+impl Widget for AsciiOnly {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if !ascii() {
+            return;
+        }
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = &mut buf[(x, y)];
+                let mut chars = cell.symbol().chars();
+                if let (Some(c), None) = (chars.next(), chars.next())
+                    && !c.is_ascii()
+                    && let Some(a) = to_ascii(c)
+                {
+                    cell.set_char(a);
+                }
+            }
+        }
+    }
+}
+
+fn to_ascii(c: char) -> Option<char> {
+    Some(match c {
+        '─' | '━' | '═' => '-',
+        '│' | '┃' | '║' => '|',
+        '┌' | '┐' | '└' | '┘' | '╭' | '╮' | '╰' | '╯' | '├' | '┤' | '┬' | '┴' | '┼' | '╔' | '╗'
+        | '╚' | '╝' | '┏' | '┓' | '┗' | '┛' => '+',
+        '▁' | '▂' => '.',
+        '▃' | '▄' => ':',
+        '▅' | '▆' => '=',
+        '▇' | '█' => '#',
+        '↑' => '^',
+        '↓' => 'v',
+        '→' => '>',
+        '…' => '.',
+        '\u{2801}'..='\u{28ff}' => '*',
+        _ => return None,
+    })
+}
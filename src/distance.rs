@@ -0,0 +1,44 @@
+use serde::Deserialize;
+
+/// `[distance]` section of config, rough distance to AP for placement surveys
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct DistanceConfig {
+    /// Shows estimate in monitoring
+    pub enabled: bool,
+    /// Assumed TX power of AP in dBm, antenna gains included
+    pub tx_power: f64,
+    /// Path loss exponent, 2 is free space, walls make it about 3
+    pub exponent: f64,
+    /// MHz used when frequency of BSS is unknown
+    pub frequency: u32,
+}
+
+impl Default for DistanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            tx_power: 20.0,
+            exponent: 2.0,
+            frequency: 2437,
+        }
+    }
+}
+
+/// Meters from log-distance path loss model, free space path loss at 1 m as reference
+pub fn estimate(config: &DistanceConfig, signal: i32, frequency: Option<u32>) -> f64 {
+    let frequency = f64::from(frequency.unwrap_or(config.frequency));
+    // free space loss at 1 m is 20 log10(f MHz) - 27.55
+    let reference = 20.0 * frequency.log10() - 27.55;
+    let loss = config.tx_power - f64::from(signal);
+    10f64.powf((loss - reference) / (10.0 * config.exponent.max(1.0)))
+}
+
+/// Estimate with precision it deserves
+pub fn describe(meters: f64) -> String {
+    if meters < 10.0 {
+        format!("~{:.1} m (approximate)", meters)
+    } else {
+        format!("~{:.0} m (approximate)", meters)
+    }
+}
@@ -0,0 +1,135 @@
+use std::{
+    io,
+    net::{IpAddr, SocketAddr, ToSocketAddrs, UdpSocket},
+    sync::{Arc, RwLock},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::events::{self, Kind};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// `[dns]` section of config
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct DnsConfig {
+    pub enabled: bool,
+    /// Name which is resolved on every check
+    pub hostname: String,
+    /// DNS server which is asked directly, system resolver is used if not set
+    pub resolver: Option<IpAddr>,
+    /// Seconds between two checks
+    pub interval: u64,
+}
+
+impl Default for DnsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hostname: "example.com".to_string(),
+            resolver: None,
+            interval: 30,
+        }
+    }
+}
+
+/// Result of the latest resolution
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Dns {
+    Disabled,
+    Unknown,
+    Resolved(Duration),
+    Failed(String),
+}
+
+impl std::fmt::Display for Dns {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Dns::Disabled => write!(f, "disabled"),
+            Dns::Unknown => write!(f, "checking…"),
+            Dns::Resolved(t) => write!(f, "ok in {:.1} ms", t.as_secs_f64() * 1000.0),
+            Dns::Failed(e) => write!(f, "failed: {}", e),
+        }
+    }
+}
+
+/// Starts thread which resolves configured name periodically, returns shared latest result
+pub fn spawn(config: DnsConfig) -> Arc<RwLock<Dns>> {
+    if !config.enabled {
+        return Arc::new(RwLock::new(Dns::Disabled));
+    }
+    let status = Arc::new(RwLock::new(Dns::Unknown));
+    let shared = status.clone();
+    std::thread::spawn(move || {
+        loop {
+            let started = Instant::now();
+            let res = match config.resolver {
+                Some(server) => query(SocketAddr::new(server, 53), &config.hostname),
+                None => (config.hostname.as_str(), 0).to_socket_addrs().map(|_| ()),
+            };
+            let res = match res {
+                Ok(()) => Dns::Resolved(started.elapsed()),
+                Err(e) => {
+                    warn!(error = %e, hostname = config.hostname, "dns check failed");
+                    Dns::Failed(e.to_string())
+                }
+            };
+            let mut current = shared.write().unwrap();
+            if matches!(*current, Dns::Failed(_)) != matches!(res, Dns::Failed(_)) {
+                info!(dns = %res, "dns state changed");
+                events::push(Kind::Network, format!("dns: {}", res));
+            }
+            *current = res;
+            drop(current);
+            sleep(Duration::from_secs(config.interval));
+        }
+    });
+    status
+}
+
+/// Asks `server` for A record of `name`, succeeds only if answer is not empty
+fn query(server: SocketAddr, name: &str) -> io::Result<()> {
+    let socket = UdpSocket::bind(if server.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    })?;
+    socket.set_read_timeout(Some(TIMEOUT))?;
+    socket.connect(server)?;
+
+    let id = std::process::id() as u16;
+    // header: id, recursion desired, one question
+    let mut request = Vec::with_capacity(512);
+    request.extend_from_slice(&id.to_be_bytes());
+    request.extend_from_slice(&[0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0]);
+    for label in name.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "bad hostname"));
+        }
+        request.push(label.len() as u8);
+        request.extend_from_slice(label.as_bytes());
+    }
+    // root, type A, class IN
+    request.extend_from_slice(&[0, 0, 1, 0, 1]);
+    socket.send(&request)?;
+
+    let mut buf = [0; 512];
+    loop {
+        let n = socket.recv(&mut buf)?;
+        if n < 12 || buf[..2] != id.to_be_bytes() {
+            continue;
+        }
+        return match buf[3] & 0x0f {
+            0 if u16::from_be_bytes([buf[6], buf[7]]) > 0 => Ok(()),
+            0 => Err(io::Error::new(io::ErrorKind::NotFound, "empty answer")),
+            3 => Err(io::Error::new(io::ErrorKind::NotFound, "NXDOMAIN")),
+            2 => Err(io::Error::other("SERVFAIL")),
+            5 => Err(io::Error::other("REFUSED")),
+            code => Err(io::Error::other(format!("rcode {}", code))),
+        };
+    }
+}
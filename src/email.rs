@@ -0,0 +1,283 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fs, io,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use lettre::{
+    Message, SmtpTransport, Transport,
+    message::{Mailbox, header::ContentType},
+    transport::smtp::authentication::Credentials,
+};
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::{alert::AlertChange, sampler::Sample};
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Unsent mails are retried not more often than this
+const RETRY: Duration = Duration::from_secs(60);
+
+/// Unsent mails kept for retry, the oldest are dropped first
+const QUEUE: usize = 50;
+
+/// `[email]` section of config, used in daemon mode
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct EmailConfig {
+    /// Mails alerts and long disconnects to `to`
+    pub enabled: bool,
+    /// SMTP server
+    pub server: String,
+    /// 465 for tls, 587 for starttls and 25 without encryption if not set
+    pub port: Option<u16>,
+    pub tls: Encryption,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+    /// Seconds interface has to stay disconnected before it's mailed, 0 mails only alerts
+    pub disconnected_secs: u64,
+    /// Also mails when alert resolves or interface connects again
+    pub resolved: bool,
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            server: "localhost".to_string(),
+            port: None,
+            tls: Encryption::Starttls,
+            username: None,
+            password: None,
+            from: "wifi@localhost".to_string(),
+            to: Vec::new(),
+            disconnected_secs: 300,
+            resolved: true,
+        }
+    }
+}
+
+/// Encryption of SMTP connection
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Encryption {
+    /// TLS from the first byte
+    Tls,
+    /// Plain connection upgraded with STARTTLS, fails if server can't
+    Starttls,
+    /// Plain text, only for relay on the same machine
+    None,
+}
+
+/// Sends mails in background, so slow SMTP server never delays samples
+pub struct Mailer {
+    transport: SmtpTransport,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+    config: EmailConfig,
+    host: String,
+    /// Interfaces without association, with time it was lost and whether mail was queued
+    dropped: HashMap<String, (Instant, bool)>,
+    /// Mails waiting to be sent, failed ones are put back by sending thread
+    queue: Arc<Mutex<VecDeque<Message>>>,
+    /// Set while sending thread runs, so mails are not sent twice
+    sending: Arc<AtomicBool>,
+    /// Last time queue was handed to sending thread
+    tried: Option<Instant>,
+}
+
+/// Mailer if `[email]` is enabled, daemon runs on without it if config is broken
+pub fn mailer(config: &EmailConfig) -> Option<Mailer> {
+    if !config.enabled {
+        return None;
+    }
+    Mailer::new(config.clone())
+        .inspect_err(|e| warn!(error = %e, "alerts are not mailed"))
+        .ok()
+}
+
+impl Mailer {
+    pub fn new(config: EmailConfig) -> io::Result<Self> {
+        let invalid =
+            |e: &dyn std::fmt::Display| io::Error::new(io::ErrorKind::InvalidInput, e.to_string());
+        let mut builder = match config.tls {
+            Encryption::Tls => SmtpTransport::relay(&config.server).map_err(|e| invalid(&e))?,
+            Encryption::Starttls => {
+                SmtpTransport::starttls_relay(&config.server).map_err(|e| invalid(&e))?
+            }
+            Encryption::None => SmtpTransport::builder_dangerous(&config.server).port(25),
+        };
+        if let Some(port) = config.port {
+            builder = builder.port(port);
+        }
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+        let from = config.from.parse().map_err(|e| invalid(&e))?;
+        let to = config
+            .to
+            .iter()
+            .map(|to| to.parse())
+            .collect::<Result<Vec<Mailbox>, _>>()
+            .map_err(|e| invalid(&e))?;
+        if to.is_empty() {
+            return Err(invalid(&"email has no recipients in `to`"));
+        }
+        let host = fs::read_to_string("/proc/sys/kernel/hostname").unwrap_or_default();
+        info!(server = config.server, to = ?config.to, "mailing alerts");
+        Ok(Self {
+            transport: builder.timeout(Some(TIMEOUT)).build(),
+            from,
+            to,
+            config,
+            host: host.trim().to_string(),
+            dropped: HashMap::new(),
+            queue: Arc::default(),
+            sending: Arc::default(),
+            tried: None,
+        })
+    }
+
+    /// Mails interface which stays disconnected longer than `disconnected_secs`,
+    /// mail is queued until SMTP server can be reached again
+    pub fn observe(&mut self, sample: &Sample) {
+        if sample.connected && self.tried.is_none_or(|t| t.elapsed() >= RETRY) {
+            self.flush();
+        }
+        if self.config.disconnected_secs == 0 {
+            return;
+        }
+        if sample.connected {
+            let Some((since, mailed)) = self.dropped.remove(&sample.iface) else {
+                return;
+            };
+            if mailed && self.config.resolved {
+                self.send(
+                    format!("{} connected again", sample.iface),
+                    format!(
+                        "{} was disconnected for {} s.\n\n{}",
+                        sample.iface,
+                        since.elapsed().as_secs(),
+                        details(sample)
+                    ),
+                );
+            }
+            return;
+        }
+        let (since, mailed) = self
+            .dropped
+            .entry(sample.iface.clone())
+            .or_insert((Instant::now(), false));
+        if *mailed || since.elapsed() < Duration::from_secs(self.config.disconnected_secs) {
+            return;
+        }
+        *mailed = true;
+        let subject = format!("{} is disconnected", sample.iface);
+        let body = format!(
+            "{} has not been associated for {} s.\n\n{}",
+            sample.iface,
+            since.elapsed().as_secs(),
+            details(sample)
+        );
+        self.send(subject, body);
+    }
+
+    /// Mails `[[alert]]` rule which fired, or resolved if `resolved` is set
+    pub fn alert(&mut self, change: &AlertChange, sample: &Sample) {
+        if !change.fired && !self.config.resolved {
+            return;
+        }
+        let state = if change.fired { "fired" } else { "resolved" };
+        self.send(
+            format!("{} {} on {}", change.rule, state, sample.iface),
+            format!(
+                "Alert {} {} on {}.\n\n{}",
+                change.rule,
+                state,
+                sample.iface,
+                details(sample)
+            ),
+        );
+    }
+
+    fn send(&mut self, subject: String, body: String) {
+        let mut message = Message::builder()
+            .from(self.from.clone())
+            .subject(format!("[wifi {}] {}", self.host, subject))
+            .header(ContentType::TEXT_PLAIN);
+        for to in &self.to {
+            message = message.to(to.clone());
+        }
+        let message = match message.body(body) {
+            Ok(message) => message,
+            Err(e) => {
+                warn!(error = %e, "failed to build mail");
+                return;
+            }
+        };
+        let mut queue = self.queue.lock().unwrap();
+        if queue.len() >= QUEUE {
+            queue.pop_front();
+            warn!("too many unsent mails, dropped the oldest");
+        }
+        queue.push_back(message);
+        drop(queue);
+        self.flush();
+    }
+
+    /// Sends queued mails in order, stops at the first failure and keeps the rest for retry
+    fn flush(&mut self) {
+        if self.queue.lock().unwrap().is_empty() || self.sending.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        self.tried = Some(Instant::now());
+        let transport = self.transport.clone();
+        let queue = self.queue.clone();
+        let sending = self.sending.clone();
+        std::thread::spawn(move || {
+            loop {
+                let Some(message) = queue.lock().unwrap().pop_front() else {
+                    break;
+                };
+                let subject = message
+                    .headers()
+                    .get_raw("Subject")
+                    .unwrap_or_default()
+                    .to_string();
+                match transport.send(&message) {
+                    Ok(_) => info!(subject, "mail sent"),
+                    Err(e) => {
+                        warn!(error = %e, subject, "failed to send mail, retrying later");
+                        queue.lock().unwrap().push_front(message);
+                        break;
+                    }
+                }
+            }
+            sending.store(false, Ordering::Release);
+        });
+    }
+}
+
+fn details(sample: &Sample) -> String {
+    format!(
+        "Time: {}\nInterface: {}\nSSID: {}\nBSSID: {}\nSignal: {} dBm\nFrequency: {}\nQuality: {}\n",
+        sample.time.to_rfc3339(),
+        sample.iface,
+        sample.ssid.as_deref().unwrap_or("-"),
+        sample.bssid.as_deref().unwrap_or("-"),
+        sample.signal,
+        sample
+            .frequency
+            .map_or("-".to_string(), |f| format!("{} MHz", f)),
+        sample
+            .score
+            .map_or("-".to_string(), |s| format!("{}/100", s)),
+    )
+}
@@ -0,0 +1,186 @@
+use std::{fmt, io};
+
+use crate::i18n;
+
+/// Error shown to user as popup, see `AppState::Error`
+#[derive(Debug)]
+pub enum AppError {
+    /// No wireless interface passed interface filter
+    NoInterface,
+    /// Kernel refused request, `what` names changed setting and interface
+    NetlinkFailure {
+        what: String,
+        source: io::Error,
+    },
+    PermissionDenied {
+        what: String,
+        needs: &'static str,
+    },
+    /// Writing `/dev/rfkill` failed
+    Rfkill {
+        index: u32,
+        source: io::Error,
+    },
+    /// Typed text is not a TX power
+    InvalidTxPower(String),
+    /// Connection manager refused connect, disconnect or scan
+    Backend {
+        name: &'static str,
+        iface: String,
+        source: io::Error,
+    },
+    /// Session report could not be written
+    Report(io::Error),
+    /// Note could not be saved
+    Note(io::Error),
+    /// Neither clipboard nor terminal took copied text
+    Clipboard(io::Error),
+}
+
+impl AppError {
+    /// Failure of netlink request which changes `what`, EPERM means missing capability
+    pub fn netlink(what: impl Into<String>, source: io::Error) -> Self {
+        let what = what.into();
+        if source.kind() == io::ErrorKind::PermissionDenied {
+            AppError::PermissionDenied {
+                what,
+                needs: "CAP_NET_ADMIN",
+            }
+        } else {
+            AppError::NetlinkFailure { what, source }
+        }
+    }
+
+    pub fn rfkill(index: u32, source: io::Error) -> Self {
+        if source.kind() == io::ErrorKind::PermissionDenied {
+            AppError::PermissionDenied {
+                what: "rfkill soft block".to_string(),
+                needs: "root or active session user",
+            }
+        } else {
+            AppError::Rfkill { index, source }
+        }
+    }
+
+    /// Title of popup
+    pub fn header(&self) -> &'static str {
+        match self {
+            AppError::NoInterface => "wifi interface error",
+            AppError::NetlinkFailure { .. } => "netlink error",
+            AppError::PermissionDenied { .. } => "permission denied",
+            AppError::Rfkill { .. } => "rfkill error",
+            AppError::InvalidTxPower(_) => "TX power error",
+            AppError::Backend { .. } => "connection manager error",
+            AppError::Report(_) => "report error",
+            AppError::Note(_) => "note error",
+            AppError::Clipboard(_) => "clipboard error",
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            AppError::NoInterface => i18n::tr("No wifi interface found").to_string(),
+            AppError::NetlinkFailure { what, source } => i18n::fill(
+                i18n::tr("Failed to change {}: {}"),
+                &[what, &source.to_string()],
+            ),
+            AppError::PermissionDenied { what, needs } => i18n::fill(
+                i18n::tr("Permission denied: changing {} needs {}"),
+                &[what, needs],
+            ),
+            AppError::Rfkill { index, source } => i18n::fill(
+                i18n::tr("Failed to change soft block of rfkill{}: {}"),
+                &[&index.to_string(), &source.to_string()],
+            ),
+            AppError::InvalidTxPower(typed) => i18n::fill(
+                i18n::tr("'{}' is not a number of dBm between 0 and 40 or 'auto'"),
+                &[typed],
+            ),
+            AppError::Backend {
+                name,
+                iface,
+                source,
+            } => i18n::fill(
+                i18n::tr("{} refused request for {}: {}"),
+                &[name, iface, &source.to_string()],
+            ),
+            AppError::Report(source) => i18n::fill(
+                i18n::tr("Failed to write session report: {}"),
+                &[&source.to_string()],
+            ),
+            AppError::Note(source) => {
+                i18n::fill(i18n::tr("Failed to save note: {}"), &[&source.to_string()])
+            }
+            AppError::Clipboard(source) => {
+                i18n::fill(i18n::tr("Failed to copy: {}"), &[&source.to_string()])
+            }
+        };
+        f.write_str(&text)
+    }
+}
+
+impl std::error::Error for AppError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AppError::NetlinkFailure { source, .. }
+            | AppError::Rfkill { source, .. }
+            | AppError::Backend { source, .. }
+            | AppError::Report(source)
+            | AppError::Note(source)
+            | AppError::Clipboard(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// `io::Error` is not Clone, errno and message are enough for popup
+fn clone_io(e: &io::Error) -> io::Error {
+    match e.raw_os_error() {
+        Some(errno) => io::Error::from_raw_os_error(errno),
+        None => io::Error::new(e.kind(), e.to_string()),
+    }
+}
+
+impl Clone for AppError {
+    fn clone(&self) -> Self {
+        match self {
+            AppError::NoInterface => AppError::NoInterface,
+            AppError::NetlinkFailure { what, source } => AppError::NetlinkFailure {
+                what: what.clone(),
+                source: clone_io(source),
+            },
+            AppError::PermissionDenied { what, needs } => AppError::PermissionDenied {
+                what: what.clone(),
+                needs,
+            },
+            AppError::Rfkill { index, source } => AppError::Rfkill {
+                index: *index,
+                source: clone_io(source),
+            },
+            AppError::InvalidTxPower(typed) => AppError::InvalidTxPower(typed.clone()),
+            AppError::Backend {
+                name,
+                iface,
+                source,
+            } => AppError::Backend {
+                name,
+                iface: iface.clone(),
+                source: clone_io(source),
+            },
+            AppError::Report(source) => AppError::Report(clone_io(source)),
+            AppError::Note(source) => AppError::Note(clone_io(source)),
+            AppError::Clipboard(source) => AppError::Clipboard(clone_io(source)),
+        }
+    }
+}
+
+/// Errors are equal when user would see the same popup
+impl PartialEq for AppError {
+    fn eq(&self, other: &Self) -> bool {
+        self.header() == other.header() && self.to_string() == other.to_string()
+    }
+}
+
+impl Eq for AppError {}
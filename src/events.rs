@@ -0,0 +1,151 @@
+use std::{collections::VecDeque, sync::Mutex};
+
+use chrono::{DateTime, Local};
+use ratatui::{
+    Frame,
+    layout::{Margin, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, List, ListItem, ListState, Scrollbar, ScrollbarOrientation, ScrollbarState,
+    },
+};
+
+use crate::{i18n, logging};
+
+pub const HINT: &str = "Up/Down to scroll, 'm' for monitoring";
+
+/// Older events are dropped, full history stays in log file
+const CAPACITY: usize = 500;
+
+static EVENTS: Mutex<VecDeque<Event>> = Mutex::new(VecDeque::new());
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// Association, disassociation, roaming between BSSIDs and channel switches
+    Roam,
+    Error,
+    /// Alert rules and detected attacks
+    Alert,
+    Scan,
+    /// Connectivity, DNS and gateway changes
+    Network,
+    /// Config file reloads
+    Config,
+    /// Missed beacons and beacon interval changes of connected AP
+    Beacon,
+}
+
+impl Kind {
+    fn color(&self) -> Color {
+        match self {
+            Kind::Roam => Color::Cyan,
+            Kind::Error => Color::Red,
+            Kind::Alert => Color::Yellow,
+            Kind::Scan => Color::Blue,
+            Kind::Network => Color::Green,
+            Kind::Config => Color::Magenta,
+            Kind::Beacon => Color::LightRed,
+        }
+    }
+}
+
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Kind::Roam => write!(f, "roam"),
+            Kind::Error => write!(f, "error"),
+            Kind::Alert => write!(f, "alert"),
+            Kind::Scan => write!(f, "scan"),
+            Kind::Network => write!(f, "network"),
+            Kind::Config => write!(f, "config"),
+            Kind::Beacon => write!(f, "beacon"),
+        }
+    }
+}
+
+/// Something user may want to see later in log view
+#[derive(Clone, Debug)]
+pub struct Event {
+    pub time: DateTime<Local>,
+    pub kind: Kind,
+    pub message: String,
+}
+
+/// Records event, can be called from any thread
+pub fn push(kind: Kind, message: impl Into<String>) {
+    let mut events = EVENTS.lock().unwrap();
+    if events.len() == CAPACITY {
+        events.pop_front();
+    }
+    events.push_back(Event {
+        time: Local::now(),
+        kind,
+        message: message.into(),
+    });
+}
+
+pub fn count() -> usize {
+    EVENTS.lock().unwrap().len()
+}
+
+/// Copy of recorded events, the newest first
+pub fn recent() -> Vec<Event> {
+    EVENTS.lock().unwrap().iter().rev().cloned().collect()
+}
+
+/// Scrollable list of recent events
+pub fn draw(f: &mut Frame, area: Rect, selected: usize, hide_info: bool) {
+    let events = recent();
+    let mut list_state = ListState::default();
+    if !events.is_empty() {
+        list_state.select(Some(selected.min(events.len() - 1)));
+    }
+    f.render_stateful_widget(list(&events, hide_info), area, &mut list_state);
+    // long log is easier to follow with position shown on the right border
+    let mut scroll = ScrollbarState::new(events.len()).position(selected);
+    f.render_stateful_widget(
+        Scrollbar::new(ScrollbarOrientation::VerticalRight),
+        area.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        &mut scroll,
+    );
+}
+
+/// The newest events without selection, for panel of monitoring
+pub fn draw_recent(f: &mut Frame, area: Rect, hide_info: bool) {
+    f.render_widget(list(&recent(), hide_info), area);
+}
+
+fn list(events: &[Event], hide_info: bool) -> List<'static> {
+    let items: Vec<ListItem> = events
+        .iter()
+        .map(|e| {
+            let message = if hide_info {
+                logging::redact(&e.message)
+            } else {
+                e.message.clone()
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{} ", e.time.format("%H:%M:%S"))),
+                Span::styled(
+                    format!("{:<8}", e.kind.to_string()),
+                    Style::default().fg(e.kind.color()),
+                ),
+                Span::raw(message),
+            ]))
+        })
+        .collect();
+    List::new(items)
+        .block(
+            Block::default()
+                .title(i18n::fill(
+                    i18n::tr("events ({})"),
+                    &[&events.len().to_string()],
+                ))
+                .borders(Borders::ALL),
+        )
+        .highlight_style(Style::default().bg(Color::DarkGray))
+}
@@ -0,0 +1,81 @@
+use std::{
+    fmt::Write as _,
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{Arc, RwLock},
+};
+
+use tracing::{debug, info};
+
+use crate::sampler::Sample;
+
+/// Starts thread which serves latest samples in Prometheus text format
+pub fn spawn(addr: SocketAddr, latest: Arc<RwLock<Vec<Sample>>>) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    info!(%addr, "exporter listening");
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            if let Err(e) = respond(stream, &latest.read().unwrap()) {
+                debug!(error = %e, "exporter request failed");
+            }
+        }
+    });
+    Ok(())
+}
+
+fn respond(mut stream: TcpStream, samples: &[Sample]) -> io::Result<()> {
+    // request itself does not matter, every path returns metrics
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let body = render(samples);
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    )
+}
+
+/// Returns metrics in Prometheus text exposition format
+pub fn render(samples: &[Sample]) -> String {
+    let mut body = String::new();
+    body.push_str(
+        "# HELP wifi_signal_dbm Signal level of BSS in dBm\n# TYPE wifi_signal_dbm gauge\n",
+    );
+    for s in samples.iter().filter(|s| s.connected) {
+        let _ = writeln!(
+            body,
+            "wifi_signal_dbm{{iface=\"{}\",bssid=\"{}\"}} {}",
+            s.iface,
+            s.bssid.as_deref().unwrap_or_default(),
+            s.signal
+        );
+    }
+    body.push_str(
+        "# HELP wifi_connected 1 if interface is associated\n# TYPE wifi_connected gauge\n",
+    );
+    for s in samples {
+        let _ = writeln!(
+            body,
+            "wifi_connected{{iface=\"{}\"}} {}",
+            s.iface, s.connected as u8
+        );
+    }
+    body.push_str(
+        "# HELP wifi_frequency_mhz Frequency of BSS in MHz\n# TYPE wifi_frequency_mhz gauge\n",
+    );
+    for s in samples {
+        if let Some(f) = s.frequency {
+            let _ = writeln!(body, "wifi_frequency_mhz{{iface=\"{}\"}} {}", s.iface, f);
+        }
+    }
+    body.push_str(
+        "# HELP wifi_link_quality Link quality score from 0 to 100\n# TYPE wifi_link_quality gauge\n",
+    );
+    for s in samples {
+        if let Some(score) = s.score {
+            let _ = writeln!(body, "wifi_link_quality{{iface=\"{}\"}} {}", s.iface, score);
+        }
+    }
+    body
+}
@@ -0,0 +1,334 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    ffi::CString,
+    fs::File,
+    io::{self, Read},
+    os::fd::FromRawFd,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use macaddr::MacAddr6;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Paragraph, Row, Table},
+};
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::{events, get_security_info, i18n, sampler};
+
+pub const HINT: &str = "'m' for monitoring";
+
+/// BSSIDs not heard for this long are dropped from table
+const MAX_AGE: Duration = Duration::from_secs(300);
+
+/// Most BSSIDs kept in table, the least recently heard are dropped first
+const MAX_BSSIDS: usize = 512;
+
+const BROADCAST: [u8; 6] = [0xff; 6];
+
+/// `[frames]` section of config
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct FramesConfig {
+    pub enabled: bool,
+    /// Interface which is already in monitor mode, e.g. created with `iw phy phy0 interface add mon0 type monitor`
+    pub iface: Option<String>,
+    /// Amount of deauthentication frames inside `window` which is reported as attack
+    pub deauth_burst: usize,
+    /// Seconds
+    pub window: u64,
+}
+
+impl Default for FramesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            iface: None,
+            deauth_burst: 20,
+            window: 10,
+        }
+    }
+}
+
+/// Management frames seen from one BSSID
+#[derive(Clone, Debug, Default)]
+pub struct Counts {
+    pub beacons: u64,
+    pub probes: u64,
+    /// Deauthentication and disassociation frames
+    pub deauths: u64,
+    /// Time of the latest frame
+    last: Option<Instant>,
+}
+
+/// Counters shared with capture thread
+#[derive(Default)]
+pub struct FrameCounters {
+    pub per_bssid: HashMap<String, Counts>,
+    /// Wildcard probe requests, they are sent to every BSS at once
+    pub broadcast_probes: u64,
+    /// Reason why capture is not running
+    pub error: Option<String>,
+    /// Socket is open and frames are being counted
+    pub running: bool,
+    /// BSSID with deauthentication burst and time of its latest frame
+    attack: Option<(String, Instant)>,
+    window: Duration,
+}
+
+impl FrameCounters {
+    /// Counts of `bssid`, makes room for it by dropping BSSIDs heard long ago
+    fn entry(&mut self, bssid: &str) -> &mut Counts {
+        if !self.per_bssid.contains_key(bssid) {
+            self.per_bssid
+                .retain(|_, c| c.last.is_some_and(|t| t.elapsed() < MAX_AGE));
+            if self.per_bssid.len() >= MAX_BSSIDS
+                && let Some(oldest) = self
+                    .per_bssid
+                    .iter()
+                    .min_by_key(|(_, c)| c.last)
+                    .map(|(b, _)| b.clone())
+            {
+                self.per_bssid.remove(&oldest);
+            }
+        }
+        let counts = self.per_bssid.entry(bssid.to_string()).or_default();
+        counts.last = Some(Instant::now());
+        counts
+    }
+
+    /// BSSID with deauthentication burst inside the latest window
+    pub fn attack(&self) -> Option<&str> {
+        self.attack
+            .as_ref()
+            .filter(|(_, at)| at.elapsed() < self.window)
+            .map(|(bssid, _)| bssid.as_str())
+    }
+}
+
+/// Starts capture thread if enabled in config
+pub fn spawn(config: FramesConfig) -> Arc<RwLock<FrameCounters>> {
+    let counters = Arc::new(RwLock::new(FrameCounters {
+        window: Duration::from_secs(config.window),
+        ..FrameCounters::default()
+    }));
+    if !config.enabled {
+        counters.write().unwrap().error = Some("disabled in config".to_string());
+        return counters;
+    }
+    let shared = counters.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = capture(&config, &shared) {
+            warn!(error = %e, "frame capture stopped");
+            let mut counters = shared.write().unwrap();
+            counters.running = false;
+            counters.error = Some(if e.kind() == io::ErrorKind::PermissionDenied {
+                "Permission denied: capture needs CAP_NET_RAW".to_string()
+            } else {
+                e.to_string()
+            });
+        }
+    });
+    counters
+}
+
+fn capture(config: &FramesConfig, shared: &RwLock<FrameCounters>) -> io::Result<()> {
+    let iface = config.iface.as_deref().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "no monitor interface in config",
+        )
+    })?;
+    if !sampler::is_monitor(iface) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not in monitor mode", iface),
+        ));
+    }
+    let mut socket = open(iface)?;
+    info!(iface, "frame capture started");
+    shared.write().unwrap().running = true;
+
+    let window = Duration::from_secs(config.window);
+    let mut deauths: HashMap<String, VecDeque<Instant>> = HashMap::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        let n = socket.read(&mut buf)?;
+        let Some((kind, bssid)) = parse(&buf[..n]) else {
+            continue;
+        };
+        let mut counters = shared.write().unwrap();
+        let Some(bssid) = bssid else {
+            counters.broadcast_probes += 1;
+            continue;
+        };
+        let counts = counters.entry(&bssid);
+        match kind {
+            Kind::Beacon => counts.beacons += 1,
+            Kind::ProbeRequest => counts.probes += 1,
+            Kind::Deauth => {
+                counts.deauths += 1;
+                let times = deauths.entry(bssid.clone()).or_default();
+                times.push_back(Instant::now());
+                while times.front().is_some_and(|t| t.elapsed() > window) {
+                    times.pop_front();
+                }
+                if times.len() >= config.deauth_burst {
+                    if counters.attack().is_none() {
+                        events::push(
+                            events::Kind::Alert,
+                            format!("deauthentication burst from {}", bssid),
+                        );
+                        warn!(
+                            bssid,
+                            frames = times.len(),
+                            "possible deauthentication attack"
+                        );
+                    }
+                    counters.attack = Some((bssid, Instant::now()));
+                }
+            }
+        }
+    }
+}
+
+/// Opens raw packet socket bound to interface
+fn open(iface: &str) -> io::Result<File> {
+    let name = CString::new(iface).map_err(io::Error::other)?;
+    let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+    if index == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let protocol = (libc::ETH_P_ALL as u16).to_be();
+    let fd = unsafe {
+        libc::socket(
+            libc::AF_PACKET,
+            libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+            protocol.into(),
+        )
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // file closes descriptor on drop and reads whole packets
+    let file = unsafe { File::from_raw_fd(fd) };
+    let mut addr: libc::sockaddr_ll = unsafe { std::mem::zeroed() };
+    addr.sll_family = libc::AF_PACKET as u16;
+    addr.sll_protocol = protocol;
+    addr.sll_ifindex = index as i32;
+    let res = unsafe {
+        libc::bind(
+            fd,
+            &addr as *const libc::sockaddr_ll as *const libc::sockaddr,
+            size_of::<libc::sockaddr_ll>() as u32,
+        )
+    };
+    if res < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(file)
+}
+
+enum Kind {
+    Beacon,
+    ProbeRequest,
+    /// Deauthentication or disassociation
+    Deauth,
+}
+
+/// Parses radiotap header and 802.11 management header, returns frame kind and BSSID
+fn parse(packet: &[u8]) -> Option<(Kind, Option<String>)> {
+    let radiotap_len = usize::from(u16::from_le_bytes([*packet.get(2)?, *packet.get(3)?]));
+    let frame = packet.get(radiotap_len..)?;
+    let fc = *frame.first()?;
+    // only management frames
+    if (fc >> 2) & 0x3 != 0 {
+        return None;
+    }
+    let kind = match fc >> 4 {
+        4 => Kind::ProbeRequest,
+        8 => Kind::Beacon,
+        10 | 12 => Kind::Deauth,
+        _ => return None,
+    };
+    // probe request is sent to addr1, wildcard probe goes to broadcast and belongs to no BSS
+    let at = match kind {
+        Kind::ProbeRequest => 4,
+        _ => 16,
+    };
+    let bssid: [u8; 6] = frame.get(at..at + 6)?.try_into().ok()?;
+    let bssid = (bssid != BROADCAST).then(|| MacAddr6::from(bssid).to_string());
+    Some((kind, bssid))
+}
+
+/// Table of counted frames
+pub fn draw(f: &mut Frame, area: Rect, counters: &FrameCounters, hide_info: bool) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3)])
+        .split(area);
+    let status = match (&counters.error, counters.attack()) {
+        (Some(e), _) => Span::styled(e.clone(), Style::default().fg(Color::Yellow)),
+        (None, Some(bssid)) => Span::styled(
+            format!(
+                "Possible deauthentication attack on {}",
+                get_security_info(bssid, hide_info)
+            ),
+            Style::default()
+                .fg(Color::White)
+                .bg(Color::Red)
+                .add_modifier(Modifier::BOLD),
+        ),
+        (None, None) => Span::raw(format!(
+            "capturing, {} broadcast probe requests",
+            counters.broadcast_probes
+        )),
+    };
+    f.render_widget(
+        Paragraph::new(status).block(
+            Block::default()
+                .title(i18n::tr("status"))
+                .borders(Borders::ALL),
+        ),
+        chunks[0],
+    );
+
+    let mut rows: Vec<(&String, &Counts)> = counters.per_bssid.iter().collect();
+    rows.sort_by_key(|(_, c)| (std::cmp::Reverse(c.deauths), std::cmp::Reverse(c.beacons)));
+    let table = Table::new(
+        rows.into_iter().map(|(bssid, c)| {
+            Row::new(vec![
+                get_security_info(bssid, hide_info),
+                c.beacons.to_string(),
+                c.probes.to_string(),
+                c.deauths.to_string(),
+            ])
+        }),
+        [
+            Constraint::Percentage(40),
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "BSSID",
+            i18n::tr("beacons"),
+            i18n::tr("probes"),
+            i18n::tr("deauth"),
+        ])
+        .style(Style::default().add_modifier(Modifier::ITALIC)),
+    )
+    .block(
+        Block::default()
+            .title(i18n::tr("frames"))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(table, chunks[1]);
+}
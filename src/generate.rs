@@ -0,0 +1,44 @@
+use std::io::{self, Write};
+
+use clap::{CommandFactory, ValueEnum};
+use clap_complete::Shell;
+
+use crate::cli::Cli;
+
+/// Writes what `--generate` asks for to stdout, `completions <shell>` or `man`
+pub fn run(args: &[String]) -> io::Result<()> {
+    let mut command = Cli::command();
+    let mut out = io::stdout().lock();
+    match args {
+        [what, shell] if what == "completions" => {
+            let shell = Shell::from_str(shell, true).map_err(|_| {
+                invalid(format!(
+                    "unknown shell {}, one of {}",
+                    shell,
+                    Shell::value_variants()
+                        .iter()
+                        .map(|s| s.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+            })?;
+            let name = command.get_name().to_string();
+            clap_complete::generate(shell, &mut command, name, &mut out);
+        }
+        [what] if what == "completions" => {
+            return Err(invalid("--generate completions needs shell".to_string()));
+        }
+        [what] if what == "man" => clap_mangen::Man::new(command).render(&mut out)?,
+        _ => {
+            return Err(invalid(format!(
+                "--generate takes 'completions <shell>' or 'man', not '{}'",
+                args.join(" ")
+            )));
+        }
+    }
+    out.flush()
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
@@ -0,0 +1,181 @@
+use std::{
+    collections::VecDeque,
+    convert::Infallible,
+    io,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::KeyCode;
+use ratatui::{
+    backend::{Backend, ClearType, TestBackend, WindowSize},
+    buffer::Cell,
+    layout::{Position, Size},
+};
+
+use crate::snapshot;
+
+/// Screen size of `--keys` runs, big enough for the full monitoring layout
+pub const SIZE: Size = Size {
+    width: 120,
+    height: 40,
+};
+
+/// Frames drawn after the last key, actions of views are taken one frame after the key
+const IDLE_FRAMES: u8 = 2;
+
+/// In-memory terminal, test backend with the same error type as crossterm one
+pub struct Headless(TestBackend);
+
+impl Headless {
+    pub fn new(size: Size) -> Self {
+        Self(TestBackend::new(size.width, size.height))
+    }
+
+    /// Last drawn screen as text
+    pub fn screen(&self) -> String {
+        let buffer = self.0.buffer();
+        snapshot::text(buffer.area, buffer)
+    }
+}
+
+impl Backend for Headless {
+    type Error = io::Error;
+
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        ok(self.0.draw(content))
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        ok(self.0.hide_cursor())
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        ok(self.0.show_cursor())
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        ok(self.0.get_cursor_position())
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        ok(self.0.set_cursor_position(position))
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        ok(self.0.clear())
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        ok(self.0.clear_region(clear_type))
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        ok(self.0.size())
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        ok(self.0.window_size())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        ok(self.0.flush())
+    }
+}
+
+/// Test backend never fails
+fn ok<T>(result: Result<T, Infallible>) -> io::Result<T> {
+    let Ok(value) = result;
+    Ok(value)
+}
+
+/// Keys pressed one per frame instead of reading terminal
+pub struct Keys {
+    keys: VecDeque<KeyCode>,
+    idle: u8,
+    /// Frames are drawn without pause until this time after the last key, for `--bench`
+    deadline: Option<Instant>,
+}
+
+impl Keys {
+    /// `m<Tab><Esc>` presses 'm', Tab and Esc, `<lt>` is '<' itself
+    pub fn parse(script: &str) -> io::Result<Keys> {
+        let mut keys = VecDeque::new();
+        let mut rest = script;
+        while let Some(c) = rest.chars().next() {
+            if c != '<' {
+                keys.push_back(KeyCode::Char(c));
+                rest = &rest[c.len_utf8()..];
+                continue;
+            }
+            let Some(end) = rest.find('>') else {
+                return Err(invalid(rest));
+            };
+            keys.push_back(key(&rest[1..end]).ok_or_else(|| invalid(&rest[..=end]))?);
+            rest = &rest[end + 1..];
+        }
+        Ok(Keys {
+            keys,
+            idle: IDLE_FRAMES,
+            deadline: None,
+        })
+    }
+
+    /// Keeps drawing as fast as possible for `duration`
+    pub fn run_for(mut self, duration: Duration) -> Keys {
+        self.deadline = Some(Instant::now() + duration);
+        self
+    }
+
+    /// false if frames are not paced by frame timer
+    pub fn paced(&self) -> bool {
+        self.deadline.is_none()
+    }
+}
+
+impl Iterator for Keys {
+    /// Key of frame, None for frames after the last key
+    type Item = Option<KeyCode>;
+
+    fn next(&mut self) -> Option<Option<KeyCode>> {
+        if let Some(key) = self.keys.pop_front() {
+            return Some(Some(key));
+        }
+        if let Some(deadline) = self.deadline {
+            return (Instant::now() < deadline).then_some(None);
+        }
+        if self.idle == 0 {
+            return None;
+        }
+        self.idle -= 1;
+        Some(None)
+    }
+}
+
+fn invalid(key: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("unknown key in --keys: {}", key),
+    )
+}
+
+fn key(name: &str) -> Option<KeyCode> {
+    Some(match name {
+        "Esc" => KeyCode::Esc,
+        "Enter" => KeyCode::Enter,
+        "Tab" => KeyCode::Tab,
+        "Backspace" => KeyCode::Backspace,
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "PageUp" => KeyCode::PageUp,
+        "PageDown" => KeyCode::PageDown,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "lt" => KeyCode::Char('<'),
+        f => KeyCode::F(f.strip_prefix('F')?.parse().ok()?),
+    })
+}
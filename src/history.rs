@@ -0,0 +1,380 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    os::{
+        fd::{AsRawFd, RawFd},
+        unix::fs::MetadataExt,
+    },
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, DurationRound, Local, TimeDelta};
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::{
+    config::InterfacesConfig,
+    connection::Connection,
+    sampler::{self, Sample},
+};
+
+const HEADER: &str = "time,iface,bssid,signal,frequency,connected,score";
+
+/// How often old rows are downsampled and removed
+const COMPACT_EVERY: Duration = Duration::from_secs(3600);
+
+/// `[retention]` section of config, keeps `history.csv` from growing forever
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct RetentionConfig {
+    /// Hours samples are kept at full resolution, older ones are merged into one per minute
+    pub full_hours: u64,
+    /// Days history is kept at all, 0 keeps everything
+    pub days: u64,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            full_hours: 24,
+            days: 30,
+        }
+    }
+}
+
+/// Signal history stored as csv file, shared by daemon and TUI
+pub struct History {
+    file: File,
+    path: PathBuf,
+    /// Lock file next to history, compaction replaces history itself
+    lock: File,
+    retention: RetentionConfig,
+    compacted: Option<Instant>,
+}
+
+/// Holds exclusive advisory lock on descriptor of lock file until dropped
+struct Locked(RawFd);
+
+impl Drop for Locked {
+    fn drop(&mut self) {
+        unsafe { libc::flock(self.0, libc::LOCK_UN) };
+    }
+}
+
+impl History {
+    /// Opens history file for appending, writes header into new file
+    pub fn open(path: &Path, retention: RetentionConfig) -> io::Result<History> {
+        let lock = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path.with_extension("csv.lock"))?;
+        let history = History {
+            file: open_append(path)?,
+            path: path.to_path_buf(),
+            lock,
+            retention,
+            compacted: None,
+        };
+        Ok(history)
+    }
+
+    /// Applies retention of reloaded config on next compaction
+    pub fn set_retention(&mut self, retention: RetentionConfig) {
+        self.retention = retention;
+    }
+
+    /// Waits for other writers, then follows file if other process compacted it meanwhile
+    fn lock(&mut self) -> io::Result<Locked> {
+        if unsafe { libc::flock(self.lock.as_raw_fd(), libc::LOCK_EX) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let replaced = fs::metadata(&self.path).map_or(true, |m| {
+            m.ino() != self.file.metadata().map_or(0, |f| f.ino())
+        });
+        let locked = Locked(self.lock.as_raw_fd());
+        if replaced {
+            self.file = open_append(&self.path)?;
+        }
+        Ok(locked)
+    }
+
+    /// Appends sample, compacts file first when it was not done for a while
+    pub fn append(&mut self, sample: &Sample) -> io::Result<()> {
+        if self.compacted.is_none_or(|t| t.elapsed() >= COMPACT_EVERY) {
+            self.compacted = Some(Instant::now());
+            if let Err(e) = self.compact() {
+                warn!(error = %e, "failed to compact history");
+            }
+        }
+        let _locked = self.lock()?;
+        writeln!(self.file, "{}", line(sample))
+    }
+
+    /// Rewrites file with old samples downsampled and expired ones removed
+    pub fn compact(&mut self) -> io::Result<()> {
+        let now = Local::now();
+        let full_since = now - TimeDelta::hours(self.retention.full_hours as i64);
+        let keep_since =
+            (self.retention.days > 0).then(|| now - TimeDelta::days(self.retention.days as i64));
+        let path = self.path.clone();
+        let _locked = self.lock()?;
+        let tmp = path.with_extension("csv.tmp");
+        let mut out = BufWriter::new(File::create(&tmp)?);
+        writeln!(out, "{}", HEADER)?;
+        // (minute, iface) of old samples which can still get rows, rows are in time order
+        // except for a few written by the other process at the same time
+        let mut minutes: BTreeMap<(DateTime<Local>, String), Minute> = BTreeMap::new();
+        let (mut rows, mut kept) = (0, 0);
+        for text in BufReader::new(File::open(&path)?).lines() {
+            let Some(sample) = parse(&text?) else {
+                continue;
+            };
+            rows += 1;
+            if keep_since.is_some_and(|since| sample.time < since) {
+                continue;
+            }
+            let minute = sample
+                .time
+                .duration_trunc(TimeDelta::minutes(1))
+                .unwrap_or(sample.time);
+            // minute older than previous one is not going to get more rows
+            let done = minute - TimeDelta::minutes(1);
+            while let Some(entry) = minutes.first_entry()
+                && (entry.key().0 < done || sample.time >= full_since)
+            {
+                writeln!(out, "{}", line(&entry.remove().merged()))?;
+                kept += 1;
+            }
+            if sample.time >= full_since {
+                writeln!(out, "{}", line(&sample))?;
+                kept += 1;
+                continue;
+            }
+            minutes
+                .entry((minute, sample.iface.clone()))
+                .or_insert_with(|| Minute::new(minute, &sample))
+                .add(sample);
+        }
+        for minute in minutes.into_values() {
+            writeln!(out, "{}", line(&minute.merged()))?;
+            kept += 1;
+        }
+        out.flush()?;
+        drop(out);
+        fs::rename(&tmp, &path)?;
+        // old handle still points to replaced file
+        self.file = open_append(&path)?;
+        info!(rows, kept, "history compacted");
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    let new = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if new {
+        writeln!(file, "{}", HEADER)?;
+    }
+    Ok(file)
+}
+
+/// Samples of one minute merged into a row, signal averages only connected ones
+struct Minute {
+    time: DateTime<Local>,
+    sum: i64,
+    connected: i64,
+    last: Sample,
+    last_connected: Option<Sample>,
+}
+
+impl Minute {
+    fn new(time: DateTime<Local>, sample: &Sample) -> Self {
+        Self {
+            time,
+            sum: 0,
+            connected: 0,
+            last: sample.clone(),
+            last_connected: None,
+        }
+    }
+
+    fn add(&mut self, sample: Sample) {
+        if sample.connected {
+            self.sum += i64::from(sample.signal);
+            self.connected += 1;
+            self.last_connected = Some(sample.clone());
+        }
+        self.last = sample;
+    }
+
+    /// Average of connected samples with the latest association, or the last sample
+    /// if interface was not connected at all, its signal is of some other BSS
+    fn merged(self) -> Sample {
+        match self.last_connected {
+            Some(last) => Sample {
+                time: self.time,
+                signal: (self.sum as f64 / self.connected as f64).round() as i32,
+                ..last
+            },
+            None => Sample {
+                time: self.time,
+                ..self.last
+            },
+        }
+    }
+}
+
+/// Records samples of all interfaces while TUI runs
+pub struct Recorder {
+    history: History,
+    last: Option<Instant>,
+}
+
+impl Recorder {
+    pub fn new(path: &Path, retention: RetentionConfig) -> io::Result<Self> {
+        Ok(Self {
+            history: History::open(path, retention)?,
+            last: None,
+        })
+    }
+
+    pub fn set_retention(&mut self, retention: RetentionConfig) {
+        self.history.set_retention(retention);
+    }
+
+    /// Appends samples if `interval` passed since previous ones
+    pub fn record(
+        &mut self,
+        connection: &mut Connection,
+        filter: &InterfacesConfig,
+        interval: u64,
+    ) {
+        if self
+            .last
+            .is_some_and(|t| t.elapsed() < Duration::from_secs(interval))
+        {
+            return;
+        }
+        self.last = Some(Instant::now());
+        let Some(samples) = connection.with(|s| sampler::collect(s, filter)) else {
+            return;
+        };
+        for sample in &samples {
+            if let Err(e) = self.history.append(sample) {
+                warn!(error = %e, "failed to append history");
+            }
+        }
+    }
+}
+
+/// Row of history file, other files share only columns of `row`
+fn line(sample: &Sample) -> String {
+    format!(
+        "{},{}",
+        row(sample),
+        sample.score.map(|s| s.to_string()).unwrap_or_default()
+    )
+}
+
+/// Formats sample as csv row without line break
+pub fn row(sample: &Sample) -> String {
+    format!(
+        "{},{},{},{},{},{}",
+        sample.time.to_rfc3339(),
+        sample.iface,
+        sample.bssid.as_deref().unwrap_or_default(),
+        sample.signal,
+        sample.frequency.map(|f| f.to_string()).unwrap_or_default(),
+        sample.connected
+    )
+}
+
+/// Parses csv row written by `row`, header and broken rows give None
+pub fn parse(line: &str) -> Option<Sample> {
+    let mut fields = line.split(',');
+    let time = DateTime::parse_from_rfc3339(fields.next()?).ok()?;
+    let iface = fields.next()?.to_string();
+    let bssid = Some(fields.next()?.to_string()).filter(|b| !b.is_empty());
+    let signal = fields.next()?.parse().ok()?;
+    let frequency = fields.next()?.parse().ok();
+    let connected = fields.next()?.parse().ok()?;
+    // rows written before score was recorded end here
+    let score = fields.next().and_then(|s| s.parse().ok());
+    Some(Sample {
+        time: time.with_timezone(&Local),
+        iface,
+        bssid,
+        // not written to csv
+        ssid: None,
+        signal,
+        frequency,
+        connected,
+        bitrate: None,
+        score,
+    })
+}
+
+/// Returns up to `limit` newest samples of history file
+pub fn tail(path: &Path, limit: usize) -> io::Result<Vec<Sample>> {
+    let content = fs::read_to_string(path)?;
+    let mut samples: Vec<Sample> = content
+        .lines()
+        .rev()
+        .filter_map(parse)
+        .take(limit)
+        .collect();
+    samples.reverse();
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(time: DateTime<Local>, signal: i32, connected: bool) -> Sample {
+        Sample {
+            time,
+            iface: "wlan0".to_string(),
+            bssid: Some("02:00:00:00:02:00".to_string()),
+            ssid: None,
+            signal,
+            frequency: Some(5180),
+            connected,
+            bitrate: None,
+            score: None,
+        }
+    }
+
+    #[test]
+    fn compact_downsamples_old_rows_and_drops_expired_ones() {
+        let dir = std::env::temp_dir().join(format!("wifi-history-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("history.csv");
+        let now = Local::now();
+        let minute = (now - TimeDelta::days(2))
+            .duration_trunc(TimeDelta::minutes(1))
+            .unwrap();
+        let rows = [
+            sample(now - TimeDelta::days(40), -30, true),
+            sample(minute + TimeDelta::seconds(1), -50, true),
+            sample(minute + TimeDelta::seconds(2), -60, true),
+            // disconnected sample is of some other BSS
+            sample(minute + TimeDelta::seconds(3), -90, false),
+            sample(now - TimeDelta::minutes(1), -40, true),
+        ];
+        let text: String = rows.iter().map(|s| format!("{}\n", line(s))).collect();
+        fs::write(&path, format!("{}\n{}", HEADER, text)).unwrap();
+        let mut history = History::open(&path, RetentionConfig::default()).unwrap();
+        history.compact().unwrap();
+        let samples = tail(&path, 10).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].time, minute);
+        assert_eq!(samples[0].signal, -55);
+        assert!(samples[0].connected);
+        assert_eq!(samples[1].signal, -40);
+    }
+}
@@ -0,0 +1,105 @@
+use std::{collections::HashMap, process::Command};
+
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::{alert::AlertChange, sampler::Sample};
+
+/// `[hooks]` section of config, shell commands which get event details in `WIFI_*` variables
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    /// Interface associated, `WIFI_BSSID` is the new BSS
+    pub on_connect: Option<String>,
+    /// Interface lost association, `WIFI_OLD_BSSID` is the BSS it left
+    pub on_disconnect: Option<String>,
+    /// Interface moved to another BSS, both `WIFI_OLD_BSSID` and `WIFI_BSSID` are set
+    pub on_roam: Option<String>,
+    /// `[[alert]]` rule fired or resolved, `WIFI_RULE` and `WIFI_ALERT_STATE` are set
+    pub on_threshold: Option<String>,
+}
+
+/// Runs hook commands on association changes and alerts
+pub struct Hooks {
+    config: HooksConfig,
+    /// Connected BSSID of every interface seen in the previous sample
+    bssids: HashMap<String, Option<String>>,
+}
+
+impl Hooks {
+    pub fn new(config: HooksConfig) -> Self {
+        Self {
+            config,
+            bssids: HashMap::new(),
+        }
+    }
+
+    /// Compares sample with previous one of the same interface, first sample runs nothing
+    pub fn observe(&mut self, sample: &Sample) {
+        let bssid = sample.bssid.clone().filter(|_| sample.connected);
+        let (event, command, old) = match (
+            self.bssids.insert(sample.iface.clone(), bssid.clone()),
+            &bssid,
+        ) {
+            (Some(Some(old)), Some(new)) if old != *new => {
+                ("roam", &self.config.on_roam, Some(old))
+            }
+            (Some(None), Some(_)) => ("connect", &self.config.on_connect, None),
+            (Some(Some(old)), None) => ("disconnect", &self.config.on_disconnect, Some(old)),
+            _ => return,
+        };
+        if let Some(command) = command {
+            let mut env = sample_env(event, sample);
+            if let Some(old) = old {
+                env.push(("WIFI_OLD_BSSID", old));
+            }
+            run(command, env);
+        }
+    }
+
+    /// Runs threshold hook for alert rule which fired or resolved on sample
+    pub fn threshold(&self, change: &AlertChange, sample: &Sample) {
+        if let Some(command) = &self.config.on_threshold {
+            let mut env = sample_env("threshold", sample);
+            env.push(("WIFI_RULE", change.rule.clone()));
+            env.push((
+                "WIFI_ALERT_STATE",
+                if change.fired { "fired" } else { "resolved" }.to_string(),
+            ));
+            run(command, env);
+        }
+    }
+}
+
+fn sample_env(event: &str, sample: &Sample) -> Vec<(&'static str, String)> {
+    vec![
+        ("WIFI_EVENT", event.to_string()),
+        ("WIFI_IFACE", sample.iface.clone()),
+        (
+            "WIFI_BSSID",
+            sample
+                .bssid
+                .clone()
+                .filter(|_| sample.connected)
+                .unwrap_or_default(),
+        ),
+        ("WIFI_SIGNAL", sample.signal.to_string()),
+        (
+            "WIFI_FREQUENCY",
+            sample.frequency.map(|f| f.to_string()).unwrap_or_default(),
+        ),
+    ]
+}
+
+/// Starts shell command without waiting for it
+pub fn run(command: &str, env: Vec<(&'static str, String)>) {
+    info!(command, ?env, "running hook");
+    let res = Command::new("sh").args(["-c", command]).envs(env).spawn();
+    match res {
+        // wait in background so finished command doesn't stay zombie
+        Ok(mut child) => {
+            std::thread::spawn(move || child.wait());
+        }
+        Err(e) => warn!(error = %e, command, "failed to run hook"),
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    path::PathBuf,
+    sync::{
+        Arc, Mutex, RwLock,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tracing::{debug, info, warn};
+
+use crate::{
+    config::InterfacesConfig,
+    connection::Connection,
+    get_security_info, history,
+    sampler::{self, Sample},
+    scan, ws,
+};
+
+/// Page with live chart and scan table, talks to `/ws` and `/scan`
+const DASHBOARD: &str = include_str!("dashboard.html");
+
+/// Client which sends or reads nothing for this long is dropped
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Amount of history samples returned when request has no `limit`
+const HISTORY_LIMIT: usize = 300;
+
+/// Longest request or header line, longer request is refused
+const MAX_LINE: u64 = 8 * 1024;
+
+/// Most headers read from one request
+const MAX_HEADERS: usize = 64;
+
+/// `[http]` section of config, used by daemon with `--http` and by TUI for its agents
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct HttpConfig {
+    /// Clients send it as `Authorization: Bearer` header or `?token=`, needed unless
+    /// address is loopback
+    pub token: Option<String>,
+    /// Connections served at once, the others get 503
+    pub clients: usize,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            token: None,
+            clients: 16,
+        }
+    }
+}
+
+/// What daemon shares with HTTP clients
+pub struct Shared {
+    pub latest: Arc<RwLock<Vec<Sample>>>,
+    /// History file, None if recording is off
+    pub history: Option<PathBuf>,
+    pub interfaces: InterfacesConfig,
+    /// Hides BSSIDs and SSIDs the same way as in log
+    pub privacy: bool,
+    pub token: Option<String>,
+}
+
+/// Frees place of connection when its thread ends
+struct Slot(Arc<AtomicUsize>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Starts thread which serves status, history and scan as JSON, `/ws` streams samples and
+/// `/` is dashboard for browser
+pub fn spawn(addr: SocketAddr, shared: Shared, clients: usize) -> io::Result<()> {
+    if shared.token.is_none() && !addr.ip().is_loopback() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("http api on {} needs `token` in [http] section", addr),
+        ));
+    }
+    let listener = TcpListener::bind(addr)?;
+    info!(%addr, "http api listening");
+    let shared = Arc::new(shared);
+    // scan needs BSS dump, sampler of daemon keeps only connected BSS
+    let connection = Arc::new(Mutex::new(Connection::new()));
+    let active = Arc::new(AtomicUsize::new(0));
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            if active.fetch_add(1, Ordering::Relaxed) >= clients {
+                active.fetch_sub(1, Ordering::Relaxed);
+                warn!(clients, "too many http clients, refusing connection");
+                let _ = stream.set_write_timeout(Some(TIMEOUT)).and_then(|_| {
+                    write_response(
+                        &mut stream,
+                        "503 Service Unavailable",
+                        "application/json",
+                        &json!({"error": "too many clients"}).to_string(),
+                    )
+                });
+                continue;
+            }
+            let slot = Slot(active.clone());
+            let shared = shared.clone();
+            let connection = connection.clone();
+            // idle client must not hold others back
+            std::thread::spawn(move || {
+                let _slot = slot;
+                if let Err(e) = respond(stream, &shared, &connection) {
+                    debug!(error = %e, "http request failed");
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+/// Reads line of at most [`MAX_LINE`] bytes
+fn read_line(reader: &mut impl BufRead, line: &mut String) -> io::Result<usize> {
+    let read = reader.take(MAX_LINE).read_line(line)?;
+    if read as u64 == MAX_LINE && !line.ends_with('\n') {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "line too long"));
+    }
+    Ok(read)
+}
+
+fn respond(
+    mut stream: TcpStream,
+    shared: &Shared,
+    connection: &Mutex<Connection>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    read_line(&mut reader, &mut request_line)?;
+    // only websocket key and token are used, but client expects headers to be read
+    let mut key = None;
+    let mut bearer = None;
+    let mut header = String::new();
+    for _ in 0..MAX_HEADERS {
+        if read_line(&mut reader, &mut header)? <= 2 {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("sec-websocket-key") {
+                key = Some(value.trim().to_string());
+            } else if name.eq_ignore_ascii_case("authorization") {
+                bearer = value.trim().strip_prefix("Bearer ").map(str::to_string);
+            }
+        }
+        header.clear();
+    }
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or("/"),
+    );
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    debug!(method, path, "http request");
+    if let Some(token) = &shared.token {
+        let given = bearer.or_else(|| param(query, "token").map(str::to_string));
+        if given.as_ref() != Some(token) {
+            return write_response(
+                &mut stream,
+                "401 Unauthorized",
+                "application/json",
+                &json!({"error": "token is missing or wrong"}).to_string(),
+            );
+        }
+    }
+    if let ("GET", "/ws", Some(key)) = (method, path, &key) {
+        return ws::serve(stream, key, &shared.latest, shared.privacy);
+    }
+    if let ("GET", "/") = (method, path) {
+        return write_response(&mut stream, "200 OK", "text/html; charset=utf-8", DASHBOARD);
+    }
+    let (status, body) = match (method, path) {
+        ("GET", "/status") => ("200 OK", status(shared)),
+        ("GET", "/history") => match history_json(shared, query) {
+            Ok(body) => ("200 OK", body),
+            Err(e) => ("500 Internal Server Error", json!({"error": e.to_string()})),
+        },
+        ("GET", "/scan") => match scan_json(shared, &mut connection.lock().unwrap()) {
+            Some(body) => ("200 OK", body),
+            None => (
+                "503 Service Unavailable",
+                json!({"error": "nl80211 is not available"}),
+            ),
+        },
+        ("GET", _) => ("404 Not Found", json!({"error": "not found"})),
+        _ => (
+            "405 Method Not Allowed",
+            json!({"error": "only GET is supported"}),
+        ),
+    };
+    write_response(&mut stream, status, "application/json", &body.to_string())
+}
+
+/// Writes whole response and closes connection
+fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+/// Value of `name=value` parameter of query
+fn param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .find_map(|p| p.strip_prefix(name)?.strip_prefix('='))
+}
+
+/// Sample as JSON, with hidden BSSID and SSID if `privacy` is set
+pub fn sample_json(sample: &Sample, privacy: bool) -> Value {
+    let mut value = sample.json();
+    if privacy {
+        hide(&mut value, "bssid");
+        hide(&mut value, "ssid");
+    }
+    value
+}
+
+/// Replaces string field with its hash, as 'h' does on screen
+fn hide(value: &mut Value, field: &str) {
+    if let Some(text) = value[field].as_str() {
+        value[field] = get_security_info(text, true).into();
+    }
+}
+
+fn status(shared: &Shared) -> Value {
+    let latest = shared.latest.read().unwrap();
+    json!({"samples": latest.iter().map(|s| sample_json(s, shared.privacy)).collect::<Vec<_>>()})
+}
+
+/// Newest samples of history file, `?limit=N` changes their amount
+fn history_json(shared: &Shared, query: &str) -> io::Result<Value> {
+    let limit = param(query, "limit")
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(HISTORY_LIMIT);
+    let samples = match &shared.history {
+        Some(path) => history::tail(path, limit)?,
+        None => Vec::new(),
+    };
+    Ok(
+        json!({"samples": samples.iter().map(|s| sample_json(s, shared.privacy)).collect::<Vec<_>>()}),
+    )
+}
+
+/// BSSes seen by every interface, strongest first
+fn scan_json(shared: &Shared, connection: &mut Connection) -> Option<Value> {
+    let devices = connection.devices(&shared.interfaces)?;
+    let interfaces: Vec<Value> = devices
+        .iter()
+        .map(|(interface, bss_list)| {
+            let networks: Vec<Value> = scan::rows(bss_list)
+                .iter()
+                .map(|r| {
+                    let mut network = json!({
+                        "ssid": r.ssid,
+                        "bssid": r.bssid,
+                        "signal": r.signal,
+                        "frequency": r.frequency,
+                        "connected": r.connected,
+                        "dfs": r.dfs,
+                        "security": r.security.to_string(),
+                        "pmf": r.pmf.to_string(),
+                        "wps": r.wps,
+                    });
+                    if shared.privacy {
+                        hide(&mut network, "ssid");
+                        hide(&mut network, "bssid");
+                    }
+                    network
+                })
+                .collect();
+            json!({"iface": sampler::iface_name(interface), "networks": networks})
+        })
+        .collect();
+    Some(json!({ "interfaces": interfaces }))
+}
@@ -0,0 +1,74 @@
+use std::{
+    collections::HashMap,
+    env,
+    sync::{LazyLock, RwLock},
+};
+
+use tracing::{info, warn};
+
+/// Catalogs by language code, English text is the key, so English needs none
+const CATALOGS: [(&str, &str); 1] = [("ru", include_str!("locales/ru.toml"))];
+
+type Catalog = HashMap<String, String>;
+
+static LOADED: LazyLock<HashMap<&'static str, Catalog>> = LazyLock::new(|| {
+    CATALOGS
+        .iter()
+        .map(|(language, raw)| {
+            let catalog = toml::from_str(raw).unwrap_or_else(|e| {
+                warn!(language, error = %e, "broken message catalog");
+                Catalog::new()
+            });
+            (*language, catalog)
+        })
+        .collect()
+});
+
+/// Language of UI, None is English
+static LANGUAGE: RwLock<Option<&'static str>> = RwLock::new(None);
+
+/// `ru_RU.UTF-8` from LC_ALL, LC_MESSAGES or LANG as `ru`
+fn env_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|v| env::var(v).ok())
+        .find(|v| !v.is_empty())
+        .and_then(|v| v.split(['_', '.', '@']).next().map(str::to_lowercase))
+}
+
+/// Selects language from config, environment is used when config has none
+pub fn set(config: Option<&str>) {
+    let wanted = config.map(str::to_lowercase).or_else(env_language);
+    let language = wanted.as_deref().and_then(|wanted| {
+        let found = CATALOGS.iter().map(|(l, _)| *l).find(|l| *l == wanted);
+        // C and POSIX locales mean English as well
+        if found.is_none() && !matches!(wanted, "en" | "c" | "posix") {
+            warn!(language = wanted, "no messages for language, using English");
+        }
+        found
+    });
+    info!(?language, "selected UI language");
+    *LANGUAGE.write().unwrap() = language;
+}
+
+/// Translation of English `text`, the text itself if catalog has none
+pub fn tr(text: &str) -> &str {
+    let Some(language) = *LANGUAGE.read().unwrap() else {
+        return text;
+    };
+    LOADED
+        .get(language)
+        .and_then(|c| c.get(text))
+        .map_or(text, String::as_str)
+}
+
+/// Replaces `{}` of translated template with `args` in order
+pub fn fill(template: &str, args: &[&str]) -> String {
+    let mut parts = template.split("{}");
+    let mut text = parts.next().unwrap_or_default().to_string();
+    for (i, part) in parts.enumerate() {
+        text.push_str(args.get(i).copied().unwrap_or_default());
+        text.push_str(part);
+    }
+    text
+}
@@ -0,0 +1,239 @@
+/// Iterates over 802.11 information elements as (element id, data) pairs
+pub fn elements(ies: &[u8]) -> impl Iterator<Item = (u8, &[u8])> {
+    let mut rest = ies;
+    std::iter::from_fn(move || {
+        let [id, len, tail @ ..] = rest else {
+            return None;
+        };
+        let data = tail.get(..usize::from(*len))?;
+        rest = &tail[usize::from(*len)..];
+        Some((*id, data))
+    })
+}
+
+/// Returns SSID from information elements, None for hidden networks
+pub fn ssid(ies: &[u8]) -> Option<String> {
+    let (_, data) = elements(ies).find(|(id, _)| *id == 0)?;
+    if data.is_empty() || data.iter().all(|b| *b == 0) {
+        return None;
+    }
+    Some(String::from_utf8_lossy(data).to_string())
+}
+
+/// Country code from country element, the third byte about environment is ignored
+pub fn country(ies: &[u8]) -> Option<String> {
+    let (_, data) = elements(ies).find(|(id, _)| *id == 7)?;
+    let code = data.get(..2)?;
+    code.iter()
+        .all(u8::is_ascii_alphabetic)
+        .then(|| String::from_utf8_lossy(code).to_ascii_uppercase())
+}
+
+/// Security of network advertised in information elements
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Security {
+    Open,
+    Wpa,
+    Wpa2,
+    Wpa3,
+}
+
+impl std::fmt::Display for Security {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Security::Open => write!(f, "open"),
+            Security::Wpa => write!(f, "WPA"),
+            Security::Wpa2 => write!(f, "WPA2"),
+            Security::Wpa3 => write!(f, "WPA3"),
+        }
+    }
+}
+
+/// Management frame protection advertised in RSN capabilities
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pmf {
+    Disabled,
+    Capable,
+    Required,
+}
+
+impl std::fmt::Display for Pmf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pmf::Disabled => write!(f, "no PMF"),
+            Pmf::Capable => write!(f, "PMF"),
+            Pmf::Required => write!(f, "PMF required"),
+        }
+    }
+}
+
+/// Offset and count of AKM suites in RSN element
+fn akm(rsn: &[u8]) -> (usize, usize) {
+    // version (2), group cipher (4), pairwise count (2) and suites, akm count (2) and suites
+    let pairwise = rsn
+        .get(6..8)
+        .map_or(0, |c| u16::from_le_bytes([c[0], c[1]]));
+    let akm_start = 8 + 4 * usize::from(pairwise);
+    let akm_count = rsn
+        .get(akm_start..akm_start + 2)
+        .map_or(0, |c| u16::from_le_bytes([c[0], c[1]]));
+    (akm_start, usize::from(akm_count))
+}
+
+/// PMF from RSN capabilities which follow AKM suites, networks without RSN have none
+pub fn pmf(ies: &[u8]) -> Pmf {
+    let Some((_, rsn)) = elements(ies).find(|(id, _)| *id == 48) else {
+        return Pmf::Disabled;
+    };
+    let (akm_start, akm_count) = akm(rsn);
+    let at = akm_start + 2 + 4 * akm_count;
+    let Some(&[low, _]) = rsn.get(at..at + 2) else {
+        return Pmf::Disabled;
+    };
+    // bit 6 is MFPR, bit 7 MFPC
+    match (low & 0x40 != 0, low & 0x80 != 0) {
+        (true, _) => Pmf::Required,
+        (false, true) => Pmf::Capable,
+        _ => Pmf::Disabled,
+    }
+}
+
+/// true for Wi-Fi Direct group owners, they send Wi-Fi Alliance P2P element
+pub fn p2p(ies: &[u8]) -> bool {
+    elements(ies).any(|(id, data)| id == 221 && data.starts_with(&[0x50, 0x6f, 0x9a, 0x09]))
+}
+
+/// true for hotspots which mark network as metered in Apple vendor element (00:17:f2, type 6),
+/// as iPhones do, other phones are not recognized
+pub fn hotspot(ies: &[u8]) -> bool {
+    elements(ies).any(|(id, data)| id == 221 && data.starts_with(&[0x00, 0x17, 0xf2, 0x06]))
+}
+
+/// true when AP advertises Wi-Fi Protected Setup in Microsoft vendor element
+pub fn wps(ies: &[u8]) -> bool {
+    elements(ies).any(|(id, data)| id == 221 && data.starts_with(&[0x00, 0x50, 0xf2, 0x04]))
+}
+
+/// Chipset vendors by OUI of their vendor specific elements
+const VENDORS: [([u8; 3], &str); 10] = [
+    ([0x00, 0x10, 0x18], "Broadcom"),
+    ([0x00, 0x90, 0x4c], "Broadcom"),
+    ([0x00, 0x03, 0x7f], "Qualcomm"),
+    ([0x8c, 0xfd, 0xf0], "Qualcomm"),
+    ([0x00, 0xe0, 0x4c], "Realtek"),
+    ([0x00, 0x0c, 0xe7], "MediaTek"),
+    ([0x00, 0x0c, 0x43], "Ralink"),
+    ([0x00, 0x40, 0x96], "Cisco"),
+    ([0x00, 0x0b, 0x86], "Aruba"),
+    ([0x00, 0x13, 0x92], "Ruckus"),
+];
+
+/// Name of chipset or AP vendor from the first known vendor specific element
+pub fn vendor(ies: &[u8]) -> Option<&'static str> {
+    elements(ies)
+        .filter(|(id, _)| *id == 221)
+        .find_map(|(_, data)| VENDORS.iter().find(|(oui, _)| data.starts_with(oui)))
+        .map(|(_, name)| *name)
+}
+
+/// Returns the strongest security advertised by RSN or WPA element
+pub fn security(ies: &[u8]) -> Security {
+    if let Some((_, rsn)) = elements(ies).find(|(id, _)| *id == 48) {
+        let (akm_start, akm_count) = akm(rsn);
+        let sae = (0..akm_count).any(|i| {
+            let at = akm_start + 2 + 4 * i;
+            // SAE and FT over SAE
+            matches!(rsn.get(at..at + 4), Some([0x00, 0x0f, 0xac, 8 | 9]))
+        });
+        return if sae { Security::Wpa3 } else { Security::Wpa2 };
+    }
+    let wpa =
+        elements(ies).any(|(id, data)| id == 221 && data.starts_with(&[0x00, 0x50, 0xf2, 0x01]));
+    if wpa { Security::Wpa } else { Security::Open }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SSID: [u8; 6] = [0, 4, b't', b'e', b's', b't'];
+
+    fn with(vendor: &[u8]) -> Vec<u8> {
+        let mut ies = SSID.to_vec();
+        ies.extend([221, vendor.len() as u8]);
+        ies.extend(vendor);
+        ies
+    }
+
+    #[test]
+    fn p2p_needs_wfa_p2p_element() {
+        assert!(p2p(&with(&[0x50, 0x6f, 0x9a, 0x09, 0x02, 0x02, 0x00])));
+        // WFA element of other type, e.g. Wi-Fi Display
+        assert!(!p2p(&with(&[0x50, 0x6f, 0x9a, 0x0a, 0x00])));
+        assert!(!p2p(&SSID));
+    }
+
+    #[test]
+    fn hotspot_needs_apple_metered_element() {
+        assert!(hotspot(&with(&[0x00, 0x17, 0xf2, 0x06, 0x01, 0x01, 0x03])));
+        assert!(!hotspot(&with(&[0x00, 0x17, 0xf2, 0x0a, 0x00])));
+        assert!(!hotspot(&with(&[0x50, 0x6f, 0x9a, 0x09])));
+        assert!(!hotspot(&SSID));
+    }
+
+    /// RSN element with one CCMP pairwise suite, one AKM suite and capabilities
+    fn rsn(akm: u8, capabilities: u8) -> Vec<u8> {
+        let mut ies = SSID.to_vec();
+        ies.extend([48, 20, 1, 0, 0x00, 0x0f, 0xac, 4, 1, 0, 0x00, 0x0f, 0xac, 4]);
+        ies.extend([1, 0, 0x00, 0x0f, 0xac, akm, capabilities, 0]);
+        ies
+    }
+
+    #[test]
+    fn security_is_read_from_rsn_or_wpa_element() {
+        assert_eq!(security(&rsn(2, 0)), Security::Wpa2);
+        assert_eq!(security(&rsn(8, 0)), Security::Wpa3);
+        // FT over SAE
+        assert_eq!(security(&rsn(9, 0)), Security::Wpa3);
+        assert_eq!(
+            security(&with(&[0x00, 0x50, 0xf2, 0x01, 1, 0])),
+            Security::Wpa
+        );
+        assert_eq!(security(&SSID), Security::Open);
+    }
+
+    #[test]
+    fn pmf_is_read_from_rsn_capabilities() {
+        assert_eq!(pmf(&rsn(8, 0xc0)), Pmf::Required);
+        assert_eq!(pmf(&rsn(2, 0x80)), Pmf::Capable);
+        assert_eq!(pmf(&rsn(2, 0)), Pmf::Disabled);
+        assert_eq!(pmf(&SSID), Pmf::Disabled);
+    }
+
+    #[test]
+    fn country_needs_two_letters() {
+        let mut ies = SSID.to_vec();
+        ies.extend([7, 3, b'd', b'e', b' ']);
+        assert_eq!(country(&ies).as_deref(), Some("DE"));
+        let mut ies = SSID.to_vec();
+        ies.extend([7, 3, b'X', 0, b' ']);
+        assert_eq!(country(&ies), None);
+        assert_eq!(country(&SSID), None);
+    }
+
+    #[test]
+    fn wps_needs_microsoft_wps_element() {
+        assert!(wps(&with(&[0x00, 0x50, 0xf2, 0x04, 0x10, 0x4a])));
+        // WPA element of the same OUI
+        assert!(!wps(&with(&[0x00, 0x50, 0xf2, 0x01, 1, 0])));
+        assert!(!wps(&SSID));
+    }
+
+    #[test]
+    fn truncated_element_is_ignored() {
+        let mut ies = SSID.to_vec();
+        ies.extend([221, 10, 0x00, 0x17, 0xf2, 0x06]);
+        assert!(!hotspot(&ies));
+        assert_eq!(ssid(&ies).as_deref(), Some("test"));
+    }
+}
@@ -0,0 +1,225 @@
+use std::io;
+
+use clap::ValueEnum;
+use neli_wifi::Bss;
+use serde_json::json;
+use tracing::{info, warn};
+
+use crate::{
+    config::{Config, InterfacesConfig},
+    connection::Connection,
+    display::{self, Icon},
+    ie, sampler,
+    wakeup::Wakeup,
+};
+
+/// Output of `--status-format` for desktop bars
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum StatusFormat {
+    /// JSON object for custom module with `return-type: json`
+    Waybar,
+    /// Full text, short text and color lines
+    I3blocks,
+    /// Text with polybar color tags
+    Polybar,
+    /// Text with tmux style codes for `status-right`
+    Tmux,
+}
+
+/// Result of health check, value is exit code of `--quiet`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Health {
+    /// Connected with fair or good signal
+    Healthy = 0,
+    /// Connected, but signal is poor or link quality is low
+    Degraded = 1,
+    NotAssociated = 2,
+    NoInterface = 3,
+    /// Config or `--replay` file can't be read
+    Error = 4,
+}
+
+/// Connection of one interface, selected the same way as at TUI start
+#[derive(Debug, Default)]
+pub struct Status {
+    pub iface: Option<String>,
+    pub ssid: Option<String>,
+    /// Signal in dBm, None if not connected
+    pub signal: Option<i32>,
+    pub frequency: Option<u32>,
+    /// Link quality 0–100, None if not connected
+    pub score: Option<u8>,
+}
+
+impl Status {
+    /// Preferred interface if it exists, otherwise the first connected one
+    pub fn read(connection: &mut Connection, filter: &InterfacesConfig) -> io::Result<Self> {
+        let devices = connection.devices(filter).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotConnected, "nl80211 is not available")
+        })?;
+        let connected = |bss_list: &[Bss]| bss_list.iter().any(|b| b.status.is_some());
+        let device = devices
+            .iter()
+            .find(|(interface, _)| {
+                filter.preferred.as_deref() == Some(sampler::iface_name(interface).as_str())
+            })
+            .or_else(|| devices.iter().find(|(_, bss_list)| connected(bss_list)))
+            .or(devices.first());
+        let Some((interface, bss_list)) = device else {
+            return Ok(Self::default());
+        };
+        let bss = bss_list.iter().find(|b| b.status.is_some());
+        let score = match (bss, interface.index) {
+            (Some(_), Some(index)) => {
+                sampler::sample(interface, bss_list).and_then(|mut sample| {
+                    connection.with(|s| sampler::link(s, index, &mut sample))?;
+                    sample.score
+                })
+            }
+            _ => None,
+        };
+        Ok(Self {
+            iface: Some(sampler::iface_name(interface)),
+            ssid: bss
+                .and_then(|b| b.information_elements.as_deref())
+                .and_then(ie::ssid),
+            signal: bss.and_then(|b| b.signal).map(|s| s / 100),
+            frequency: bss.and_then(|b| b.frequency),
+            score,
+        })
+    }
+
+    /// Link quality below this is degraded, red part of score scale
+    const DEGRADED_SCORE: u8 = 40;
+
+    pub fn health(&self) -> Health {
+        match (&self.iface, self.signal) {
+            (None, _) => Health::NoInterface,
+            (Some(_), None) => Health::NotAssociated,
+            _ if self.class() == "poor" || self.score.is_some_and(|s| s < Self::DEGRADED_SCORE) => {
+                Health::Degraded
+            }
+            _ => Health::Healthy,
+        }
+    }
+
+    /// good, fair, poor or disconnected
+    pub fn class(&self) -> &'static str {
+        self.signal.map_or("disconnected", display::quality)
+    }
+
+    /// Signal in configured unit
+    pub fn value(&self) -> Option<String> {
+        self.signal
+            .map(|dbm| format!("{} {}", display::signal_value(dbm), display::unit()))
+    }
+
+    /// Icon, SSID and signal
+    pub fn text(&self) -> String {
+        let icon = display::icon(Icon::Wifi);
+        match (&self.ssid, self.value()) {
+            (Some(ssid), Some(value)) => format!("{}{} {}", icon, ssid, value),
+            (None, Some(value)) => format!("{}<hidden> {}", icon, value),
+            _ => format!("{}disconnected", icon),
+        }
+    }
+
+    fn color(&self) -> &'static str {
+        match self.class() {
+            "good" => "#50fa7b",
+            "fair" => "#f1fa8c",
+            "poor" => "#ff5555",
+            _ => "#888888",
+        }
+    }
+
+    /// tmux colour name, terminal palette decides how it looks
+    fn tmux_color(&self) -> &'static str {
+        match self.class() {
+            "good" => "green",
+            "fair" => "yellow",
+            "poor" => "red",
+            _ => "colour244",
+        }
+    }
+
+    fn tooltip(&self) -> String {
+        format!(
+            "{}: {}{}{}",
+            self.iface.as_deref().unwrap_or("no interface"),
+            self.ssid.as_deref().unwrap_or("-"),
+            self.frequency
+                .map_or(String::new(), |f| format!(", {} MHz", f)),
+            self.score
+                .map_or(String::new(), |s| format!(", quality {}/100", s))
+        )
+    }
+
+    /// Status in format of bar, i3blocks gets several lines
+    pub fn format(&self, format: StatusFormat) -> String {
+        match format {
+            StatusFormat::Waybar => json!({
+                "text": self.text(),
+                "tooltip": self.tooltip(),
+                "class": self.class(),
+                "percentage": self.signal.map_or(0, display::percent),
+                "quality": self.score,
+            })
+            .to_string(),
+            StatusFormat::I3blocks => format!(
+                "{}\n{}\n{}",
+                self.text(),
+                self.value().unwrap_or_else(|| "-".to_string()),
+                self.color()
+            ),
+            StatusFormat::Polybar => format!("%{{F{}}}{}%{{F-}}", self.color(), self.text()),
+            // '#' starts tmux format, so it's doubled in SSID
+            StatusFormat::Tmux => format!(
+                "#[fg={}]{}#[default]",
+                self.tmux_color(),
+                self.text().replace('#', "##")
+            ),
+        }
+    }
+}
+
+/// Health of connection without printing anything, for `--quiet`
+pub fn check(config: &Config, connection: &mut Connection) -> Health {
+    match Status::read(connection, &config.interfaces) {
+        Ok(status) => status.health(),
+        // without nl80211 there is no wifi interface to speak of
+        Err(_) => Health::NoInterface,
+    }
+}
+
+/// Prints status once, or every interval if `follow` is set
+pub fn run(
+    format: StatusFormat,
+    follow: bool,
+    config: &Config,
+    connection: &mut Connection,
+) -> io::Result<()> {
+    info!(?format, follow, "printing status");
+    // single status needs no events
+    let wakeup = if follow {
+        Wakeup::spawn(config)
+    } else {
+        Wakeup::none()
+    };
+    loop {
+        // bar keeps showing something while socket reconnects
+        let status = match Status::read(connection, &config.interfaces) {
+            Ok(status) => status,
+            Err(e) if follow => {
+                warn!(error = %e, "failed to read status");
+                Status::default()
+            }
+            Err(e) => return Err(e),
+        };
+        println!("{}", status.format(format));
+        if !follow {
+            return Ok(());
+        }
+        wakeup.sleep(wakeup.interval(config.interval));
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    fs, io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use neli::{
+    consts::{
+        nl::{NlmF, NlmFFlags},
+        rtnl::{Ifa, IfaFFlags, RtAddrFamily, Rtm},
+        socket::NlFamily,
+    },
+    nl::{NlPayload, Nlmsghdr},
+    rtnl::Ifaddrmsg,
+    socket::NlSocketHandle,
+    types::RtBuffer,
+};
+
+/// Addresses are read again after this time
+const REFRESH: Duration = Duration::from_secs(2);
+
+/// Addressing of one interface
+#[derive(Clone, Debug, Default)]
+pub struct IpConfig {
+    /// Addresses with prefix length
+    pub addresses: Vec<(IpAddr, u8)>,
+    pub gateway: Option<Ipv4Addr>,
+    pub gateway6: Option<Ipv6Addr>,
+    /// Name servers of the system resolver, they are not bound to interface
+    pub dns: Vec<IpAddr>,
+}
+
+/// Reads addressing of interface with `index` and `name`
+pub fn read(index: i32, name: &str) -> io::Result<IpConfig> {
+    Ok(IpConfig {
+        addresses: addresses(index)?,
+        gateway: default_gateway(Some(name)),
+        gateway6: default_gateway6(name),
+        dns: nameservers(),
+    })
+}
+
+/// Keeps IP configuration of selected interface, so netlink is not asked every frame
+pub struct IpConfigMonitor {
+    index: Option<i32>,
+    read_at: Option<Instant>,
+    config: Option<IpConfig>,
+}
+
+impl IpConfigMonitor {
+    pub fn new() -> Self {
+        Self {
+            index: None,
+            read_at: None,
+            config: None,
+        }
+    }
+
+    pub fn update(&mut self, index: Option<i32>, name: &str) -> Option<&IpConfig> {
+        if self.index != index || self.read_at.is_none_or(|t| t.elapsed() >= REFRESH) {
+            self.index = index;
+            self.read_at = Some(Instant::now());
+            self.config = index.and_then(|i| read(i, name).ok());
+        }
+        self.config.as_ref()
+    }
+}
+
+fn addresses(index: i32) -> io::Result<Vec<(IpAddr, u8)>> {
+    let mut rtnl = NlSocketHandle::connect(NlFamily::Route, None, &[])?;
+    let msg = Ifaddrmsg {
+        ifa_family: RtAddrFamily::Unspecified,
+        ifa_prefixlen: 0,
+        ifa_flags: IfaFFlags::empty(),
+        ifa_scope: 0,
+        ifa_index: index,
+        rtattrs: RtBuffer::new(),
+    };
+    let header = Nlmsghdr::new(
+        None,
+        Rtm::Getaddr,
+        NlmFFlags::new(&[NlmF::Request, NlmF::Root]),
+        None,
+        None,
+        NlPayload::Payload(msg),
+    );
+    rtnl.send(header).map_err(io::Error::other)?;
+
+    let mut res = Vec::new();
+    for response in rtnl.iter::<Rtm, Ifaddrmsg>(false) {
+        let response = response.map_err(|e| io::Error::other(e.to_string()))?;
+        // kernel ignores index in dump request
+        let NlPayload::Payload(p) = response.nl_payload else {
+            continue;
+        };
+        if p.ifa_index != index {
+            continue;
+        }
+        for attr in p.rtattrs.iter() {
+            if attr.rta_type != Ifa::Address {
+                continue;
+            }
+            let payload: &[u8] = attr.rta_payload.as_ref();
+            let addr = match payload.len() {
+                4 => IpAddr::from(<[u8; 4]>::try_from(payload).unwrap()),
+                16 => IpAddr::from(<[u8; 16]>::try_from(payload).unwrap()),
+                _ => continue,
+            };
+            res.push((addr, p.ifa_prefixlen));
+        }
+    }
+    Ok(res)
+}
+
+/// Reads IPv4 default route from `/proc/net/route`, optionally only through `iface`
+pub fn default_gateway(iface: Option<&str>) -> Option<Ipv4Addr> {
+    const RTF_GATEWAY: u32 = 0x2;
+    let routes = fs::read_to_string("/proc/net/route").ok()?;
+    routes.lines().skip(1).find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if iface.is_some_and(|i| Some(&i) != fields.first()) {
+            return None;
+        }
+        let flags = u32::from_str_radix(fields.get(3)?, 16).ok()?;
+        if *fields.get(1)? != "00000000" || flags & RTF_GATEWAY == 0 {
+            return None;
+        }
+        // kernel prints address in network order as native integer
+        let gw = u32::from_str_radix(fields.get(2)?, 16).ok()?;
+        Some(Ipv4Addr::from(gw.to_ne_bytes()))
+    })
+}
+
+/// Reads IPv6 default route of `iface` from `/proc/net/ipv6_route`
+fn default_gateway6(iface: &str) -> Option<Ipv6Addr> {
+    let routes = fs::read_to_string("/proc/net/ipv6_route").ok()?;
+    routes.lines().find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 10 || fields[9] != iface || fields[1] != "00" {
+            return None;
+        }
+        let dest = u128::from_str_radix(fields[0], 16).ok()?;
+        let gw = u128::from_str_radix(fields[4], 16).ok()?;
+        (dest == 0 && gw != 0).then(|| Ipv6Addr::from(gw))
+    })
+}
+
+/// Reads name servers from resolv.conf, systemd-resolved upstream servers are preferred over its stub
+fn nameservers() -> Vec<IpAddr> {
+    let path = ["/run/systemd/resolve/resolv.conf", "/etc/resolv.conf"]
+        .into_iter()
+        .find(|p| Path::new(p).exists());
+    let Some(raw) = path.and_then(|p| fs::read_to_string(p).ok()) else {
+        return Vec::new();
+    };
+    raw.lines()
+        .filter_map(|l| l.strip_prefix("nameserver"))
+        .filter_map(|l| l.trim().parse().ok())
+        .collect()
+}
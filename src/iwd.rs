@@ -0,0 +1,107 @@
+use std::{collections::HashMap, io};
+
+use zbus::{
+    blocking::{Connection, Proxy},
+    zvariant::{OwnedObjectPath, OwnedValue},
+};
+
+use crate::control::ControlBackend;
+
+const DEST: &str = "net.connman.iwd";
+
+type Objects = HashMap<OwnedObjectPath, HashMap<String, HashMap<String, OwnedValue>>>;
+
+/// iwd control over system D-Bus
+pub struct Iwd {
+    conn: Connection,
+}
+
+impl Iwd {
+    /// Connects to system bus and checks that iwd is running
+    pub fn connect() -> io::Result<Self> {
+        let conn = Connection::system().map_err(io::Error::other)?;
+        let iwd = Self { conn };
+        iwd.objects()?;
+        Ok(iwd)
+    }
+
+    fn proxy<'a>(&self, path: &'a str, iface: &'a str) -> io::Result<Proxy<'a>> {
+        Proxy::new(&self.conn, DEST, path, iface).map_err(io::Error::other)
+    }
+
+    fn objects(&self) -> io::Result<Objects> {
+        self.proxy("/", "org.freedesktop.DBus.ObjectManager")?
+            .call("GetManagedObjects", &())
+            .map_err(io::Error::other)
+    }
+
+    /// Returns string property of interface implemented by object
+    fn property(
+        objects: &Objects,
+        path: &OwnedObjectPath,
+        iface: &str,
+        name: &str,
+    ) -> Option<String> {
+        let value = objects.get(path)?.get(iface)?.get(name)?.clone();
+        String::try_from(value).ok()
+    }
+
+    /// Returns object path of device (which is also station) with interface name
+    fn station(&self, objects: &Objects, iface: &str) -> io::Result<OwnedObjectPath> {
+        objects
+            .keys()
+            .find(|p| {
+                Self::property(objects, p, "net.connman.iwd.Device", "Name").as_deref()
+                    == Some(iface)
+            })
+            .cloned()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "iwd does not manage interface"))
+    }
+}
+
+impl ControlBackend for Iwd {
+    fn name(&self) -> &'static str {
+        "iwd"
+    }
+
+    fn known_networks(&self) -> io::Result<Vec<String>> {
+        let objects = self.objects()?;
+        Ok(objects
+            .keys()
+            .filter_map(|p| Self::property(&objects, p, "net.connman.iwd.KnownNetwork", "Name"))
+            .collect())
+    }
+
+    fn scan(&self, iface: &str) -> io::Result<()> {
+        let objects = self.objects()?;
+        let station = self.station(&objects, iface)?;
+        self.proxy(station.as_str(), "net.connman.iwd.Station")?
+            .call::<_, _, ()>("Scan", &())
+            .map_err(io::Error::other)
+    }
+
+    fn connect(&self, iface: &str, ssid: &str) -> io::Result<()> {
+        let objects = self.objects()?;
+        let station = self.station(&objects, iface)?;
+        // networks are children of station object
+        let network = objects
+            .keys()
+            .find(|p| {
+                p.as_str().starts_with(station.as_str())
+                    && Self::property(&objects, p, "net.connman.iwd.Network", "Name").as_deref()
+                        == Some(ssid)
+            })
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "iwd does not see network"))?;
+        self.proxy(network.as_str(), "net.connman.iwd.Network")?
+            .call::<_, _, ()>("Connect", &())
+            .map_err(io::Error::other)
+    }
+
+    fn disconnect(&self, iface: &str) -> io::Result<()> {
+        let objects = self.objects()?;
+        let station = self.station(&objects, iface)?;
+        self.proxy(station.as_str(), "net.connman.iwd.Station")?
+            .call::<_, _, ()>("Disconnect", &())
+            .map_err(io::Error::other)
+    }
+}
@@ -0,0 +1,88 @@
+use std::{
+    fs, io,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use neli::{
+    consts::{
+        nl::{NlmF, NlmFFlags, Nlmsg},
+        rtnl::{Arphrd, RtAddrFamily, Rtm},
+        socket::NlFamily,
+    },
+    err::NlError,
+    nl::{NlPayload, Nlmsghdr},
+    rtnl::Ifinfomsg,
+    socket::NlSocketHandle,
+    types::{Buffer, RtBuffer},
+};
+use tracing::{debug, warn};
+
+/// Returns true if interface has IFF_UP flag
+pub fn is_up(iface: &str) -> Option<bool> {
+    let flags = fs::read_to_string(format!("/sys/class/net/{}/flags", iface)).ok()?;
+    let flags = u32::from_str_radix(flags.trim().trim_start_matches("0x"), 16).ok()?;
+    Some(flags & libc::IFF_UP as u32 != 0)
+}
+
+/// Sets interface up or down, same as `ip link set dev <iface> up|down`
+pub fn set_up(index: i32, up: bool) -> io::Result<()> {
+    let mut rtnl = NlSocketHandle::connect(NlFamily::Route, None, &[])?;
+    let msg = if up {
+        Ifinfomsg::up(
+            RtAddrFamily::Unspecified,
+            Arphrd::None,
+            index,
+            RtBuffer::new(),
+        )
+    } else {
+        Ifinfomsg::down(
+            RtAddrFamily::Unspecified,
+            Arphrd::None,
+            index,
+            RtBuffer::new(),
+        )
+    };
+    let header = Nlmsghdr::new(
+        None,
+        Rtm::Newlink,
+        NlmFFlags::new(&[NlmF::Request, NlmF::Ack]),
+        None,
+        None,
+        NlPayload::Payload(msg),
+    );
+    rtnl.send(header).map_err(io::Error::other)?;
+    match rtnl.recv::<Nlmsg, Buffer>() {
+        Ok(_) => Ok(()),
+        // kernel returns negative errno
+        Err(NlError::Nlmsgerr(e)) => Err(io::Error::from_raw_os_error(-e.error)),
+        Err(e) => Err(io::Error::other(e.to_string())),
+    }
+}
+
+/// Starts thread which sets returned flag when kernel adds, removes or changes an interface
+pub fn watch() -> io::Result<Arc<AtomicBool>> {
+    let mut rtnl = NlSocketHandle::connect(NlFamily::Route, None, &[libc::RTNLGRP_LINK])?;
+    // interfaces are not known yet
+    let changed = Arc::new(AtomicBool::new(true));
+    let flag = changed.clone();
+    std::thread::spawn(move || {
+        loop {
+            match rtnl.recv::<Rtm, Buffer>() {
+                Ok(Some(msg)) if matches!(msg.nl_type, Rtm::Newlink | Rtm::Dellink) => {
+                    debug!(kind = ?msg.nl_type, "link changed");
+                    flag.store(true, Ordering::Relaxed);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    // interfaces are still read again from time to time
+                    warn!(error = %e, "failed to read link events");
+                    return;
+                }
+            }
+        }
+    });
+    Ok(changed)
+}
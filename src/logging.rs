@@ -0,0 +1,361 @@
+use chrono::Local;
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    os::unix::net::UnixDatagram,
+    path::{Path, PathBuf},
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant, SystemTime},
+};
+use tracing::Level;
+
+use crate::{
+    CONFIGURATION,
+    config::{LogConfig, LogOutput},
+    get_security_info,
+};
+
+const IDENTIFIER: &str = "wifi-check-tui";
+
+/// Mac addresses are hidden in log while it is set, see [`RedactingWriter`]
+static PRIVACY: AtomicBool = AtomicBool::new(false);
+
+/// Turns mac address hiding in log on or off while running
+pub fn set_privacy(privacy: bool) {
+    PRIVACY.store(privacy, Ordering::Relaxed);
+}
+
+/// Starts logging to the output selected in config, error tells why output can't be opened
+pub fn initialization_log_file(privacy: bool, config: &LogConfig) -> io::Result<()> {
+    let writer: Box<dyn Write + Send> = match config.output {
+        LogOutput::Off => return Ok(()),
+        LogOutput::Stderr => Box::new(io::stderr()),
+        LogOutput::File => match &config.file {
+            Some(path) => Box::new(
+                File::options()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .map_err(|e| {
+                        io::Error::new(
+                            e.kind(),
+                            format!("failed to open log file {}: {}", path.display(), e),
+                        )
+                    })?,
+            ),
+            None => {
+                let log_path_raw = format!("{}/logs/", CONFIGURATION.as_str());
+                let log_path: &Path = Path::new(&log_path_raw);
+                if !log_path.exists() {
+                    let _ = fs::create_dir_all(log_path);
+                }
+                remove_old_logs(
+                    log_path,
+                    Duration::from_secs(config.retention_days * 24 * 60 * 60),
+                );
+                Box::new(RotatingWriter::new(log_path.to_path_buf(), config)?)
+            }
+        },
+        output => Box::new(DatagramWriter::new(output)?),
+    };
+    set_privacy(privacy);
+    let writer = RedactingWriter::new(writer);
+
+    // You can use info/debug/error macros from tracing and you're logs will be writing to output
+    let builder = tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_max_level(Level::from(config.level))
+        .with_writer(Mutex::new(writer));
+    if matches!(config.output, LogOutput::File | LogOutput::Stderr) {
+        builder.init();
+    } else {
+        // journald and syslog add timestamp by themselves
+        builder.without_time().with_target(false).init();
+    }
+    Ok(())
+}
+
+/// Removes `run-*.log` files which were not modified for longer than `retention`
+fn remove_old_logs(dir: &Path, retention: Duration) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if !name.starts_with("run-") || !name.ends_with(".log") {
+            continue;
+        }
+        let age = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|m| SystemTime::now().duration_since(m).ok());
+        if age.is_some_and(|age| age > retention) {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// Writer which starts a new `run-<timestamp>.log` file when current one is too big or too old
+pub struct RotatingWriter {
+    dir: PathBuf,
+    file: File,
+    written: u64,
+    opened: Instant,
+    line_start: bool,
+    max_size: u64,
+    max_age: Duration,
+}
+
+impl RotatingWriter {
+    pub fn new(dir: PathBuf, config: &LogConfig) -> io::Result<Self> {
+        let file = Self::open(&dir)?;
+        Ok(Self {
+            dir,
+            file,
+            written: 0,
+            opened: Instant::now(),
+            line_start: true,
+            max_size: config.max_size,
+            max_age: Duration::from_secs(config.max_age_hours * 60 * 60),
+        })
+    }
+
+    fn open(dir: &Path) -> io::Result<File> {
+        File::create(dir.join(format!("run-{}.log", Local::now())))
+    }
+
+    fn rotate_if_needed(&mut self) -> io::Result<()> {
+        // never split one record between two files
+        if !self.line_start {
+            return Ok(());
+        }
+        if self.written >= self.max_size || self.opened.elapsed() >= self.max_age {
+            self.file = Self::open(&self.dir)?;
+            self.written = 0;
+            self.opened = Instant::now();
+        }
+        Ok(())
+    }
+}
+
+impl Write for RotatingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.rotate_if_needed()?;
+        let n = self.file.write(buf)?;
+        self.written += n as u64;
+        if n > 0 {
+            self.line_start = buf[n - 1] == b'\n';
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Writer which sends every line as one datagram to journald or syslog socket
+pub struct DatagramWriter {
+    socket: UnixDatagram,
+    output: LogOutput,
+    line: Vec<u8>,
+}
+
+impl DatagramWriter {
+    pub fn new(output: LogOutput) -> io::Result<Self> {
+        let socket = UnixDatagram::unbound()?;
+        match output {
+            LogOutput::Journald => socket.connect("/run/systemd/journal/socket")?,
+            _ => socket.connect("/dev/log")?,
+        }
+        Ok(Self {
+            socket,
+            output,
+            line: Vec::new(),
+        })
+    }
+
+    fn send_line(&mut self) -> io::Result<()> {
+        let line = String::from_utf8_lossy(&self.line);
+        let line = line.trim_end();
+        // line starts with level because time is disabled for this writer
+        let priority = match line.split_whitespace().next() {
+            Some("ERROR") => 3,
+            Some("WARN") => 4,
+            Some("INFO") => 6,
+            _ => 7,
+        };
+        let datagram = match self.output {
+            LogOutput::Journald => format!(
+                "PRIORITY={}\nSYSLOG_IDENTIFIER={}\nMESSAGE={}\n",
+                priority, IDENTIFIER, line
+            ),
+            // facility user
+            _ => format!("<{}>{}: {}", 8 + priority, IDENTIFIER, line),
+        };
+        self.line.clear();
+        self.socket.send(datagram.as_bytes()).map(|_| ())
+    }
+}
+
+impl Write for DatagramWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for &b in buf {
+            if b == b'\n' {
+                self.send_line()?;
+            } else {
+                self.line.push(b);
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.line.is_empty() {
+            self.send_line()?;
+        }
+        Ok(())
+    }
+}
+
+/// Writer which hides every mac address in line before passing it to the inner writer,
+/// lines are passed unchanged while privacy is off
+pub struct RedactingWriter<W: Write> {
+    inner: W,
+    line: Vec<u8>,
+}
+
+impl<W: Write> RedactingWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            line: Vec::new(),
+        }
+    }
+
+    fn write_line(&mut self) -> io::Result<()> {
+        if !PRIVACY.load(Ordering::Relaxed) {
+            let res = self.inner.write_all(&self.line);
+            self.line.clear();
+            return res;
+        }
+        let line = String::from_utf8_lossy(&self.line);
+        let redacted = redact(&line);
+        self.line.clear();
+        self.inner.write_all(redacted.as_bytes())
+    }
+}
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // logger writes one record with a few calls, so buffer until the end of line
+        for &b in buf {
+            self.line.push(b);
+            if b == b'\n' {
+                self.write_line()?;
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.line.is_empty() {
+            self.write_line()?;
+        }
+        self.inner.flush()
+    }
+}
+
+/// Replaces all mac addresses (`aa:bb:cc:dd:ee:ff`) in text with their hash
+pub fn redact(text: &str) -> String {
+    const MAC_LEN: usize = 17;
+    let bytes = text.as_bytes();
+    let mut res = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if i + MAC_LEN <= bytes.len() && is_mac(&bytes[i..i + MAC_LEN]) {
+            res.push_str(&get_security_info(&text[i..i + MAC_LEN], true));
+            i += MAC_LEN;
+            continue;
+        }
+        let ch = text[i..].chars().next().unwrap();
+        res.push(ch);
+        i += ch.len_utf8();
+    }
+    res
+}
+
+fn is_mac(b: &[u8]) -> bool {
+    b.iter().enumerate().all(|(i, c)| {
+        if i % 3 == 2 {
+            *c == b':'
+        } else {
+            c.is_ascii_hexdigit()
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_old_logs_keeps_recent_and_foreign_files() {
+        let dir = std::env::temp_dir().join(format!("wifi-logs-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let old = SystemTime::now() - Duration::from_secs(2 * 60 * 60);
+        for name in ["run-old.log", "run-new.log", "notes.log"] {
+            let file = File::create(dir.join(name)).unwrap();
+            if name != "run-new.log" {
+                file.set_modified(old).unwrap();
+            }
+        }
+        remove_old_logs(&dir, Duration::from_secs(60 * 60));
+        let exists = |name: &str| dir.join(name).exists();
+        let kept = (
+            exists("run-old.log"),
+            exists("run-new.log"),
+            exists("notes.log"),
+        );
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(kept, (false, true, true));
+    }
+
+    #[test]
+    fn redact_hashes_every_mac() {
+        let text = "bssid 02:00:00:00:02:00 left, AA:BB:CC:DD:EE:FF joined";
+        let redacted = redact(text);
+        assert!(!redacted.contains("02:00:00:00:02:00"));
+        assert!(!redacted.contains("AA:BB:CC:DD:EE:FF"));
+        assert!(redacted.contains(&get_security_info("02:00:00:00:02:00", true)));
+        assert!(redacted.starts_with("bssid "));
+        assert!(redacted.ends_with(" joined"));
+    }
+
+    #[test]
+    fn redact_keeps_text_without_mac() {
+        for text in [
+            "",
+            "signal -54 dBm",
+            "time 12:34:56",
+            "02:00:00:00:02",
+            "привет 0g:00:00:00:00:00",
+        ] {
+            assert_eq!(redact(text), text);
+        }
+    }
+
+    #[test]
+    fn redact_handles_mac_at_end_and_multibyte_text() {
+        let redacted = redact("ёж 02:00:00:00:02:00");
+        assert_eq!(
+            redacted,
+            format!("ёж {}", get_security_info("02:00:00:00:02:00", true))
+        );
+    }
+}
@@ -1,16 +1,17 @@
-use chrono::Local;
 use std::{
-    fs,
     hash::{DefaultHasher, Hash, Hasher},
     io::{self, Stdout},
     path::Path,
-    sync::{Arc, LazyLock, RwLock},
-    thread::sleep,
+    sync::{
+        Arc, LazyLock, RwLock,
+        mpsc::{self, Receiver, TryRecvError},
+    },
+    thread::{JoinHandle, sleep},
     time::Duration,
 };
 
 use crossterm::{
-    event::{self, DisableMouseCapture, KeyCode},
+    event::{self, DisableMouseCapture, Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind},
     execute,
     terminal::{LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
@@ -18,20 +19,142 @@ use crossterm::{
 #[cfg(target_os = "windows")]
 use directories::UserDirs;
 
-use log::{debug, info};
 use macaddr::MacAddr6;
-use neli_wifi::{Interface, Socket};
-use tui::{
+use neli_wifi::{Bss, Interface};
+use ratatui::{
     Terminal,
-    backend::CrosstermBackend,
+    backend::{Backend, CrosstermBackend},
     layout::{Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
-    text::{Span, Spans},
-    widgets::{Block, Borders, Paragraph},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
 };
+use tracing::{debug, info, warn};
 
+mod adapter;
+mod alert;
+mod ap;
+mod api;
 mod appstate;
-use appstate::{AppState, ProgramState};
+mod beacon;
+mod bench;
+#[cfg(feature = "bluetooth")]
+mod bluetooth;
+mod bufferbloat;
+mod chart;
+mod cli;
+mod clipboard;
+#[cfg(feature = "parquet")]
+mod columnar;
+mod compact;
+mod config;
+mod connection;
+mod connectivity;
+mod control;
+mod csa;
+mod daemon;
+#[cfg(feature = "dbus")]
+mod dbus;
+mod deauth;
+mod display;
+mod distance;
+mod dns;
+#[cfg(feature = "email")]
+mod email;
+mod error;
+mod events;
+mod exporter;
+mod frames;
+mod generate;
+mod headless;
+mod history;
+mod hooks;
+mod http;
+mod i18n;
+mod ie;
+mod indicator;
+mod ipconfig;
+#[cfg(feature = "iwd")]
+mod iwd;
+mod link;
+mod logging;
+mod monitoring;
+mod nagios;
+mod nl80211;
+#[cfg(feature = "networkmanager")]
+mod nm;
+mod notes;
+#[cfg(feature = "otlp")]
+mod otlp;
+mod panels;
+mod phy;
+mod ping;
+mod plain;
+mod profile;
+mod qr;
+mod quality;
+mod radio;
+mod regdomain;
+mod remote;
+mod replay;
+mod report;
+mod retries;
+mod rfkill;
+mod sampler;
+mod scan;
+mod settings;
+mod snapshot;
+mod speedtest;
+mod statsd;
+mod statusbar;
+mod steering;
+mod stream;
+mod suspend;
+mod systemd;
+mod throughput;
+mod timeline;
+mod twin;
+mod wakeup;
+mod watch;
+mod wizard;
+mod wpa;
+mod ws;
+use adapter::AdapterView;
+use ap::StationsView;
+use appstate::{Action, AppState, Event, Input, ProgramState};
+use bench::Counters;
+use bufferbloat::BufferbloatStatus;
+use clap::Parser;
+use cli::Cli;
+use compact::Compact;
+use config::{Config, InterfacesConfig, LogOutput};
+use connection::Connection;
+use connectivity::Connectivity;
+use control::ControlBackend;
+use display::Icon;
+use dns::Dns;
+use error::AppError;
+use events::Kind;
+use headless::{Headless, Keys};
+use history::Recorder;
+use indicator::{Health, StatusFormat};
+use monitoring::{Card, MonitoringView};
+use panels::Panel;
+use phy::PhyView;
+use qr::QrView;
+use regdomain::RegdomainView;
+use replay::Replay;
+use scan::{Category, ScanView};
+use settings::SettingsView;
+use speedtest::SpeedTestStatus;
+use statusbar::StatusBar;
+use suspend::SuspendDetector;
+use timeline::TimelineView;
+use watch::ConfigWatcher;
+
+#[cfg(feature = "bench")]
+#[global_allocator]
+static ALLOCATOR: bench::Counting = bench::Counting;
 
 #[cfg(target_os = "linux")]
 static CONFIGURATION: LazyLock<String> = LazyLock::new(|| {
@@ -42,16 +165,117 @@ static CONFIGURATION: LazyLock<String> = LazyLock::new(|| {
 static CONFIGURATION: LazyLock<String> =
     LazyLock::new(|| UserDirs::home_dir() + "\\wifi-check-tui");
 
+/// Config file with command line options, display and language are set from it
+fn setup(cli: &Cli, dir: &Path) -> io::Result<Config> {
+    let mut config = Config::load(dir)?;
+    cli.apply(&mut config);
+    display::set(&config.display);
+    i18n::set(config.display.language.as_deref());
+    Ok(config)
+}
+
+/// Netlink connection, or recorded frames with --replay
+fn connect(cli: &Cli) -> io::Result<Connection> {
+    let mut connection = match &cli.replay {
+        Some(path) => Connection::replay(Replay::load(path)?),
+        None => Connection::new(),
+    };
+    if let Some(path) = &cli.record {
+        connection.record(path.clone());
+    }
+    Ok(connection)
+}
+
 fn main() -> Result<(), io::Error> {
-    initialization_log_file();
+    let cli = Cli::parse();
+    if !cli.generate.is_empty() {
+        // packagers run it at build time, so config is not read
+        return generate::run(&cli.generate);
+    }
+    let dir = Path::new(CONFIGURATION.as_str());
+    if cli.quiet {
+        // health check of scripts, nothing is logged and startup errors have own code
+        let health = setup(&cli, dir)
+            .and_then(|config| Ok(indicator::check(&config, &mut connect(&cli)?)))
+            .unwrap_or(Health::Error);
+        std::process::exit(health as i32);
+    }
+    if cli.check {
+        // plugin runs often, so it logs nothing like --quiet, errors are UNKNOWN
+        let state = match setup(&cli, dir).and_then(|config| Ok((config, connect(&cli)?))) {
+            Ok((config, mut connection)) => {
+                nagios::run(cli.warn, cli.crit, &config, &mut connection)
+            }
+            Err(e) => nagios::unknown(&e),
+        };
+        std::process::exit(state as i32);
+    }
+    // checked before logging creates the directory
+    let first_run = !dir.exists();
+    let mut config = setup(&cli, dir)?;
+    if let Some(request) = &cli.query {
+        println!(
+            "{}",
+            api::query(&api::socket_path(dir), request, Duration::from_secs(30))?
+        );
+        return Ok(());
+    }
+    if cli.tmux {
+        // runs on every tmux status refresh, so log files are not even opened
+        return indicator::run(StatusFormat::Tmux, false, &config, &mut Connection::new());
+    }
+    if !cli.headless() && config.log.output == LogOutput::Stderr {
+        // stderr is used by TUI, so log would break the screen
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "logging to stderr is not available in TUI mode",
+        ));
+    }
+    // log asked for by flag or config must not be dropped silently
+    logging::initialization_log_file(config.privacy, &config.log)?;
 
     info!("createing socket");
-    let mut socket: Socket = Socket::connect().expect("device not found");
-    let state: Arc<RwLock<ProgramState>> = Arc::new(RwLock::new(ProgramState {
-        hide_info: true,
-        running: true,
-        state: AppState::Main,
-    }));
+    let mut connection = connect(&cli)?;
+    if cli.daemon {
+        return daemon::run(config, &cli, &mut connection);
+    }
+    if cli.stream {
+        return stream::run(&config, &mut connection);
+    }
+    if config.display.plain {
+        return plain::run(&config, &mut connection);
+    }
+    if let Some(format) = cli.status_format {
+        return indicator::run(format, cli.follow, &config, &mut connection);
+    }
+
+    let state: Arc<RwLock<ProgramState>> = Arc::new(RwLock::new(ProgramState::new()));
+    bench::write(&state).panels = config.panels.clone();
+
+    if cli.keys.is_some() || cli.bench.is_some() {
+        // benchmark measures monitoring view unless keys open another one
+        let mut keys = Keys::parse(cli.keys.as_deref().unwrap_or("m"))?;
+        if let Some(seconds) = cli.bench {
+            keys = keys.run_for(Duration::from_secs(seconds));
+        }
+        let mut terminal = Terminal::new(Headless::new(headless::SIZE))?;
+        let devices = connection.devices(&config.interfaces).unwrap_or_default();
+        select_preferred(&state, &devices, &config.interfaces);
+        let started = cli.bench.map(|_| Counters::now());
+        handle_app_state(
+            state,
+            &mut config,
+            &mut terminal,
+            &mut connection,
+            &cli.connect,
+            Some(keys),
+        )?;
+        match started {
+            Some(started) => println!("{}", Counters::since(started)),
+            None => print!("{}", terminal.backend().screen()),
+        }
+        return Ok(());
+    }
 
     info!("app started..");
     enable_raw_mode()?;
@@ -60,11 +284,42 @@ fn main() -> Result<(), io::Error> {
     let mut terminal = Terminal::new(backend)?;
     let _ = terminal.clear();
 
+    let devices = connection.devices(&config.interfaces).unwrap_or_default();
+    if first_run && !cli.no_wizard {
+        let names: Vec<String> = devices
+            .iter()
+            .map(|(interface, _)| sampler::iface_name(interface))
+            .collect();
+        match wizard::run(&mut terminal, dir, &names) {
+            Ok(mut answers) => {
+                cli.apply(&mut answers);
+                logging::set_privacy(answers.privacy);
+                config = answers;
+            }
+            Err(e) => tracing::error!(error = %e, "first run wizard failed"),
+        }
+        let _ = terminal.clear();
+    }
+    select_preferred(&state, &devices, &config.interfaces);
+
     let state_clone = state.clone();
-    open_input_thread(state_clone);
+    let (shutdown, shutdown_rx) = mpsc::channel();
+    let input = open_input_thread(state_clone, shutdown_rx);
 
-    handle_app_state(state, &mut terminal, &mut socket)?;
+    handle_app_state(
+        state,
+        &mut config,
+        &mut terminal,
+        &mut connection,
+        &cli.connect,
+        None,
+    )?;
 
+    // terminal is restored only after input thread stopped reading it
+    let _ = shutdown.send(());
+    if input.join().is_err() {
+        warn!("input thread panicked");
+    }
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
@@ -77,281 +332,1584 @@ fn main() -> Result<(), io::Error> {
     Ok(())
 }
 
-/// Main function for start app
-fn handle_app_state(
-    state: Arc<RwLock<ProgramState<'_>>>,
-    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
-    socket: &mut Socket,
+/// Selects interface named in config, the first one stays selected if it is not found
+fn select_preferred(
+    state: &RwLock<ProgramState>,
+    devices: &[(Interface, Vec<Bss>)],
+    filter: &InterfacesConfig,
+) {
+    if let Some(preferred) = &filter.preferred
+        && let Some(i) = devices
+            .iter()
+            .position(|(interface, _)| sampler::iface_name(interface) == *preferred)
+    {
+        bench::write(state).selected = i;
+    }
+}
+
+/// Main function for start app, `keys` replace terminal input in headless runs
+fn handle_app_state<B: Backend<Error = io::Error>>(
+    state: Arc<RwLock<ProgramState>>,
+    config: &mut Config,
+    terminal: &mut Terminal<B>,
+    connection: &mut Connection,
+    agents: &[String],
+    mut keys: Option<Keys>,
 ) -> Result<(), io::Error> {
-    // need add to app fern logger in the future
+    if !agents.is_empty() {
+        state
+            .write()
+            .unwrap()
+            .transition(Event::Open(AppState::Remote));
+    }
+    let dir = Path::new(CONFIGURATION.as_str());
+    // running daemon samples already, TUI shows them among agents as its client
+    let daemon = api::socket_path(dir);
+    // stuck daemon must not hold TUI start back
+    let daemon_running = keys.is_none()
+        && api::query(&daemon, r#"{"cmd":"status"}"#, Duration::from_millis(500)).is_ok();
+    let replay = connection.is_replay();
+    let mut monitoring_view = MonitoringView::new(config, daemon_running, replay);
+    let mut scan_view = ScanView::new();
+    let mut qr_view = QrView::new();
+    let mut stations_view = StationsView::new();
+    let frame_counters = frames::spawn(frames::FramesConfig {
+        enabled: config.frames.enabled && !replay,
+        ..config.frames.clone()
+    });
+    let mut addrs = agents.to_vec();
+    if daemon_running {
+        info!(
+            socket = %daemon.display(),
+            "daemon is running, history, alerts, hooks and deauth watcher are left to it"
+        );
+        addrs.push(daemon.display().to_string());
+    }
+    let agents = remote::spawn(&addrs, config.interval, config.http.token.clone());
+    let mut regdomain_view = RegdomainView::new();
+    let mut adapter_view = AdapterView::new();
+    let mut phy_view = PhyView::new();
+    let mut settings_view = SettingsView::new();
+    let mut timeline_view = TimelineView::new(
+        (config.tui_history() || daemon_running).then(|| dir.join("history.csv")),
+        notes::path(dir),
+    );
+    let mut recorder = if config.tui_history() && !daemon_running {
+        Recorder::new(&dir.join("history.csv"), config.retention.clone())
+            .inspect_err(|e| warn!(error = %e, "history is not recorded"))
+            .ok()
+    } else {
+        None
+    };
+    // created after first run wizard, so answers it saved are not a reload
+    let mut watcher = ConfigWatcher::new(dir)
+        .inspect_err(|e| warn!(error = %e, "config file is not watched"))
+        .ok();
+    let mut suspend = SuspendDetector::new();
+    let mut status_bar = StatusBar::new();
     loop {
-        let rrunning = state.read().unwrap().running;
+        let rrunning = bench::read(&state).running;
 
         if !rrunning {
             break;
         }
 
-        let rhide_info = state.read().unwrap().hide_info;
-        let rstate = state.read().unwrap().state;
-        info!("current state {}", rstate);
-        match rstate {
-            AppState::Main => {
-                terminal.draw(|f| {
-                    // Create a vertical layout with 2 sections
-                    let chunks = Layout::default()
-                        .direction(Direction::Vertical)
-                        .constraints(
-                            [Constraint::Percentage(80), Constraint::Percentage(20)].as_ref(),
-                        )
-                        .split(f.size());
+        if let Some(keys) = keys.as_mut() {
+            match keys.next() {
+                Some(Some(key)) => handle_key(&state, key),
+                Some(None) => {}
+                None => break,
+            }
+        }
 
-                    let information = Paragraph::new(
-                        "Приложение создано при помощи библиотек neli_wifi, tui и их зависимостей",
-                    )
-                    .block(Block::default().borders(Borders::ALL).title("Info"));
+        if let Some(slept) = suspend.check() {
+            info!(slept_secs = slept.as_secs(), "resumed from suspend");
+            connection.reset();
+            monitoring_view.chart.clear();
+            monitoring_view.retries.clear();
+        }
+
+        if let Some(w) = watcher.as_mut()
+            && w.changed()
+        {
+            match Config::load(dir) {
+                Ok(mut new_config) => {
+                    // logger is set up once at start
+                    new_config.log = config.log.clone();
+                    *config = new_config;
+                    display::set(&config.display);
+                    i18n::set(config.display.language.as_deref());
+                    logging::set_privacy(config.privacy);
+                    monitoring_view.reload(config);
+                    bench::write(&state).panels = config.panels.clone();
+                    if let Some(r) = recorder.as_mut() {
+                        r.set_retention(config.retention.clone());
+                    }
+                    info!(interval = config.interval, "config reloaded");
+                    events::push(Kind::Config, "config reloaded");
+                }
+                Err(e) => {
+                    warn!(error = %e, "config reload failed, keeping old config");
+                    events::push(Kind::Config, format!("config reload failed: {}", e));
+                }
+            }
+        }
+
+        if let Some(r) = recorder.as_mut() {
+            r.record(connection, &config.interfaces, config.interval);
+        }
+        // alerts and hooks run whichever view is open
+        monitoring_view.tick(config, connection);
+
+        let panels = bench::read(&state).panels.clone();
+        if panels != config.panels {
+            config.panels = panels;
+            match config.save_panels(dir) {
+                // own write is not a reload
+                Ok(()) => {
+                    if let Some(w) = watcher.as_mut() {
+                        w.sync();
+                    }
+                }
+                Err(e) => warn!(error = %e, "failed to save panels"),
+            }
+        }
+        status_bar.set_hint(config.panels.hint);
 
-                    let tip = Paragraph::new("Press 'esc' to quit\nPress 'm' to change state")
-                        .block(Block::default().borders(Borders::ALL).title("Tip"));
+        let rhide_info = bench::read(&state).hide_info;
+        let rstate = bench::read(&state).state.clone();
+        info!(state = %rstate, "current state");
+        status_bar.update(
+            connection,
+            &config.interfaces,
+            bench::read(&state).selected,
+            &rstate,
+        );
+        let bar = &status_bar;
+        let busy = if !config.confirm_quit {
+            None
+        } else if monitoring_view.speedtest.status() == SpeedTestStatus::Running {
+            Some("speed test")
+        } else if monitoring_view.bufferbloat.status() == BufferbloatStatus::Running {
+            Some("bufferbloat test")
+        } else if frame_counters.read().unwrap().running {
+            Some("frame capture")
+        } else {
+            None
+        };
+        bench::write(&state).busy = busy;
+        // error popup is drawn by status bar over the view it was raised in
+        let view = bench::read(&state).view();
+        match view {
+            AppState::Main | AppState::Error(_) => {
+                let information = Paragraph::new(i18n::tr(
+                    "Built with neli_wifi, ratatui and their dependencies",
+                ))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(i18n::tr("Info")),
+                );
+                terminal.draw(|f| {
+                    bar.frame(
+                        f,
+                        "Press 'q' to quit, Esc goes back\nPress 'm' to change state",
+                        |f, area| f.render_widget(information, area),
+                    )
+                })?;
+            }
+            AppState::QrCode => {
+                qr_state(
+                    state.clone(),
+                    terminal,
+                    connection,
+                    &mut qr_view,
+                    scan_view.backend(),
+                    &config.interfaces,
+                    bar,
+                )?;
+            }
+            AppState::Input { purpose } => {
+                let input = bench::read(&state).input.clone();
+                let text = if purpose.secret() {
+                    "*".repeat(input.chars().count())
+                } else {
+                    input
+                };
+                terminal.draw(|f| {
+                    bar.frame(f, "Press Enter to accept, Esc to cancel", |f, area| {
+                        let chunks = Layout::default()
+                            .direction(Direction::Vertical)
+                            .constraints([Constraint::Length(3), Constraint::Min(0)])
+                            .split(area);
 
-                    // Render the widgets in their respective chunks
-                    f.render_widget(information, chunks[0]);
-                    f.render_widget(tip, chunks[1]);
+                        let input_paragraph = Paragraph::new(text).block(
+                            Block::default()
+                                .title(i18n::tr(purpose.prompt()))
+                                .borders(Borders::ALL),
+                        );
+                        f.render_widget(input_paragraph, chunks[0]);
+                    })
                 })?;
             }
-            AppState::Error { h, d } => {
+            AppState::Scan => {
+                scan_state(
+                    state.clone(),
+                    terminal,
+                    connection,
+                    &mut scan_view,
+                    config,
+                    rhide_info,
+                    bar,
+                )?;
+            }
+            AppState::Stations => {
+                stations_state(
+                    state.clone(),
+                    terminal,
+                    connection,
+                    &mut stations_view,
+                    &config.interfaces,
+                    rhide_info,
+                    bar,
+                )?;
+            }
+            AppState::Frames => {
+                let counters = frame_counters.read().unwrap();
                 terminal.draw(|f| {
-                    let chunks = Layout::default()
-                        .direction(Direction::Vertical)
-                        .constraints([
-                            Constraint::Percentage(10),
-                            Constraint::Percentage(80),
-                            Constraint::Percentage(10),
-                        ])
-                        .split(f.size());
-                    let header_chunk = chunks[0];
-                    let description_chunk = chunks[1];
-                    let keybind_chunk = chunks[2];
-
-                    let header_paragraph =
-                        Paragraph::new(h).block(Block::default().borders(Borders::ALL));
-                    let keybind_paragraph = Paragraph::new("For update menu press 'u'")
-                        .block(Block::default().title("hint").borders(Borders::ALL));
-                    let description_paragraph =
-                        Paragraph::new(Span::styled(d, Style::default().fg(Color::Red)))
-                            .block(Block::default().borders(Borders::ALL));
-
-                    f.render_widget(header_paragraph, header_chunk);
-                    f.render_widget(description_paragraph, description_chunk);
-                    f.render_widget(keybind_paragraph, keybind_chunk);
+                    bar.frame(f, frames::HINT, |f, area| {
+                        frames::draw(f, area, &counters, rhide_info)
+                    })
+                })?;
+            }
+            AppState::Regdomain => {
+                regdomain_state(
+                    state.clone(),
+                    terminal,
+                    connection,
+                    &mut regdomain_view,
+                    &config.interfaces,
+                    bar,
+                )?;
+            }
+            AppState::Adapter => {
+                adapter_state(
+                    state.clone(),
+                    terminal,
+                    connection,
+                    &mut adapter_view,
+                    &config.interfaces,
+                    bar,
+                )?;
+            }
+            AppState::Phy => {
+                phy_state(
+                    state.clone(),
+                    terminal,
+                    connection,
+                    &mut phy_view,
+                    &config.interfaces,
+                    bar,
+                )?;
+            }
+            AppState::Remote => {
+                let agents = agents.read().unwrap();
+                terminal.draw(|f| {
+                    bar.frame(f, remote::HINT, |f, area| {
+                        remote::draw(f, area, &agents, rhide_info)
+                    })
+                })?;
+            }
+            AppState::History => {
+                let (zoom, pan) = {
+                    let state = bench::read(&state);
+                    (state.history_zoom, state.history_pan)
+                };
+                if bench::write(&state).take_action() == Some(Action::AddNote) {
+                    let text = bench::write(&state).take_input();
+                    if let Err(e) = timeline_view.add_note(&text) {
+                        warn!(error = %e, "failed to save note");
+                        bench::write(&state).fail(AppError::Note(e));
+                    }
+                }
+                timeline_view.update();
+                terminal.draw(|f| {
+                    bar.frame(f, timeline::HINT, |f, area| {
+                        timeline_view.draw(f, area, zoom, pan)
+                    })
+                })?;
+            }
+            AppState::Log => {
+                let selected = {
+                    let mut wstate = bench::write(&state);
+                    wstate.log_selected =
+                        wstate.log_selected.min(events::count().saturating_sub(1));
+                    wstate.log_selected
+                };
+                terminal.draw(|f| {
+                    bar.frame(f, events::HINT, |f, area| {
+                        events::draw(f, area, selected, rhide_info)
+                    })
+                })?;
+            }
+            AppState::Settings => {
+                let selected = bench::read(&state).settings_selected;
+                let up = match bench::write(&state).take_action() {
+                    Some(Action::IncreaseSetting) => Some(true),
+                    Some(Action::DecreaseSetting) => Some(false),
+                    _ => None,
+                };
+                if let Some(up) = up {
+                    settings_view.change(config, dir, selected, up);
+                    // own write is not a reload
+                    if let Some(w) = watcher.as_mut() {
+                        w.sync();
+                    }
+                }
+                terminal.draw(|f| {
+                    bar.frame(f, settings::HINT, |f, area| {
+                        settings_view.draw(f, area, config, selected)
+                    })
+                })?;
+            }
+            AppState::Confirm { action } => {
+                let mut question = vec![Line::from(Span::styled(
+                    i18n::tr(action.question()),
+                    Style::default().fg(Color::Yellow),
+                ))];
+                if let (Action::Quit, Some(busy)) = (action, busy) {
+                    question.push(Line::from(format!("running: {}", busy)));
+                }
+                terminal.draw(|f| {
+                    bar.frame(f, "Press 'y' to confirm, 'n' to cancel", |f, area| {
+                        let question = Paragraph::new(question).block(
+                            Block::default()
+                                .title(i18n::tr("confirm"))
+                                .borders(Borders::ALL),
+                        );
+                        f.render_widget(question, area);
+                    })
                 })?;
             }
             AppState::Monitoring => {
-                monitoring_state(state.clone(), terminal, socket, rhide_info)?;
+                monitoring_state(
+                    state.clone(),
+                    terminal,
+                    connection,
+                    &mut monitoring_view,
+                    config,
+                    rhide_info,
+                    bar,
+                )?;
             }
         }
-        sleep(Duration::from_millis(1000 / 30));
+        connection.advance();
+        bench::frame();
+        if keys.as_ref().is_none_or(Keys::paced) {
+            sleep(Duration::from_millis(1000 / 30));
+        }
+    }
+    if config.report.on_exit {
+        let hide_info = bench::read(&state).hide_info;
+        match monitoring_view.report(dir, config, connection, hide_info) {
+            Ok(path) => info!(path = %path.display(), "session report written"),
+            Err(e) => warn!(error = %e, "failed to write session report"),
+        }
     }
     Ok(())
 }
 
-fn monitoring_state(
-    state: Arc<RwLock<ProgramState<'_>>>,
-    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
-    socket: &mut Socket,
+fn monitoring_state<B: Backend<Error = io::Error>>(
+    state: Arc<RwLock<ProgramState>>,
+    terminal: &mut Terminal<B>,
+    connection: &mut Connection,
+    view: &mut MonitoringView,
+    config: &Config,
     rhide_info: bool,
+    bar: &StatusBar,
 ) -> Result<(), io::Error> {
     info!("Hello from monitoring");
-    let wifi_interface = socket.get_interfaces_info().unwrap();
-    if wifi_interface.len() == 1 {
-        state.write().unwrap().change_state(AppState::Error {
-            h: "wifi interface error",
-            d: "wifi interface is not existed",
-        });
+    let Some(devices) = connection.devices(&config.interfaces) else {
+        return draw_reconnecting(terminal, connection, "monitoring", bar);
+    };
+    if devices.is_empty() {
+        bench::write(&state).fail(AppError::NoInterface);
+        // error popup needs a frame under it
+        terminal.draw(|f| {
+            bar.frame(f, "Press 'q' to quit", |f, area| {
+                f.render_widget(
+                    Block::default()
+                        .title(i18n::tr("monitoring"))
+                        .borders(Borders::ALL),
+                    area,
+                )
+            })
+        })?;
         return Ok(());
     }
+    let selected_index = bench::read(&state).selected % devices.len();
+    let (selected_interface, selected_bss) = &devices[selected_index];
+    let selected_name = sampler::iface_name(selected_interface);
+    // chart follows selected interface only
+    if view.chart_iface != selected_name {
+        view.chart.clear();
+        view.retries.clear();
+        view.chart_iface = selected_name.clone();
+    }
+    if let Some(signal) = selected_bss
+        .iter()
+        .find(|bss| bss.status.is_some())
+        .and_then(|bss| bss.signal)
+    {
+        view.chart
+            .push(signal / 100, Duration::from_secs(config.interval));
+        if view.fresh()
+            && let Some(stations) = selected_interface
+                .index
+                .and_then(|index| connection.with(|s| s.get_station_info(index)))
+            && let Some(station) = stations.first()
+        {
+            // station info is read once per interval already
+            view.retries.push(station, Duration::ZERO);
+        }
+    }
     debug!("initialization wifi_interface");
-    let widget = match create_device(&wifi_interface, socket, rhide_info) {
-        Ok(t) => t,
-        Err(e) => {
-            state.write().unwrap().change_state(e);
+    for (i, (interface, bss_list)) in devices.iter().enumerate() {
+        let Some(name) = interface.name.as_deref() else {
+            continue;
+        };
+        let score = view.score(sampler::trimmed(name));
+        let card = Card::new(interface, bss_list, score, rhide_info, i == selected_index);
+        if view
+            .cards
+            .get(name)
+            .is_none_or(|(cached, _)| *cached != card)
+        {
+            let widget = create_device(interface, bss_list, score, rhide_info, i == selected_index);
+            view.cards.insert(name.to_vec(), (card, widget));
+        }
+    }
+    view.cards
+        .retain(|name, _| devices.iter().any(|(i, _)| i.name.as_ref() == Some(name)));
+    let widgets: Vec<&Paragraph> = devices
+        .iter()
+        .filter_map(|(interface, _)| view.cards.get(interface.name.as_deref()?))
+        .map(|(_, widget)| widget)
+        .collect();
+    let rfkill = selected_interface.phy.and_then(rfkill::for_phy);
+    let link_up = view.link_up(&selected_name);
+    let action = bench::write(&state).take_action();
+    if action == Some(Action::ToggleRfkill)
+        && let Some(r) = rfkill
+    {
+        info!(
+            index = r.index,
+            block = !r.soft,
+            "changing rfkill soft block"
+        );
+        if let Err(e) = rfkill::set_soft_block(r.index, !r.soft) {
+            bench::write(&state).fail(AppError::rfkill(r.index, e));
+            return Ok(());
+        }
+    }
+    if action == Some(Action::ToggleLink)
+        && let (Some(index), Some(up)) = (selected_interface.index, link_up)
+    {
+        info!(iface = selected_name, up = !up, "changing link state");
+        if let Err(e) = link::set_up(index, !up) {
+            bench::write(&state).fail(AppError::netlink(
+                format!("link state of {}", selected_name),
+                e,
+            ));
             return Ok(());
         }
+    }
+    let power_save = view.power_save.update(selected_interface.index);
+    if action == Some(Action::TogglePowerSave)
+        && let (Some(index), Some(enabled)) = (selected_interface.index, power_save)
+    {
+        info!(
+            iface = selected_name,
+            enabled = !enabled,
+            "changing power save"
+        );
+        if let Err(e) = radio::set_power_save(index, !enabled) {
+            tracing::error!(error = %e, "failed to set power save");
+            bench::write(&state).fail(AppError::netlink(
+                format!("power save of {}", selected_name),
+                e,
+            ));
+            return Ok(());
+        }
+        view.power_save.invalidate();
+    }
+    if action == Some(Action::SetTxPower)
+        && let Some(index) = selected_interface.index
+    {
+        let typed = bench::write(&state).take_input();
+        let Some(dbm) = radio::parse_tx_power(&typed) else {
+            bench::write(&state).fail(AppError::InvalidTxPower(typed));
+            return Ok(());
+        };
+        info!(iface = selected_name, ?dbm, "changing tx power");
+        if let Err(e) = radio::set_tx_power(index, dbm) {
+            tracing::error!(error = %e, "failed to set tx power");
+            bench::write(&state).fail(AppError::netlink(
+                format!("TX power of {}", selected_name),
+                e,
+            ));
+            return Ok(());
+        }
+    }
+    if action == Some(Action::Report)
+        && let Err(e) = view.report(
+            Path::new(CONFIGURATION.as_str()),
+            config,
+            connection,
+            rhide_info,
+        )
+    {
+        warn!(error = %e, "failed to write report");
+        bench::write(&state).fail(AppError::Report(e));
+        return Ok(());
+    }
+    if action == Some(Action::Copy) {
+        let ip = view.ip.update(selected_interface.index, &selected_name);
+        let text = match ip.and_then(|ip| ip.addresses.first()) {
+            Some((addr, _)) => Some(addr.to_string()),
+            None => selected_bss
+                .iter()
+                .find(|bss| bss.status.is_some())
+                .and_then(sampler::format_bssid),
+        };
+        if let Some(text) = text
+            && !copy(&state, &text)
+        {
+            return Ok(());
+        }
+    }
+    if action == Some(Action::Bufferbloat) {
+        view.bufferbloat.start(
+            selected_device(&devices, &state)
+                .and_then(|(interface, bss_list)| sampler::sample(interface, bss_list)),
+        );
+    }
+    if action == Some(Action::SpeedTest) {
+        view.speedtest.start(
+            selected_device(&devices, &state)
+                .and_then(|(interface, bss_list)| sampler::sample(interface, bss_list)),
+        );
+    }
+    let size = terminal.size()?;
+    if bench::read(&state).compact || statusbar::too_small(size.into()) {
+        let connected = selected_bss.iter().find(|bss| bss.status.is_some());
+        let summary = Compact {
+            iface: &selected_name,
+            ssid: connected
+                .and_then(|bss| bss.information_elements.as_deref())
+                .and_then(ie::ssid),
+            signal: connected.and_then(|bss| bss.signal).map(|s| s / 100),
+            trend: view.chart.trend(),
+            error: bar.error(),
+        };
+        terminal.draw(|f| compact::draw(f, &summary))?;
+        return Ok(());
+    }
+    view.throughput.update(&selected_name);
+    let deviations = view.deviations(&selected_name).map(<[String]>::to_vec);
+    #[cfg(feature = "bluetooth")]
+    let score = view.score(&selected_name);
+    let supplicant = view.supplicant.update(&selected_name);
+    let mut status = vec![
+        Line::from(vec![
+            Span::raw(i18n::tr("Selected ")),
+            Span::styled(
+                selected_name.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(i18n::fill(
+            i18n::tr("rfkill: {}"),
+            &[&rfkill.map_or(i18n::tr("not available").to_string(), |r| r.to_string())],
+        )),
+        Line::from(i18n::tr(match link_up {
+            Some(true) => "link: up",
+            Some(false) => "link: down",
+            None => "link: unknown",
+        })),
+        Line::from(i18n::fill(
+            i18n::tr("tx power: {}"),
+            &[&selected_interface
+                .power
+                .map_or(i18n::tr("unknown").to_string(), |p| {
+                    format!("{:.2} dBm", f64::from(p) / 100.0)
+                })],
+        )),
+        Line::from(vec![
+            Span::raw(i18n::tr("power save: ")),
+            match power_save {
+                // power save is known to cause latency spikes
+                Some(true) => Span::styled(i18n::tr("on"), Style::default().fg(Color::Yellow)),
+                Some(false) => Span::raw(i18n::tr("off")),
+                None => Span::raw(i18n::tr("unknown")),
+            },
+        ]),
+        Line::from(i18n::fill(
+            i18n::tr("supplicant: {}"),
+            &[supplicant
+                .and_then(|s| s.state())
+                .unwrap_or(i18n::tr("not available"))],
+        )),
+    ];
+    let internet = *view.connectivity.read().unwrap();
+    let ping = view.ping.read().unwrap();
+    let dns = view.dns.read().unwrap().clone();
+    let connectivity = vec![
+        Line::from(vec![
+            Span::raw(i18n::tr("internet: ")),
+            Span::styled(
+                internet.to_string(),
+                Style::default().fg(match internet {
+                    Connectivity::Online => Color::Green,
+                    Connectivity::CaptivePortal => Color::Yellow,
+                    Connectivity::NoDns | Connectivity::NoInternet => Color::Red,
+                    Connectivity::Off | Connectivity::Unknown => Color::Gray,
+                }),
+            ),
+        ]),
+        Line::from(match (ping.gateway, &ping.error) {
+            (None, _) => i18n::tr("gateway: no default route").to_string(),
+            (Some(gw), Some(e)) => i18n::fill(
+                i18n::tr("gateway: {} ({})"),
+                &[&gw.to_string(), &e.to_string()],
+            ),
+            (Some(gw), None) => i18n::fill(
+                i18n::tr("gateway: {} {} ±{} loss {}"),
+                &[
+                    &gw.to_string(),
+                    &ping.latency().map_or("-".to_string(), |l| {
+                        format!("{:.1} ms", l.as_secs_f64() * 1000.0)
+                    }),
+                    &ping.jitter().map_or("-".to_string(), |j| {
+                        format!("{:.1} ms", j.as_secs_f64() * 1000.0)
+                    }),
+                    &ping
+                        .loss()
+                        .map_or("-".to_string(), |l| format!("{:.0}%", l)),
+                ],
+            ),
+        }),
+        Line::from(vec![
+            Span::raw(i18n::tr("dns: ")),
+            Span::styled(
+                dns.to_string(),
+                Style::default().fg(match dns {
+                    Dns::Resolved(_) => Color::Green,
+                    Dns::Failed(_) => Color::Red,
+                    _ => Color::Gray,
+                }),
+            ),
+        ]),
+        Line::from(i18n::fill(
+            i18n::tr("speed: {}"),
+            &[&view.speedtest.status().to_string()],
+        )),
+        Line::from(i18n::fill(
+            i18n::tr("bufferbloat: {}"),
+            &[&view.bufferbloat.status().to_string()],
+        )),
+    ];
+    let twins = twin::suspicious(selected_bss).len().saturating_sub(1);
+    if twins > 0 {
+        status.push(Line::from(Span::styled(
+            i18n::fill(
+                i18n::tr("{}possible evil twin: {} BSSIDs, see scan"),
+                &[display::icon(Icon::Warning), &twins.to_string()],
+            ),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    if config.distance.enabled
+        && let Some(bss) = selected_bss.iter().find(|bss| bss.status.is_some())
+        && let Some(signal) = bss.signal
+    {
+        let meters = distance::estimate(&config.distance, signal / 100, bss.frequency);
+        status.push(Line::from(i18n::fill(
+            i18n::tr("distance: {}"),
+            &[&distance::describe(meters)],
+        )));
+    }
+    let power = view.power.update(&selected_name, selected_interface.phy);
+    if !power.is_empty() {
+        let hot = power.temperature.is_some_and(|t| t >= adapter::HOT);
+        status.push(Line::from(Span::styled(
+            i18n::fill(
+                i18n::tr("adapter: {}{}"),
+                &[
+                    &power.to_string(),
+                    if hot {
+                        i18n::tr(", may be throttled")
+                    } else {
+                        ""
+                    },
+                ],
+            ),
+            if hot {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            },
+        )));
+    }
+    if let Some(switch) = view.channel_switch.read().unwrap().as_ref() {
+        status.push(Line::from(i18n::fill(
+            i18n::tr("last channel switch: {}"),
+            &[&switch.to_string()],
+        )));
+    }
+    if let Some(ap) = connected_country(selected_bss) {
+        match view
+            .country
+            .update()
+            .and_then(|local| regdomain::conflict(local, &ap))
+        {
+            Some(warning) => status.push(Line::from(Span::styled(
+                i18n::fill(
+                    i18n::tr("{}country: {}"),
+                    &[display::icon(Icon::Warning), &warning.to_string()],
+                ),
+                Style::default().fg(Color::Yellow),
+            ))),
+            None => status.push(Line::from(i18n::fill(
+                i18n::tr("country: {}"),
+                &[&ap.to_string()],
+            ))),
+        }
+    }
+    #[cfg(feature = "bluetooth")]
+    if let Some(bss) = selected_bss.iter().find(|bss| bss.status.is_some())
+        && bss
+            .frequency
+            .is_some_and(|f| profile::Band::of(f) == profile::Band::Band2)
+        && score.is_some_and(|s| quality::color(s) == Color::Red)
+    {
+        let devices = view.bluetooth.read().unwrap();
+        if !devices.is_empty() {
+            status.push(Line::from(Span::styled(
+                i18n::fill(
+                    i18n::tr("possible Bluetooth coexistence interference: {} shares 2.4 GHz"),
+                    &[&devices.join(", ")],
+                ),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+    }
+    if let Some(hint) = steering::hint(&scan::rows(selected_bss)) {
+        status.push(Line::from(i18n::fill(
+            i18n::tr("band: {}"),
+            &[&hint.to_string()],
+        )));
+    }
+    match deviations.as_deref() {
+        Some([]) => status.push(Line::from(Span::styled(
+            i18n::tr("profile: as usual"),
+            Style::default().fg(Color::Green),
+        ))),
+        Some(deviations) => status.push(Line::from(Span::styled(
+            i18n::fill(i18n::tr("worse than usual: {}"), &[&deviations.join(", ")]),
+            Style::default().fg(Color::Yellow),
+        ))),
+        None => {}
+    }
+    if let Some(failure) = supplicant.and_then(|s| s.last_failure()) {
+        status.push(Line::from(Span::styled(
+            i18n::fill(i18n::tr("last failure: {}"), &[failure]),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    let ip = view.ip.update(selected_interface.index, &selected_name);
+    let ip_lines = match ip {
+        Some(ip) => vec![
+            Line::from(i18n::fill(
+                i18n::tr("addresses: {}"),
+                &[&ip
+                    .addresses
+                    .iter()
+                    .map(|(addr, prefix)| format!("{}/{}", addr, prefix))
+                    .collect::<Vec<_>>()
+                    .join(", ")],
+            )),
+            Line::from(i18n::fill(
+                i18n::tr("gateway: {} / {}"),
+                &[
+                    &ip.gateway.map_or("-".to_string(), |g| g.to_string()),
+                    &ip.gateway6.map_or("-".to_string(), |g| g.to_string()),
+                ],
+            )),
+            Line::from(i18n::fill(
+                i18n::tr("dns: {}"),
+                &[&ip
+                    .dns
+                    .iter()
+                    .map(|d| d.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")],
+            )),
+        ],
+        None => vec![Line::from(i18n::tr("not available"))],
     };
-    let hide_text = if rhide_info {
-        "For show mac address press 'h'"
-    } else {
-        "For hide mac address press 'h'"
+    let presets = panels::presets(&config.layout);
+    let preset = &presets[bench::read(&state).layout % presets.len()];
+    if let Some(action @ (Action::GrowCharts | Action::ShrinkCharts)) = action {
+        // saved by render loop on the next frame
+        state
+            .write()
+            .unwrap()
+            .panels
+            .resize(preset, action == Action::GrowCharts);
+    }
+    let adjusted = preset.adjusted(&bench::read(&state).panels);
+    let horizontal = bench::read(&state).panels.horizontal;
+    let hide_text = i18n::fill(
+        i18n::tr(monitoring::HINT),
+        &[
+            i18n::tr(if rhide_info { "show" } else { "hide" }),
+            i18n::tr(if link_up == Some(true) { "down" } else { "up" }),
+            &presets.len().min(4).to_string(),
+            &preset.name,
+        ],
+    );
+    let deauth = view.deauth.read().unwrap();
+    let banner = deauth.attack().then(|| {
+        (
+            i18n::fill(
+                i18n::tr(
+                    "Possible deauthentication attack: {} disconnects in a short time, last reason {}",
+                ),
+                &[
+                    &deauth.count().to_string(),
+                    &deauth
+                        .reason
+                        .map_or(i18n::tr("unknown").to_string(), |r| r.to_string()),
+                ],
+            ),
+            true,
+        )
+    });
+    drop(deauth);
+    // attack is more urgent than alert
+    let banner = banner.or_else(|| view.alert_banner().map(|(text, on)| (text.to_string(), on)));
+    terminal.draw(|f| {
+        bar.frame(f, &hide_text, |f, area| {
+            let area = match banner {
+                Some((banner, on)) => {
+                    let parts = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([Constraint::Length(3), Constraint::Min(0)])
+                        .split(area);
+                    f.render_widget(
+                        Paragraph::new(banner)
+                            .style(
+                                Style::default()
+                                    .fg(Color::White)
+                                    .bg(if on { Color::Red } else { Color::DarkGray })
+                                    .add_modifier(Modifier::BOLD),
+                            )
+                            .block(
+                                Block::default()
+                                    .title(format!(
+                                        "{}{}",
+                                        display::icon(Icon::Warning),
+                                        i18n::tr("warning")
+                                    ))
+                                    .borders(Borders::ALL),
+                            ),
+                        parts[0],
+                    );
+                    parts[1]
+                }
+                None => area,
+            };
+            // narrow terminal gets one column, panels are stacked
+            let narrow = statusbar::narrow(area);
+            let (across, along) = if narrow {
+                (Direction::Vertical, Direction::Horizontal)
+            } else {
+                (Direction::Horizontal, Direction::Vertical)
+            };
+            let mut widgets = Some(widgets);
+            let mut status = Some(status);
+            let mut connectivity = Some(connectivity);
+            let mut ip_lines = Some(ip_lines);
+            for (panel, area) in
+                panels::split(&adjusted, area, across, along, horizontal && !narrow)
+            {
+                match panel {
+                    Panel::Devices => {
+                        let widgets = widgets.take().unwrap_or_default();
+                        let blocks = Layout::default()
+                            .direction(Direction::Horizontal)
+                            .constraints(vec![
+                                Constraint::Ratio(1, widgets.len().max(1) as u32);
+                                widgets.len()
+                            ])
+                            .split(area);
+                        for (widget, area) in widgets.into_iter().zip(blocks.iter().copied()) {
+                            f.render_widget(widget, area);
+                        }
+                    }
+                    Panel::Status => f.render_widget(
+                        Paragraph::new(status.take().unwrap_or_default()).block(
+                            Block::default()
+                                .title(i18n::tr("status"))
+                                .borders(Borders::ALL),
+                        ),
+                        area,
+                    ),
+                    Panel::Connectivity => f.render_widget(
+                        Paragraph::new(connectivity.take().unwrap_or_default())
+                            .wrap(Wrap { trim: true })
+                            .block(
+                                Block::default()
+                                    .title(i18n::tr("connectivity"))
+                                    .borders(Borders::ALL),
+                            ),
+                        area,
+                    ),
+                    Panel::Ip => f.render_widget(
+                        Paragraph::new(ip_lines.take().unwrap_or_default())
+                            .wrap(Wrap { trim: true })
+                            .block(Block::default().title(i18n::tr("ip")).borders(Borders::ALL)),
+                        area,
+                    ),
+                    Panel::Signal => f.render_widget(view.chart.widget(area.width), area),
+                    Panel::Retries => {
+                        let parts = Layout::default()
+                            .direction(along)
+                            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                            .split(area);
+                        let (retries, failed) = view.retries.widgets(area.width);
+                        f.render_widget(retries, parts[0]);
+                        f.render_widget(failed, parts[1]);
+                    }
+                    Panel::Latency => f.render_widget(ping.widget(area.width), area),
+                    Panel::Events => events::draw_recent(f, area, rhide_info),
+                    Panel::Traffic => {
+                        let parts = Layout::default()
+                            .direction(along)
+                            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                            .split(area);
+                        let (rx, tx) = view.throughput.widgets(area.width);
+                        f.render_widget(rx, parts[0]);
+                        f.render_widget(tx, parts[1]);
+                    }
+                }
+            }
+        })
+    })?;
+    Ok(())
+}
+
+/// Scan view with networks seen by selected interface
+fn scan_state<B: Backend<Error = io::Error>>(
+    state: Arc<RwLock<ProgramState>>,
+    terminal: &mut Terminal<B>,
+    connection: &mut Connection,
+    view: &mut ScanView,
+    config: &Config,
+    rhide_info: bool,
+    bar: &StatusBar,
+) -> Result<(), io::Error> {
+    let Some(devices) = connection.devices(&config.interfaces) else {
+        return draw_reconnecting(terminal, connection, "scan", bar);
+    };
+    let selected = selected_device(&devices, &state);
+    view.update_channels(selected.and_then(|(interface, _)| interface.phy));
+    let (iface, mut rows, suspicious) = match selected {
+        Some((interface, bss_list)) => (
+            sampler::iface_name(interface),
+            scan::rows(bss_list),
+            twin::suspicious(bss_list),
+        ),
+        None => (String::new(), Vec::new(), Vec::new()),
+    };
+    if !config.scan.devices {
+        rows.retain(|r| r.category == Category::Network);
+    }
+    let total = rows.len();
+    let (filter, typing, sort) = {
+        let rstate = bench::read(&state);
+        (
+            rstate.scan_filter.clone(),
+            rstate.scan_typing,
+            rstate.scan_sort,
+        )
+    };
+    rows.retain(|r| filter.matches(r));
+    sort.apply(&mut rows);
+    view.set_filter(&filter, typing, rows.len(), total);
+    view.set_sort(sort);
+    let selected_row = bench::read(&state).scan_selected;
+    let action = bench::write(&state).take_action();
+    if action == Some(Action::CopyScan) {
+        let lines = scan::lines(&rows, view.expanded());
+        let text = match lines.get(selected_row % lines.len().max(1)) {
+            Some(scan::Line::Bss { row, .. }) => Some(row.bssid.as_str()),
+            Some(scan::Line::Group { ssid, .. }) => Some(*ssid),
+            None => None,
+        };
+        if let Some(text) = text
+            && !copy(&state, text)
+        {
+            return Ok(());
+        }
+    }
+    if action == Some(Action::ToggleGroup) {
+        let lines = scan::lines(&rows, view.expanded());
+        view.toggle(lines.get(selected_row % lines.len().max(1)));
+    }
+    let lines = scan::lines(&rows, view.expanded());
+
+    if let Some(action @ (Action::Connect | Action::Disconnect | Action::Scan)) = action
+        && let Some(backend) = view.backend()
+    {
+        let res = match action {
+            Action::Connect => match lines
+                .get(selected_row % lines.len().max(1))
+                .and_then(|l| l.ssid())
+            {
+                Some(ssid) => {
+                    info!(iface, ssid, backend = backend.name(), "connecting");
+                    events::push(Kind::Scan, format!("{} connecting to {}", iface, ssid));
+                    backend.connect(&iface, ssid)
+                }
+                None => Ok(()),
+            },
+            Action::Scan => {
+                info!(iface, backend = backend.name(), "requesting scan");
+                events::push(Kind::Scan, format!("scan requested on {}", iface));
+                backend.scan(&iface)
+            }
+            _ => {
+                info!(iface, backend = backend.name(), "disconnecting");
+                events::push(Kind::Scan, format!("{} disconnecting", iface));
+                backend.disconnect(&iface)
+            }
+        };
+        if let Err(e) = res {
+            tracing::error!(error = %e, ?action, "control backend failed");
+            bench::write(&state).fail(AppError::Backend {
+                name: backend.name(),
+                iface,
+                source: e,
+            });
+            return Ok(());
+        }
+        view.invalidate();
+    }
+
+    terminal.draw(|f| {
+        bar.frame(f, view.hint(), |f, area| {
+            view.draw(f, area, &lines, selected_row, &suspicious, rhide_info)
+        })
+    })?;
+    Ok(())
+}
+
+/// Copies `text` to clipboard, shows error and returns false on failure
+fn copy(state: &RwLock<ProgramState>, text: &str) -> bool {
+    match clipboard::copy(text) {
+        Ok(through) => {
+            info!(through, "copied selected value");
+            true
+        }
+        Err(e) => {
+            warn!(error = %e, "failed to copy");
+            bench::write(state).fail(AppError::Clipboard(e));
+            false
+        }
+    }
+}
+
+/// Clients of selected interface when it runs as access point
+fn stations_state<B: Backend<Error = io::Error>>(
+    state: Arc<RwLock<ProgramState>>,
+    terminal: &mut Terminal<B>,
+    connection: &mut Connection,
+    view: &mut StationsView,
+    filter: &InterfacesConfig,
+    rhide_info: bool,
+    bar: &StatusBar,
+) -> Result<(), io::Error> {
+    let Some(devices) = connection.devices(filter) else {
+        return draw_reconnecting(terminal, connection, "stations", bar);
+    };
+    let selected = selected_device(&devices, &state).map(|(interface, _)| interface);
+    let iface = selected.map(sampler::iface_name).unwrap_or_default();
+    let rows = selected.and_then(|i| i.index).and_then(|i| view.update(i));
+    terminal.draw(|f| {
+        bar.frame(f, ap::HINT, |f, area| {
+            view.draw(f, area, &iface, rows.as_deref(), rhide_info)
+        })
+    })?;
+    Ok(())
+}
+
+/// Country advertised by connected BSS
+fn connected_country(bss_list: &[Bss]) -> Option<String> {
+    bss_list
+        .iter()
+        .find(|b| b.status.is_some())
+        .and_then(|b| b.information_elements.as_deref())
+        .and_then(ie::country)
+}
+
+/// Regulatory domain and channels of selected interface
+fn regdomain_state<B: Backend<Error = io::Error>>(
+    state: Arc<RwLock<ProgramState>>,
+    terminal: &mut Terminal<B>,
+    connection: &mut Connection,
+    view: &mut RegdomainView,
+    filter: &InterfacesConfig,
+    bar: &StatusBar,
+) -> Result<(), io::Error> {
+    let Some(devices) = connection.devices(filter) else {
+        return draw_reconnecting(terminal, connection, "regulatory domain", bar);
     };
+    let selected = selected_device(&devices, &state);
+    let iface = selected
+        .map(|(interface, _)| sampler::iface_name(interface))
+        .unwrap_or_default();
+    let ap_country = selected.and_then(|(_, bss_list)| connected_country(bss_list));
+    view.update(selected.and_then(|(interface, _)| interface.phy));
     terminal.draw(|f| {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(80), Constraint::Percentage(20)].as_ref())
-            .split(f.size());
+        bar.frame(f, regdomain::HINT, |f, area| {
+            view.draw(f, area, &iface, ap_country.as_deref())
+        })
+    })?;
+    Ok(())
+}
 
-        let hide_paragraph =
-            Paragraph::new(hide_text).block(Block::default().title("hint").borders(Borders::ALL));
+/// Driver and firmware of selected interface
+fn adapter_state<B: Backend<Error = io::Error>>(
+    state: Arc<RwLock<ProgramState>>,
+    terminal: &mut Terminal<B>,
+    connection: &mut Connection,
+    view: &mut AdapterView,
+    filter: &InterfacesConfig,
+    bar: &StatusBar,
+) -> Result<(), io::Error> {
+    let Some(devices) = connection.devices(filter) else {
+        return draw_reconnecting(terminal, connection, "adapter", bar);
+    };
+    if let Some((interface, _)) = selected_device(&devices, &state) {
+        view.update(&sampler::iface_name(interface), interface.phy);
+    }
+    terminal.draw(|f| bar.frame(f, adapter::HINT, |f, area| view.draw(f, area)))?;
+    Ok(())
+}
 
-        f.render_widget(widget, chunks[0]);
-        f.render_widget(hide_paragraph, chunks[1]);
+/// Capability tree of wiphy of selected interface
+fn phy_state<B: Backend<Error = io::Error>>(
+    state: Arc<RwLock<ProgramState>>,
+    terminal: &mut Terminal<B>,
+    connection: &mut Connection,
+    view: &mut PhyView,
+    filter: &InterfacesConfig,
+    bar: &StatusBar,
+) -> Result<(), io::Error> {
+    let Some(devices) = connection.devices(filter) else {
+        return draw_reconnecting(terminal, connection, "phy", bar);
+    };
+    let selected = selected_device(&devices, &state).map(|(interface, _)| interface);
+    let iface = selected.map(sampler::iface_name).unwrap_or_default();
+    view.update(selected.and_then(|i| i.phy));
+    let selected_line = bench::read(&state).phy_selected;
+    if bench::write(&state).take_action() == Some(Action::ToggleNode) {
+        view.toggle(selected_line);
+    }
+    terminal.draw(|f| {
+        bar.frame(f, phy::HINT, |f, area| {
+            view.draw(f, area, &iface, selected_line)
+        })
     })?;
     Ok(())
 }
 
-/// Starts logging in file
-fn initialization_log_file() {
-    let log_path_raw = format!("{}/logs/", CONFIGURATION.as_str());
-    let log_path: &Path = Path::new(&log_path_raw);
-    let log_file = format!("{}/run-{}.log", log_path.to_str().unwrap(), Local::now());
-    if !log_path.exists() {
-        let _ = fs::create_dir(log_path);
+/// QR code view for sharing network of selected interface
+fn qr_state<B: Backend<Error = io::Error>>(
+    state: Arc<RwLock<ProgramState>>,
+    terminal: &mut Terminal<B>,
+    connection: &mut Connection,
+    view: &mut QrView,
+    backend: Option<&dyn ControlBackend>,
+    filter: &InterfacesConfig,
+    bar: &StatusBar,
+) -> Result<(), io::Error> {
+    let Some(devices) = connection.devices(filter) else {
+        return draw_reconnecting(terminal, connection, "share", bar);
+    };
+    if bench::write(&state).take_action() == Some(Action::SetPassword) {
+        view.set_password(bench::write(&state).take_input());
     }
+    let connected = selected_device(&devices, &state)
+        .and_then(|(_, bss_list)| bss_list.iter().find(|b| b.status.is_some()));
+    terminal.draw(|f| {
+        bar.frame(f, qr::HINT, |f, area| {
+            view.draw(f, area, connected, backend)
+        })
+    })?;
+    Ok(())
+}
 
-    // You can use info/debug/error loggers for logging and you're logs will be writing to file
-    let _ = simple_logging::log_to_file(log_file, log::LevelFilter::Info);
+/// Returns interface selected by user with its BSS list
+fn selected_device<'d>(
+    devices: &'d [(Interface, Vec<Bss>)],
+    state: &RwLock<ProgramState>,
+) -> Option<&'d (Interface, Vec<Bss>)> {
+    devices.get(bench::read(state).selected % devices.len().max(1))
 }
 
-/// Thread for input
-fn open_input_thread(state_clone: Arc<RwLock<ProgramState<'static>>>) {
+/// Draws reconnecting status while netlink socket is lost
+fn draw_reconnecting<B: Backend<Error = io::Error>>(
+    terminal: &mut Terminal<B>,
+    connection: &Connection,
+    title: &str,
+    bar: &StatusBar,
+) -> Result<(), io::Error> {
+    let text = i18n::fill(
+        i18n::tr("reconnecting… attempt {}, next try in {}s"),
+        &[
+            &(connection.attempts() + 1).to_string(),
+            &connection.retry_in().as_secs().to_string(),
+        ],
+    );
+    terminal.draw(|f| {
+        bar.frame(f, "Press 'q' to quit", |f, area| {
+            let reconnecting =
+                Paragraph::new(Span::styled(text, Style::default().fg(Color::Yellow))).block(
+                    Block::default()
+                        .title(i18n::tr(title))
+                        .borders(Borders::ALL),
+                );
+            f.render_widget(reconnecting, area);
+        })
+    })?;
+    Ok(())
+}
+
+/// Maps key to state transition, `None` for keys handled by current view itself
+fn key_event(state: &AppState, key: KeyCode) -> Option<Event> {
+    match state {
+        AppState::Input { .. } => match key {
+            KeyCode::Enter => Some(Event::Submit),
+            KeyCode::Esc => Some(Event::Cancel),
+            _ => None,
+        },
+        AppState::Confirm { .. } => match key {
+            KeyCode::Char('y') => Some(Event::Accept),
+            KeyCode::Char('n') | KeyCode::Esc => Some(Event::Cancel),
+            _ => None,
+        },
+        AppState::Error(_) => match key {
+            KeyCode::Esc | KeyCode::Enter => Some(Event::Dismiss),
+            KeyCode::Char('q') => Some(Event::Quit),
+            _ => None,
+        },
+        view => match key {
+            KeyCode::Esc => Some(Event::Back),
+            KeyCode::Char('q') => Some(Event::Quit),
+            KeyCode::Char('m' | 'u') => Some(Event::Open(AppState::Monitoring)),
+            KeyCode::Char('s') => Some(Event::Open(AppState::Scan)),
+            KeyCode::Char('a') => Some(Event::Open(AppState::Stations)),
+            KeyCode::Char('f') => Some(Event::Open(AppState::Frames)),
+            KeyCode::Char('g') => Some(Event::Open(AppState::Regdomain)),
+            KeyCode::Char('v') => Some(Event::Open(AppState::Adapter)),
+            KeyCode::Char('b') => Some(Event::Open(AppState::Phy)),
+            KeyCode::Char('e') => Some(Event::Open(AppState::Log)),
+            KeyCode::Char('w') => Some(Event::Open(AppState::QrCode)),
+            KeyCode::Char('o') => Some(Event::Open(AppState::Settings)),
+            KeyCode::Char('n') => Some(Event::Open(AppState::Remote)),
+            KeyCode::Char('H') => Some(Event::Open(AppState::History)),
+            KeyCode::Char('l') => Some(Event::Ask(Action::ToggleLink)),
+            KeyCode::Char('i') if *view == AppState::Monitoring => {
+                Some(Event::Ask(Action::SpeedTest))
+            }
+            KeyCode::Char('B') if *view == AppState::Monitoring => {
+                Some(Event::Ask(Action::Bufferbloat))
+            }
+            KeyCode::Char('x') if *view == AppState::Monitoring => {
+                Some(Event::Prompt(Input::TxPower))
+            }
+            KeyCode::Char('N') if *view == AppState::History => Some(Event::Prompt(Input::Note)),
+            KeyCode::Char('p') if *view == AppState::QrCode => {
+                Some(Event::Prompt(Input::WifiPassword))
+            }
+            KeyCode::Char('d') if *view == AppState::Scan => Some(Event::Ask(Action::Disconnect)),
+            _ => None,
+        },
+    }
+}
+
+/// Input thread wakes up this often to notice shutdown
+const INPUT_POLL: Duration = Duration::from_millis(100);
+
+/// Press is always handled, held navigation keys repeat, release is ignored
+fn accepted(key: &KeyEvent) -> bool {
+    match key.kind {
+        KeyEventKind::Press => true,
+        KeyEventKind::Repeat => matches!(
+            key.code,
+            KeyCode::Up
+                | KeyCode::Down
+                | KeyCode::Left
+                | KeyCode::Right
+                | KeyCode::PageUp
+                | KeyCode::PageDown
+                | KeyCode::Backspace
+        ),
+        KeyEventKind::Release => false,
+    }
+}
+
+/// Waits for key not longer than `INPUT_POLL`, so thread can stop without keypress
+fn next_key() -> io::Result<Option<KeyEvent>> {
+    if !event::poll(INPUT_POLL)? {
+        return Ok(None);
+    }
+    Ok(match event::read()? {
+        CrosstermEvent::Key(key) if accepted(&key) => Some(key),
+        _ => None,
+    })
+}
+
+/// Thread for input, stops when app quits or `shutdown` gets message or its sender is dropped
+fn open_input_thread(
+    state_clone: Arc<RwLock<ProgramState>>,
+    shutdown: Receiver<()>,
+) -> JoinHandle<()> {
     info!("input thread starting..");
     std::thread::spawn(move || {
         loop {
-            if !state_clone.read().unwrap().running {
+            if !bench::read(&state_clone).running
+                || !matches!(shutdown.try_recv(), Err(TryRecvError::Empty))
+            {
                 break;
             }
 
-            if let Some(key) = &event::read().unwrap().as_key_press_event() {
-                let mut wstate = state_clone.write().unwrap();
-                info!("{}", key.code);
-                if key.code == KeyCode::Esc {
-                    info!("exiting..");
-                    wstate.change_running();
+            let key = match next_key() {
+                Ok(Some(key)) => key,
+                Ok(None) => continue,
+                Err(e) => {
+                    // terminal is gone, nobody can quit with a key anymore
+                    tracing::error!(error = %e, "failed to read terminal input");
+                    bench::write(&state_clone).running = false;
+                    break;
                 }
-                if key.code == KeyCode::Char('q') {
-                    info!("exiting..");
-                    wstate.change_running();
-                }
-                if key.code == KeyCode::Char('m') {
-                    info!("chagning state to Monitoring..");
-                    wstate.change_state(AppState::Monitoring);
-                }
-                if key.code == KeyCode::Char('h') {
-                    info!("changed hide boolean");
-                    wstate.toggle_hide_info();
-                }
-                if key.code == KeyCode::Char('u') {
-                    info!("updating screen");
-                    wstate.change_state(AppState::Monitoring);
-                }
-            }
+            };
+            handle_key(&state_clone, key.code);
         }
         debug!("exit from thread");
-    });
+    })
 }
 
-/// Returns Paragraph for TUI if everything OK or else AppState with state in Error
-fn create_device<'a>(
-    intf: &[Interface],
-    sock: &mut Socket,
-    hide_info: bool,
-) -> Result<Paragraph<'a>, AppState<'a>> {
-    let mut text: Vec<Spans> = Vec::with_capacity(intf.len() + 2);
-    for interface in intf {
-        if let Some(indx) = interface.name.as_ref()
-            && let Some(bss) = sock
-                .get_bss_info(interface.index.unwrap())
-                .unwrap()
-                .first_mut()
-        {
-            let status = match bss.status {
-                Some(stat) => stat,
-                None => {
-                    return Err(AppState::Error {
-                        h: "Internet connection failed",
-                        d: "Internet connection do not exists",
-                    });
-                }
-            };
-            let span = Spans::from(vec![Span::styled(
-                String::from_utf8(indx.to_vec()).unwrap(),
-                Style::default().add_modifier(if status == 1 {
-                    Modifier::BOLD
-                } else {
-                    Modifier::DIM
-                }),
-            )]);
-
-            let mut signal: i32 = 0;
-            if let Some(sig) = bss.signal {
-                signal = sig / 100;
+/// Applies key to state, the same for terminal input and `--keys`
+fn handle_key(state: &RwLock<ProgramState>, key: KeyCode) {
+    let mut wstate = bench::write(state);
+    info!(key = %key, "key pressed");
+    if wstate.state == AppState::Scan && wstate.scan_typing {
+        match key {
+            KeyCode::Char(c) => wstate.scan_filter.text.push(c),
+            KeyCode::Backspace => {
+                wstate.scan_filter.text.pop();
             }
+            KeyCode::Enter => wstate.scan_typing = false,
+            KeyCode::Esc => {
+                wstate.scan_filter.text.clear();
+                wstate.scan_typing = false;
+            }
+            _ => {}
+        }
+        return;
+    }
+    if let Some(event) = key_event(&wstate.state, key) {
+        let state = wstate.transition(event);
+        info!(%state, "state changed");
+        return;
+    }
+    match (wstate.state.clone(), key) {
+        (AppState::Input { .. }, KeyCode::Backspace) => {
+            wstate.input.pop();
+        }
+        (AppState::Input { .. }, KeyCode::Char(c)) => wstate.input.push(c),
+        (AppState::Input { .. } | AppState::Confirm { .. } | AppState::Error(_), _) => {}
+        (_, KeyCode::Char('h')) => {
+            info!("changed hide boolean");
+            wstate.toggle_hide_info();
+        }
+        (_, KeyCode::Tab) => {
+            info!("selecting next interface");
+            wstate.select_next();
+        }
+        (AppState::Monitoring, KeyCode::Char('r')) => {
+            info!("toggling rfkill");
+            wstate.pending = Some(Action::ToggleRfkill);
+        }
+        (AppState::Monitoring, KeyCode::Char('y')) => wstate.pending = Some(Action::Copy),
+        (AppState::Scan, KeyCode::Char('y')) => wstate.pending = Some(Action::CopyScan),
+        (AppState::Monitoring, KeyCode::Char('z')) => {
+            info!("toggling compact view");
+            wstate.toggle_compact();
+        }
+        (AppState::Monitoring, KeyCode::Char('R')) => {
+            info!("writing session report");
+            wstate.pending = Some(Action::Report);
+        }
+        (AppState::Monitoring, KeyCode::Char('[')) => wstate.pending = Some(Action::ShrinkCharts),
+        (AppState::Monitoring, KeyCode::Char(']')) => wstate.pending = Some(Action::GrowCharts),
+        (AppState::Monitoring, KeyCode::Char('S')) => wstate.panels.status = !wstate.panels.status,
+        (AppState::Monitoring, KeyCode::Char('E')) => wstate.panels.events = !wstate.panels.events,
+        (AppState::Monitoring, KeyCode::Char('L')) => {
+            wstate.panels.horizontal = !wstate.panels.horizontal
+        }
+        (_, KeyCode::Char('?')) => wstate.panels.hint = !wstate.panels.hint,
+        (_, KeyCode::Char('P')) => {
+            info!("requesting snapshot");
+            snapshot::request();
+        }
+        (AppState::Monitoring, KeyCode::F(n @ 1..=4)) => {
+            info!(layout = n, "switching layout");
+            wstate.layout = usize::from(n - 1);
+        }
+        (AppState::Monitoring, KeyCode::Char('p')) => {
+            info!("toggling power save");
+            wstate.pending = Some(Action::TogglePowerSave);
+        }
+        (AppState::History, KeyCode::Left) => wstate.history_older(),
+        (AppState::History, KeyCode::Right) => wstate.history_newer(),
+        (AppState::History, KeyCode::Char('+')) => wstate.history_zoom_in(),
+        (AppState::History, KeyCode::Char('-')) => wstate.history_zoom_out(),
+        (AppState::History, KeyCode::End) => wstate.history_pan = 0,
+        (AppState::Log, KeyCode::Up) => wstate.log_up(),
+        (AppState::Log, KeyCode::Down) => wstate.log_down(),
+        (AppState::Phy, KeyCode::Up) => wstate.phy_up(),
+        (AppState::Phy, KeyCode::Down) => wstate.phy_down(),
+        (AppState::Phy, KeyCode::Enter) => wstate.pending = Some(Action::ToggleNode),
+        (AppState::Settings, KeyCode::Up) => wstate.settings_up(),
+        (AppState::Settings, KeyCode::Down) => wstate.settings_down(),
+        (AppState::Settings, KeyCode::Right | KeyCode::Enter) => {
+            wstate.pending = Some(Action::IncreaseSetting)
+        }
+        (AppState::Settings, KeyCode::Left) => wstate.pending = Some(Action::DecreaseSetting),
+        (AppState::Scan, KeyCode::Up) => wstate.scan_up(),
+        (AppState::Scan, KeyCode::Down) => wstate.scan_down(),
+        (AppState::Scan, KeyCode::Enter) => wstate.pending = Some(Action::ToggleGroup),
+        (AppState::Scan, KeyCode::Char('t')) => {
+            info!("triggering scan");
+            wstate.pending = Some(Action::Scan);
+        }
+        (AppState::Scan, KeyCode::Char('c')) => {
+            info!("connecting to selected network");
+            wstate.pending = Some(Action::Connect);
+        }
+        (AppState::Scan, KeyCode::Char('/')) => wstate.scan_typing = true,
+        (AppState::Scan, KeyCode::Char('S')) => wstate.scan_sort.next_column(),
+        (AppState::Scan, KeyCode::Char('R')) => wstate.scan_sort.reverse(),
+        (AppState::Scan, KeyCode::Char('1')) => wstate.scan_filter.next_band(),
+        (AppState::Scan, KeyCode::Char('2')) => wstate.scan_filter.next_security(),
+        (AppState::Scan, KeyCode::Char('3')) => wstate.scan_filter.next_signal(),
+        (AppState::Scan, KeyCode::Char('0')) => wstate.scan_filter = Default::default(),
+        _ => {}
+    }
+}
 
+/// Returns block with signal and mac address of one interface, selected one has highlighted border
+fn create_device(
+    interface: &Interface,
+    bss_list: &[Bss],
+    score: Option<u8>,
+    hide_info: bool,
+    selected: bool,
+) -> Paragraph<'static> {
+    let iface = sampler::iface_name(interface);
+    let mut text: Vec<Line> = Vec::with_capacity(4);
+    // internet itself is checked by connectivity probe, here only association is known
+    match bss_list.iter().find(|b| b.status.is_some()) {
+        Some(bss) => {
+            let signal = bss.signal.map(|sig| sig / 100).unwrap_or_default();
+            let bssid = sampler::format_bssid(bss).unwrap_or_default();
             info!(
-                "frequency {} beacon_interval {} seen_ms_ago {}",
-                bss.frequency.unwrap(),
-                bss.beacon_interval.unwrap(),
-                bss.seen_ms_ago.unwrap()
+                iface,
+                bssid,
+                signal,
+                frequency = bss.frequency,
+                beacon_interval = bss.beacon_interval,
+                seen_ms_ago = bss.seen_ms_ago,
+                "bss info"
             );
-            if let Some(m) = interface.mac.as_ref() {
-                let addr: [u8; 6] = m.as_slice().try_into().unwrap();
-                let mac = get_security_info(&MacAddr6::from(addr).to_string(), hide_info);
-
-                info!(
-                    "mac {} channel {} power {} phy {} device {}",
-                    mac,
-                    interface.channel.unwrap(),
-                    interface.power.unwrap(),
-                    interface.phy.unwrap(),
-                    interface.device.unwrap()
-                );
-
-                let signal_span = Spans::from(vec![
-                    Span::raw("Connection"),
+            text.push(Line::from(vec![
+                Span::raw(format!("{}Connection", display::icon(Icon::Wifi))),
+                Span::styled(
+                    format!(" {} ", display::signal_value(signal)),
+                    Style::default().fg(display::signal_color(signal)),
+                ),
+                Span::styled(
+                    display::unit().to_string(),
+                    Style::default().add_modifier(Modifier::ITALIC),
+                ),
+            ]));
+            if let Some(score) = score {
+                text.push(Line::from(vec![
+                    Span::raw("Quality"),
                     Span::styled(
-                        format!(" {} ", signal),
-                        Style::default().fg(get_color_for_signal(signal.abs())),
+                        format!(" {}/100", score),
+                        Style::default()
+                            .fg(quality::color(score))
+                            .add_modifier(Modifier::BOLD),
                     ),
-                    Span::styled("dBm", Style::default().add_modifier(Modifier::ITALIC)),
-                ]);
-
-                let mac_span = Spans::from(vec![
-                    Span::raw("Mac address"),
-                    Span::styled(
-                        format!(" {} ", get_security_info(&mac.to_string(), hide_info)),
-                        Style::default().fg(Color::Green),
-                    ),
-                ]);
-                text.extend([span, signal_span, mac_span]);
+                ]));
             }
         }
+        None => text.push(Line::from(Span::styled(
+            "Not associated",
+            Style::default().add_modifier(Modifier::DIM),
+        ))),
     }
-    Ok(Paragraph::new(text).block(Block::default().title("monitoring").borders(Borders::ALL)))
-}
-
-/// Returns Color for signal level.
-///
-/// # Example
-///
-/// ```
-/// use tui::style::Color;
-///
-/// let signal: i32 = 40;
-///
-/// // Returns green color for good internet connection level
-/// let color_for_signal: Color = get_color_for_signal(signal);
-/// ```
-/// # And example for bad connection
-/// ```
-/// let signal: i32 = 120;
-///
-/// // And returns red color for bad internet connection level
-/// let color_for_signal: Color = get_color_for_signal(signal);
-/// ```
-fn get_color_for_signal(signal: i32) -> Color {
-    match signal {
-        0..=60 => Color::Green,
-        61..=100 => Color::Yellow,
-        _ => Color::Red,
+    if let Some(m) = interface.mac.as_ref()
+        && let Ok(addr) = <[u8; 6]>::try_from(m.as_slice())
+    {
+        let mac = MacAddr6::from(addr).to_string();
+        info!(
+            iface,
+            mac = get_security_info(&mac, hide_info),
+            channel = interface.channel,
+            power = interface.power,
+            phy = interface.phy,
+            device = interface.device,
+            "interface info"
+        );
+        text.push(Line::from(vec![
+            Span::raw("Mac address"),
+            Span::styled(
+                format!(" {} ", get_security_info(&mac, hide_info)),
+                Style::default().fg(Color::Green),
+            ),
+        ]));
     }
+    let border = if selected {
+        display::selected_style()
+    } else {
+        Style::default()
+    };
+    Paragraph::new(text).block(
+        Block::default()
+            .title(Span::styled(
+                iface,
+                Style::default().add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(border),
+    )
 }
 
 /// Returns a information in numbers for secutiry info
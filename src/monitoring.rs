@@ -0,0 +1,394 @@
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use neli_wifi::{Bss, Interface};
+use ratatui::widgets::Paragraph;
+use tracing::warn;
+
+use crate::{
+    adapter::PowerMonitor,
+    alert::{AlertChange, Alerts},
+    beacon::Beacons,
+    bufferbloat::BufferbloatTest,
+    chart::SignalChart,
+    config::Config,
+    connection::Connection,
+    connectivity::{self, Connectivity, ConnectivityConfig},
+    csa::{self, ChannelSwitch},
+    deauth::{self, DeauthConfig, DeauthMonitor},
+    display,
+    dns::{self, Dns, DnsConfig},
+    events::{self, Kind},
+    hooks::{Hooks, HooksConfig},
+    ie,
+    ipconfig::IpConfigMonitor,
+    link, logging, notes,
+    ping::{self, PingStats},
+    profile::{self, Profile},
+    radio::PowerSaveMonitor,
+    regdomain::CountryMonitor,
+    report::{Report, Session},
+    retries::RetryChart,
+    sampler::{self, Sample},
+    scan,
+    speedtest::SpeedTest,
+    throughput::Throughput,
+    wpa::SupplicantMonitor,
+};
+
+/// Key hint, `{}` are filled with show or hide, up or down, amount of layouts and current layout
+pub const HINT: &str = "For {} mac address press 'h', Tab selects next interface, 's' opens scan, 'a' AP clients, 'f' frames, 'g' regulatory domain, 'v' adapter info, 'b' phy capabilities, 'e' event log, 'o' settings, 'n' remote agents, 'H' stored history, 'w' shares network, 'z' compact view\nPress 'r' to toggle rfkill soft block, 'l' to bring link {}, 'i' to run speed test, 'B' bufferbloat test, 'x' to set TX power, 'p' to toggle power save, 'R' to write session report, 'y' copies IP address, 'P' saves screen snapshot, F1-F{} layouts ({}), '['/']' resize charts, 'S' status, 'E' events, 'L' side by side, '?' hints";
+
+/// Fired alert stays on screen this long unless it recovers sooner
+const BANNER_TIME: Duration = Duration::from_secs(10);
+
+/// What block of one interface shows, block is built again only when this changes
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Card {
+    bssid: Option<[u8; 6]>,
+    signal: Option<i32>,
+    mac: Option<[u8; 6]>,
+    score: Option<u8>,
+    hide_info: bool,
+    selected: bool,
+    display: u64,
+}
+
+impl Card {
+    pub fn new(
+        interface: &Interface,
+        bss_list: &[Bss],
+        score: Option<u8>,
+        hide_info: bool,
+        selected: bool,
+    ) -> Self {
+        let connected = bss_list.iter().find(|b| b.status.is_some());
+        let mac = |bytes: Option<&[u8]>| bytes.and_then(|b| <[u8; 6]>::try_from(b).ok());
+        Self {
+            bssid: mac(connected.and_then(|b| b.bssid.as_deref())),
+            signal: connected.map(|b| b.signal.unwrap_or_default() / 100),
+            mac: mac(interface.mac.as_deref()),
+            score: connected.and(score),
+            hide_info,
+            selected,
+            display: display::generation(),
+        }
+    }
+}
+
+/// Monitoring view state which lives between frames
+pub struct MonitoringView {
+    pub chart: SignalChart,
+    /// Interface which points in chart belong to
+    pub chart_iface: String,
+    /// TX retries and failures of the same interface as chart
+    pub retries: RetryChart,
+    pub supplicant: SupplicantMonitor,
+    pub connectivity: Arc<RwLock<Connectivity>>,
+    pub ping: Arc<RwLock<PingStats>>,
+    pub dns: Arc<RwLock<Dns>>,
+    pub throughput: Throughput,
+    pub speedtest: SpeedTest,
+    pub bufferbloat: BufferbloatTest,
+    pub ip: IpConfigMonitor,
+    pub power_save: PowerSaveMonitor,
+    pub deauth: Arc<RwLock<DeauthMonitor>>,
+    /// Local regulatory domain compared with country of AP
+    pub country: CountryMonitor,
+    /// Temperature and power state of selected adapter
+    pub power: PowerMonitor,
+    /// The latest channel switch announced by AP
+    pub channel_switch: Arc<RwLock<Option<ChannelSwitch>>>,
+    /// Connected Bluetooth audio devices
+    #[cfg(feature = "bluetooth")]
+    pub bluetooth: Arc<RwLock<Vec<String>>>,
+    /// Connected BSSID of every interface seen in the previous frame
+    bssids: HashMap<String, Option<String>>,
+    /// Samples are taken once per `interval`, alert rules and hooks see only them
+    interval: Duration,
+    sampled: Option<Instant>,
+    fresh: bool,
+    /// Daemon runs alert rules, hooks and deauth watcher, TUI would run them twice
+    daemon: bool,
+    /// Recorded frames are played back, probes of this host would not match them
+    replay: bool,
+    alerts: Alerts,
+    hooks: Hooks,
+    beacons: Beacons,
+    profiles: Vec<Profile>,
+    /// Deviations from profile of every interface connected to network with profile
+    deviations: HashMap<String, Vec<String>>,
+    session: Session,
+    /// Latest quality score of every connected interface
+    scores: HashMap<String, u8>,
+    /// Alert which fired last and when, shown as banner for a while
+    fired: Option<(String, Instant)>,
+    /// Blocks of interfaces by raw name, kept while nothing they show changes
+    pub cards: HashMap<Vec<u8>, (Card, Paragraph<'static>)>,
+}
+
+/// Alert rules and hooks of config, none while daemon runs them
+fn rules(config: &Config, daemon: bool) -> (Alerts, Hooks) {
+    if daemon {
+        return (Alerts::new(Vec::new()), Hooks::new(HooksConfig::default()));
+    }
+    (
+        Alerts::new(config.alert.clone()),
+        Hooks::new(config.hooks.clone()),
+    )
+}
+
+impl MonitoringView {
+    /// Creates view and starts background probes, `daemon` leaves alerts to running daemon,
+    /// `replay` starts no probes at all
+    pub fn new(config: &Config, daemon: bool, replay: bool) -> Self {
+        let deauth = DeauthConfig {
+            enabled: config.deauth.enabled && !daemon && !replay,
+            ..config.deauth.clone()
+        };
+        let (alerts, hooks) = rules(config, daemon);
+        Self {
+            chart: SignalChart::new(),
+            chart_iface: String::new(),
+            retries: RetryChart::new(),
+            supplicant: SupplicantMonitor::new(),
+            connectivity: connectivity::spawn(ConnectivityConfig {
+                enabled: config.connectivity.enabled && !replay,
+                ..config.connectivity.clone()
+            }),
+            ping: if replay {
+                Arc::default()
+            } else {
+                ping::spawn()
+            },
+            dns: dns::spawn(DnsConfig {
+                enabled: config.dns.enabled && !replay,
+                ..config.dns.clone()
+            }),
+            throughput: Throughput::new(),
+            speedtest: SpeedTest::new(config.speedtest.clone()),
+            bufferbloat: BufferbloatTest::new(config.speedtest.clone()),
+            ip: IpConfigMonitor::new(),
+            power_save: PowerSaveMonitor::new(),
+            deauth: deauth::spawn(deauth),
+            country: CountryMonitor::new(),
+            power: PowerMonitor::new(),
+            channel_switch: if replay { Arc::default() } else { csa::spawn() },
+            #[cfg(feature = "bluetooth")]
+            bluetooth: if replay {
+                Arc::default()
+            } else {
+                crate::bluetooth::spawn()
+            },
+            bssids: HashMap::new(),
+            interval: Duration::from_secs(config.interval),
+            sampled: None,
+            fresh: false,
+            daemon,
+            replay,
+            alerts,
+            hooks,
+            beacons: Beacons::new(config.beacon.clone()),
+            profiles: config.profile.clone(),
+            deviations: HashMap::new(),
+            session: Session::new(),
+            scores: HashMap::new(),
+            fired: None,
+            cards: HashMap::new(),
+        }
+    }
+
+    /// Takes alert rules of reloaded config, background probes keep their settings
+    pub fn reload(&mut self, config: &Config) {
+        self.interval = Duration::from_secs(config.interval);
+        (self.alerts, self.hooks) = rules(config, self.daemon);
+        self.beacons = Beacons::new(config.beacon.clone());
+        self.profiles = config.profile.clone();
+    }
+
+    /// Operational state of interface, unknown in replay
+    pub fn link_up(&self, iface: &str) -> Option<bool> {
+        if self.replay {
+            return None;
+        }
+        link::is_up(iface)
+    }
+
+    pub fn score(&self, iface: &str) -> Option<u8> {
+        self.scores.get(iface).copied()
+    }
+
+    /// Ways interface is worse than usual, None if its network has no profile
+    pub fn deviations(&self, iface: &str) -> Option<&[String]> {
+        self.deviations.get(iface).map(Vec::as_slice)
+    }
+
+    /// Writes session report with current scan results into `dir`
+    pub fn report(
+        &self,
+        dir: &Path,
+        config: &Config,
+        connection: &mut Connection,
+        hide_info: bool,
+    ) -> io::Result<PathBuf> {
+        let devices = connection.devices(&config.interfaces).unwrap_or_default();
+        let report = Report {
+            session: &self.session,
+            chart_iface: &self.chart_iface,
+            chart: self.chart.levels(),
+            scan: devices
+                .iter()
+                .map(|(interface, bss_list)| (sampler::iface_name(interface), scan::rows(bss_list)))
+                .collect(),
+            notes: notes::load(&notes::path(dir))?,
+        };
+        let path = if hide_info || config.privacy {
+            report.write(dir, config.report.format, logging::redact)?
+        } else {
+            report.write(dir, config.report.format, str::to_string)?
+        };
+        events::push(
+            Kind::Config,
+            format!("report written to {}", path.display()),
+        );
+        Ok(path)
+    }
+
+    /// Samples interfaces once per `interval` whichever view is open
+    pub fn tick(&mut self, config: &Config, connection: &mut Connection) {
+        self.fresh = false;
+        if self.sampled.is_some_and(|t| t.elapsed() < self.interval) {
+            return;
+        }
+        let Some(devices) = connection.devices(&config.interfaces) else {
+            return;
+        };
+        self.sampled = Some(Instant::now());
+        self.fresh = true;
+        self.track(&devices, connection);
+    }
+
+    /// true if interfaces were sampled in this frame
+    pub fn fresh(&self) -> bool {
+        self.fresh
+    }
+
+    /// Records association changes, beacon gaps and alert rules of all interfaces in event log
+    fn track(&mut self, devices: &[(Interface, Vec<Bss>)], connection: &mut Connection) {
+        for (interface, bss_list) in devices {
+            let iface = sampler::iface_name(interface);
+            let bssid = bss_list
+                .iter()
+                .find(|b| b.status.is_some())
+                .and_then(sampler::format_bssid);
+            match (self.bssids.insert(iface.clone(), bssid.clone()), bssid) {
+                (Some(Some(old)), Some(new)) if old != new => events::push(
+                    Kind::Roam,
+                    format!("{} roamed from {} to {}", iface, old, new),
+                ),
+                (Some(None), Some(new)) => {
+                    events::push(Kind::Roam, format!("{} associated with {}", iface, new))
+                }
+                (Some(Some(old)), None) => {
+                    events::push(Kind::Roam, format!("{} disassociated from {}", iface, old))
+                }
+                _ => {}
+            }
+            if let Some(bss) = bss_list.iter().find(|b| b.status.is_some()) {
+                let beacon_loss = interface
+                    .index
+                    .filter(|_| self.beacons.enabled())
+                    .and_then(|index| connection.with(|s| s.get_station_info(index)))
+                    .and_then(|stations| stations.first().and_then(|s| s.beacon_loss));
+                self.beacons.observe(&iface, bss, beacon_loss);
+            }
+            let Some(mut sample) = sampler::sample(interface, bss_list) else {
+                continue;
+            };
+            if let (true, Some(index)) = (sample.connected, interface.index) {
+                connection.with(|s| sampler::link(s, index, &mut sample));
+            }
+            match sample.score {
+                Some(score) => self.scores.insert(iface.clone(), score),
+                None => self.scores.remove(&iface),
+            };
+            self.hooks.observe(&sample);
+            self.session.observe(&sample);
+            if sample.connected {
+                for change in self.alerts.evaluate(&sample) {
+                    self.hooks.threshold(&change, &sample);
+                    self.notify(&iface, &change);
+                }
+            }
+            let ssid = bss_list
+                .iter()
+                .find(|b| b.status.is_some())
+                .and_then(|b| b.information_elements.as_deref())
+                .and_then(ie::ssid);
+            self.compare(&iface, ssid.as_deref(), &sample);
+        }
+    }
+
+    /// Rings bell and raises banner for fired rule, recovered rule takes its banner down
+    fn notify(&mut self, iface: &str, change: &AlertChange) {
+        let text = format!("alert {} fired on {}", change.rule, iface);
+        if !change.fired {
+            if self.fired.as_ref().is_some_and(|(t, _)| *t == text) {
+                self.fired = None;
+            }
+            return;
+        }
+        if display::bell() {
+            // BEL is not drawn, so it does not disturb frame
+            let mut stdout = io::stdout();
+            if let Err(e) = stdout.write_all(b"\x07").and_then(|_| stdout.flush()) {
+                warn!(error = %e, "failed to ring bell");
+            }
+        }
+        self.fired = Some((text, Instant::now()));
+    }
+
+    /// Banner of recently fired alert, `on` alternates every second so it flashes
+    pub fn alert_banner(&self) -> Option<(&str, bool)> {
+        if !display::flash() {
+            return None;
+        }
+        self.fired
+            .as_ref()
+            .filter(|(_, t)| t.elapsed() < BANNER_TIME)
+            .map(|(text, t)| (text.as_str(), t.elapsed().as_secs() % 2 == 0))
+    }
+
+    /// Compares sample with profile of its network, logs when it gets worse or recovers
+    fn compare(&mut self, iface: &str, ssid: Option<&str>, sample: &Sample) {
+        let current = ssid
+            .filter(|_| sample.connected)
+            .and_then(|ssid| profile::deviations(&self.profiles, ssid, sample));
+        let was_worse = self.deviations.get(iface).is_some_and(|d| !d.is_empty());
+        match &current {
+            Some(d) if !d.is_empty() && !was_worse => events::push(
+                Kind::Alert,
+                format!(
+                    "{} worse than usual on {}: {}",
+                    iface,
+                    ssid.unwrap_or_default(),
+                    d.join(", ")
+                ),
+            ),
+            Some(d) if d.is_empty() && was_worse => events::push(
+                Kind::Alert,
+                format!("{} back to usual on {}", iface, ssid.unwrap_or_default()),
+            ),
+            _ => {}
+        }
+        match current {
+            Some(d) => self.deviations.insert(iface.to_string(), d),
+            None => self.deviations.remove(iface),
+        };
+    }
+}
@@ -0,0 +1,79 @@
+use std::io;
+
+use tracing::info;
+
+use crate::{
+    config::Config,
+    connection::Connection,
+    indicator::{Health, Status},
+};
+
+/// Standard exit codes of monitoring plugins
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum State {
+    Ok = 0,
+    Warning = 1,
+    Critical = 2,
+    Unknown = 3,
+}
+
+impl State {
+    fn name(self) -> &'static str {
+        match self {
+            State::Ok => "OK",
+            State::Warning => "WARNING",
+            State::Critical => "CRITICAL",
+            State::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+/// Prints plugin line with perfdata, returns exit code
+pub fn run(warn: i32, crit: i32, config: &Config, connection: &mut Connection) -> State {
+    let (state, line) = match Status::read(connection, &config.interfaces) {
+        Ok(status) => check(&status, warn, crit),
+        Err(e) => return unknown(&e),
+    };
+    info!(?state, "check finished");
+    println!("WIFI {} - {}", state.name(), line);
+    state
+}
+
+/// Prints plugin error, e.g. config which can't be read
+pub fn unknown(error: &io::Error) -> State {
+    println!("WIFI {} - {}", State::Unknown.name(), error);
+    State::Unknown
+}
+
+fn check(status: &Status, warn: i32, crit: i32) -> (State, String) {
+    let iface = status.iface.as_deref().unwrap_or_default();
+    let Some(signal) = status.signal else {
+        return match status.health() {
+            Health::NoInterface => (State::Unknown, "no wifi interface found".to_string()),
+            _ => (State::Critical, format!("{} is not associated", iface)),
+        };
+    };
+    let state = if signal < crit {
+        State::Critical
+    } else if signal < warn {
+        State::Warning
+    } else {
+        State::Ok
+    };
+    // 'N:' range alerts below N, units stay out as dBm is not known to plugin parsers
+    let mut perfdata = format!("signal={};{}:;{}:", signal, warn, crit);
+    if let Some(score) = status.score {
+        perfdata.push_str(&format!(" quality={}%;;;0;100", score));
+    }
+    if let Some(frequency) = status.frequency {
+        perfdata.push_str(&format!(" frequency={}", frequency));
+    }
+    let line = format!(
+        "{} {} dBm on {} | {}",
+        status.ssid.as_deref().unwrap_or("<hidden>"),
+        signal,
+        iface,
+        perfdata
+    );
+    (state, line)
+}
@@ -0,0 +1,115 @@
+use std::io;
+
+use neli::{
+    consts::{
+        nl::{NlmF, NlmFFlags, Nlmsg},
+        socket::NlFamily,
+    },
+    err::NlError,
+    genl::{Genlmsghdr, Nlattr},
+    nl::{NlPayload, Nlmsghdr},
+    socket::NlSocketHandle,
+    types::{Buffer, GenlBuffer},
+};
+use neli_wifi::{NL_80211_GENL_NAME, NL_80211_GENL_VERSION, Nl80211Attr, Nl80211Cmd};
+
+/// nl80211 message with attributes
+pub type Message = Genlmsghdr<Nl80211Cmd, Nl80211Attr>;
+pub type Attrs = GenlBuffer<Nl80211Attr, Buffer>;
+
+/// Raw nl80211 socket for commands which neli-wifi doesn't provide
+pub struct Nl80211 {
+    sock: NlSocketHandle,
+    family: u16,
+}
+
+impl Nl80211 {
+    pub fn connect() -> io::Result<Self> {
+        let mut sock = NlSocketHandle::connect(NlFamily::Generic, None, &[])?;
+        let family = sock
+            .resolve_genl_family(NL_80211_GENL_NAME)
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        Ok(Self { sock, family })
+    }
+
+    /// Sends command and returns replies, `dump` asks for all objects instead of one
+    pub fn send(&mut self, cmd: Nl80211Cmd, attrs: Attrs, dump: bool) -> io::Result<Vec<Message>> {
+        let flags = if dump {
+            NlmFFlags::new(&[NlmF::Request, NlmF::Dump])
+        } else {
+            NlmFFlags::new(&[NlmF::Request, NlmF::Ack])
+        };
+        let msg = Genlmsghdr::new(cmd, NL_80211_GENL_VERSION, attrs);
+        let header = Nlmsghdr::new(
+            None,
+            self.family,
+            flags,
+            None,
+            None,
+            NlPayload::Payload(msg),
+        );
+        self.sock.send(header).map_err(io::Error::other)?;
+
+        let mut replies = Vec::new();
+        for response in self.sock.iter::<Nlmsg, Message>(false) {
+            match response {
+                Ok(r) => {
+                    if let NlPayload::Payload(p) = r.nl_payload {
+                        replies.push(p);
+                    }
+                }
+                // kernel returns negative errno
+                Err(NlError::Nlmsgerr(e)) => return Err(io::Error::from_raw_os_error(-e.error)),
+                Err(e) => return Err(io::Error::other(e.to_string())),
+            }
+        }
+        Ok(replies)
+    }
+}
+
+impl Nl80211 {
+    /// Joins multicast group of nl80211 like `mlme` or `scan`, events are read with [`Nl80211::event`]
+    pub fn subscribe(&mut self, group: &str) -> io::Result<()> {
+        let id = self
+            .sock
+            .resolve_nl_mcast_group(NL_80211_GENL_NAME, group)
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        self.sock.add_mcast_membership(&[id])
+    }
+
+    /// Blocks until the next event from subscribed groups
+    pub fn event(&mut self) -> io::Result<Message> {
+        loop {
+            match self.sock.recv::<Nlmsg, Message>() {
+                Ok(Some(msg)) => {
+                    if let NlPayload::Payload(p) = msg.nl_payload {
+                        return Ok(p);
+                    }
+                }
+                Ok(None) => continue,
+                Err(e) => return Err(io::Error::other(e.to_string())),
+            }
+        }
+    }
+}
+
+impl Nl80211 {
+    /// Dumps wiphy description, split dump is needed because bands and channels don't fit one message
+    pub fn wiphy(&mut self, phy: u32) -> io::Result<Vec<Message>> {
+        let mut attrs = attrs(&[(Nl80211Attr::AttrWiphy, phy)])?;
+        attrs.push(
+            Nlattr::new(false, false, Nl80211Attr::AttrSplitWiphyDump, ())
+                .map_err(io::Error::other)?,
+        );
+        self.send(Nl80211Cmd::CmdGetWiphy, attrs, true)
+    }
+}
+
+/// Creates attribute list from pairs of type and u32 payload
+pub fn attrs(list: &[(Nl80211Attr, u32)]) -> io::Result<Attrs> {
+    let mut attrs = GenlBuffer::new();
+    for (kind, payload) in list {
+        attrs.push(Nlattr::new(false, false, *kind, *payload).map_err(io::Error::other)?);
+    }
+    Ok(attrs)
+}
@@ -0,0 +1,152 @@
+use std::{collections::HashMap, io};
+
+use zbus::{
+    blocking::{Connection, Proxy},
+    zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value},
+};
+
+use crate::control::ControlBackend;
+
+const DEST: &str = "org.freedesktop.NetworkManager";
+const PATH: &str = "/org/freedesktop/NetworkManager";
+
+type Settings = HashMap<String, HashMap<String, OwnedValue>>;
+
+/// NetworkManager control over system D-Bus
+pub struct NetworkManager {
+    conn: Connection,
+}
+
+impl NetworkManager {
+    /// Connects to system bus and checks that NetworkManager is running
+    pub fn connect() -> io::Result<Self> {
+        let conn = Connection::system().map_err(io::Error::other)?;
+        let nm = Self { conn };
+        nm.proxy(PATH, DEST)?
+            .get_property::<String>("Version")
+            .map_err(io::Error::other)?;
+        Ok(nm)
+    }
+
+    fn proxy<'a>(&self, path: &'a str, iface: &'a str) -> io::Result<Proxy<'a>> {
+        Proxy::new(&self.conn, DEST, path, iface).map_err(io::Error::other)
+    }
+
+    fn device(&self, iface: &str) -> io::Result<OwnedObjectPath> {
+        self.proxy(PATH, DEST)?
+            .call("GetDeviceByIpIface", &(iface,))
+            .map_err(io::Error::other)
+    }
+
+    /// Returns (object path, settings) of every saved connection
+    fn connections(&self) -> io::Result<Vec<(OwnedObjectPath, Settings)>> {
+        let paths: Vec<OwnedObjectPath> = self
+            .proxy(
+                "/org/freedesktop/NetworkManager/Settings",
+                "org.freedesktop.NetworkManager.Settings",
+            )?
+            .call("ListConnections", &())
+            .map_err(io::Error::other)?;
+        let mut res = Vec::with_capacity(paths.len());
+        for path in paths {
+            let settings: Settings = self
+                .proxy(
+                    path.as_str(),
+                    "org.freedesktop.NetworkManager.Settings.Connection",
+                )?
+                .call("GetSettings", &())
+                .map_err(io::Error::other)?;
+            res.push((path, settings));
+        }
+        Ok(res)
+    }
+}
+
+impl NetworkManager {
+    /// Returns saved connection for network with SSID
+    fn saved(&self, ssid: &str) -> io::Result<Option<(OwnedObjectPath, Settings)>> {
+        Ok(self.connections()?.into_iter().find(|(_, s)| {
+            setting(s, "802-11-wireless", "ssid")
+                .and_then(|v| Vec::<u8>::try_from(v.clone()).ok())
+                .is_some_and(|v| v == ssid.as_bytes())
+        }))
+    }
+}
+
+fn setting<'a>(settings: &'a Settings, group: &str, key: &str) -> Option<&'a OwnedValue> {
+    settings.get(group)?.get(key)
+}
+
+impl ControlBackend for NetworkManager {
+    fn name(&self) -> &'static str {
+        "NetworkManager"
+    }
+
+    fn known_networks(&self) -> io::Result<Vec<String>> {
+        Ok(self
+            .connections()?
+            .into_iter()
+            .filter(|(_, s)| s.contains_key("802-11-wireless"))
+            .filter_map(|(_, s)| String::try_from(setting(&s, "connection", "id")?.clone()).ok())
+            .collect())
+    }
+
+    fn scan(&self, iface: &str) -> io::Result<()> {
+        let device = self.device(iface)?;
+        let options: HashMap<&str, Value> = HashMap::new();
+        self.proxy(
+            device.as_str(),
+            "org.freedesktop.NetworkManager.Device.Wireless",
+        )?
+        .call::<_, _, ()>("RequestScan", &(options,))
+        .map_err(io::Error::other)
+    }
+
+    fn connect(&self, iface: &str, ssid: &str) -> io::Result<()> {
+        let device = self.device(iface)?;
+        let root = ObjectPath::try_from("/").map_err(io::Error::other)?;
+        let saved = self.saved(ssid)?;
+        let nm = self.proxy(PATH, DEST)?;
+        match saved {
+            Some((path, _)) => {
+                let _: OwnedObjectPath = nm
+                    .call("ActivateConnection", &(path, device, root))
+                    .map_err(io::Error::other)?;
+            }
+            None => {
+                // NetworkManager asks secret agent for password if network needs one
+                let mut wireless: HashMap<&str, Value> = HashMap::new();
+                wireless.insert("ssid", Value::from(ssid.as_bytes().to_vec()));
+                let mut settings: HashMap<&str, HashMap<&str, Value>> = HashMap::new();
+                settings.insert("802-11-wireless", wireless);
+                let _: (OwnedObjectPath, OwnedObjectPath) = nm
+                    .call("AddAndActivateConnection", &(settings, device, root))
+                    .map_err(io::Error::other)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn psk(&self, ssid: &str) -> io::Result<Option<String>> {
+        let Some((path, _)) = self.saved(ssid)? else {
+            return Ok(None);
+        };
+        // NetworkManager returns secrets only to privileged or owning users
+        let secrets: Settings = self
+            .proxy(
+                path.as_str(),
+                "org.freedesktop.NetworkManager.Settings.Connection",
+            )?
+            .call("GetSecrets", &("802-11-wireless-security",))
+            .map_err(io::Error::other)?;
+        Ok(setting(&secrets, "802-11-wireless-security", "psk")
+            .and_then(|v| String::try_from(v.clone()).ok()))
+    }
+
+    fn disconnect(&self, iface: &str) -> io::Result<()> {
+        let device = self.device(iface)?;
+        self.proxy(device.as_str(), "org.freedesktop.NetworkManager.Device")?
+            .call::<_, _, ()>("Disconnect", &())
+            .map_err(io::Error::other)
+    }
+}
@@ -0,0 +1,51 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Local};
+
+/// Text user attached to a moment of history
+#[derive(Clone, Debug)]
+pub struct Note {
+    pub time: DateTime<Local>,
+    pub text: String,
+}
+
+/// Notes are kept next to `history.csv`, one `time,text` line each
+pub fn path(dir: &Path) -> PathBuf {
+    dir.join("notes.csv")
+}
+
+/// Appends note with current time, empty text is ignored
+pub fn add(path: &Path, text: &str) -> io::Result<()> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(());
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{},{}", Local::now().to_rfc3339(), text)
+}
+
+/// All notes, the oldest first, missing file means no notes
+pub fn load(path: &Path) -> io::Result<Vec<Note>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    Ok(content
+        .lines()
+        .filter_map(|line| {
+            // text may contain commas, time never does
+            let (time, text) = line.split_once(',')?;
+            Some(Note {
+                time: DateTime::parse_from_rfc3339(time)
+                    .ok()?
+                    .with_timezone(&Local),
+                text: text.to_string(),
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,161 @@
+use std::{
+    fs,
+    io::{self, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    sync::{Arc, RwLock},
+    thread::sleep,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tracing::{debug, info, warn};
+
+use crate::sampler::Sample;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// `[otlp]` section of config, used in daemon mode
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct OtlpConfig {
+    /// Pushes latest samples to OpenTelemetry collector
+    pub enabled: bool,
+    /// OTLP/HTTP metrics endpoint of collector, only plain http
+    pub endpoint: String,
+    /// Seconds between two pushes
+    pub interval: u64,
+}
+
+impl Default for OtlpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: "http://localhost:4318/v1/metrics".to_string(),
+            interval: 60,
+        }
+    }
+}
+
+/// Starts thread which pushes latest samples as OTLP JSON every interval
+pub fn spawn(config: OtlpConfig, latest: Arc<RwLock<Vec<Sample>>>) -> io::Result<()> {
+    let (host, path) = split(&config.endpoint)?;
+    info!(endpoint = config.endpoint, "pushing metrics to collector");
+    std::thread::spawn(move || {
+        loop {
+            sleep(Duration::from_secs(config.interval.max(1)));
+            let body = render(&latest.read().unwrap()).to_string();
+            if let Err(e) = post(&host, &path, &body) {
+                // collector may come up later, next push tries again
+                warn!(error = %e, endpoint = config.endpoint, "failed to push metrics");
+            }
+        }
+    });
+    Ok(())
+}
+
+/// `host:port` and path of `http://` endpoint, port 4318 if it is missing
+fn split(endpoint: &str) -> io::Result<(String, String)> {
+    let rest = endpoint.strip_prefix("http://").ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("otlp endpoint must start with http://: {}", endpoint),
+        )
+    })?;
+    let (host, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/v1/metrics"),
+    };
+    let host = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:4318", host)
+    };
+    Ok((host, path.to_string()))
+}
+
+fn post(host: &str, path: &str, body: &str) -> io::Result<()> {
+    let socket = host
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "address not resolved"))?;
+    let mut stream = TcpStream::connect_timeout(&socket, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    write!(
+        stream,
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        path,
+        host,
+        body.len(),
+        body
+    )?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let status = response.lines().next().unwrap_or_default();
+    if !status.starts_with("HTTP/1.1 2") && !status.starts_with("HTTP/1.0 2") {
+        return Err(io::Error::other(status.to_string()));
+    }
+    debug!(status, "metrics pushed");
+    Ok(())
+}
+
+fn attribute(key: &str, value: &str) -> Value {
+    json!({"key": key, "value": {"stringValue": value}})
+}
+
+/// Gauge with one data point per sample which has the value
+fn gauge(
+    name: &str,
+    unit: &str,
+    samples: &[Sample],
+    value: impl Fn(&Sample) -> Option<i64>,
+) -> Value {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string();
+    let points: Vec<Value> = samples
+        .iter()
+        .filter_map(|s| {
+            let mut attributes = vec![attribute("iface", &s.iface)];
+            if let Some(ssid) = &s.ssid {
+                attributes.push(attribute("ssid", ssid));
+            }
+            if let Some(bssid) = &s.bssid {
+                attributes.push(attribute("bssid", bssid));
+            }
+            // int64 is a string in protobuf JSON mapping
+            Some(json!({
+                "attributes": attributes,
+                "timeUnixNano": now,
+                "asInt": value(s)?.to_string(),
+            }))
+        })
+        .collect();
+    json!({"name": name, "unit": unit, "gauge": {"dataPoints": points}})
+}
+
+/// Samples as OTLP `ExportMetricsServiceRequest` in JSON encoding
+pub fn render(samples: &[Sample]) -> Value {
+    let host = fs::read_to_string("/proc/sys/kernel/hostname").unwrap_or_default();
+    json!({
+        "resourceMetrics": [{
+            "resource": {
+                "attributes": [
+                    attribute("service.name", env!("CARGO_PKG_NAME")),
+                    attribute("host.name", host.trim()),
+                ],
+            },
+            "scopeMetrics": [{
+                "scope": {"name": env!("CARGO_PKG_NAME"), "version": env!("CARGO_PKG_VERSION")},
+                "metrics": [
+                    gauge("wifi.signal", "dBm", samples, |s| s.connected.then_some(s.signal.into())),
+                    gauge("wifi.bitrate", "Mbit/s", samples, |s| s.bitrate.map(i64::from)),
+                    gauge("wifi.quality", "1", samples, |s| s.score.map(i64::from)),
+                    gauge("wifi.connected", "1", samples, |s| Some(s.connected.into())),
+                ],
+            }],
+        }],
+    })
+}
@@ -0,0 +1,272 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use serde::Deserialize;
+
+/// Part of monitoring view which layout may show
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Panel {
+    Devices,
+    Status,
+    Connectivity,
+    Ip,
+    Signal,
+    /// TX retries and failures under signal chart
+    Retries,
+    /// Gateway latency chart
+    Latency,
+    /// RX and TX charts
+    Traffic,
+    /// Recent events next to ip, the same as in log view
+    Events,
+}
+
+const ALL: [Panel; 8] = [
+    Panel::Devices,
+    Panel::Status,
+    Panel::Connectivity,
+    Panel::Ip,
+    Panel::Signal,
+    Panel::Retries,
+    Panel::Latency,
+    Panel::Traffic,
+];
+
+/// Column of a row, weight across and panels stacked along it with their weights
+type Column = (u16, &'static [(Panel, u16)]);
+
+/// Details, ip and charts rows from top to bottom
+const ROWS: [&[Column]; 3] = [
+    &[
+        (50, &[(Panel::Devices, 1)]),
+        (25, &[(Panel::Status, 1)]),
+        (25, &[(Panel::Connectivity, 1)]),
+    ],
+    &[(60, &[(Panel::Ip, 1)]), (40, &[(Panel::Events, 1)])],
+    &[
+        // retries are under signal, so spikes line up with it
+        (40, &[(Panel::Signal, 1), (Panel::Retries, 1)]),
+        (30, &[(Panel::Latency, 1)]),
+        (30, &[(Panel::Traffic, 1)]),
+    ],
+];
+
+/// Column with hidden panels left out
+type ShownColumn = (u16, Vec<(Panel, u16)>);
+
+/// `[panels]` section of config, changed with keys and saved back
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct PanelsConfig {
+    /// Percent of height taken by charts row, 0 keeps proportions of layout
+    pub charts: u16,
+    pub status: bool,
+    pub events: bool,
+    /// Key hints above status line in all views
+    pub hint: bool,
+    /// Charts on the left and details on the right, wide terminals only
+    pub horizontal: bool,
+}
+
+impl Default for PanelsConfig {
+    fn default() -> Self {
+        Self {
+            charts: 0,
+            status: true,
+            events: false,
+            hint: true,
+            horizontal: false,
+        }
+    }
+}
+
+/// Step of '[' and ']' in percent
+const CHARTS_STEP: u16 = 5;
+
+impl PanelsConfig {
+    /// Grows charts row, proportions of layout are the start
+    pub fn resize(&mut self, preset: &LayoutPreset, grow: bool) {
+        let current = match self.charts {
+            0 => {
+                let total: u32 = preset.rows.iter().map(|r| u32::from(*r)).sum();
+                (u32::from(preset.rows[2]) * 100 / total.max(1)) as u16
+            }
+            charts => charts,
+        };
+        self.charts = if grow {
+            current.saturating_add(CHARTS_STEP)
+        } else {
+            current.saturating_sub(CHARTS_STEP)
+        }
+        .clamp(10, 90);
+    }
+}
+
+/// `[[layout]]` named arrangement of monitoring panels, F1–F4 pick the first four
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct LayoutPreset {
+    pub name: String,
+    /// Panels which are drawn, order doesn't matter
+    pub panels: Vec<Panel>,
+    /// Relative heights of details, ip and charts rows
+    pub rows: [u16; 3],
+}
+
+impl Default for LayoutPreset {
+    fn default() -> Self {
+        Self {
+            name: "default".to_string(),
+            panels: ALL.to_vec(),
+            rows: [40, 25, 35],
+        }
+    }
+}
+
+impl LayoutPreset {
+    /// Layout with panels and charts height changed by user
+    pub fn adjusted(&self, panels: &PanelsConfig) -> LayoutPreset {
+        let mut preset = self.clone();
+        if !panels.status {
+            preset.panels.retain(|p| *p != Panel::Status);
+        }
+        if panels.events && !preset.panels.contains(&Panel::Events) {
+            preset.panels.push(Panel::Events);
+        }
+        if panels.charts > 0 && preset.rows[2] > 0 {
+            let details = u32::from(preset.rows[0]) + u32::from(preset.rows[1]);
+            let rest = u32::from(100 - panels.charts.min(100));
+            // details rows keep their proportion between each other
+            preset.rows = [
+                (u32::from(preset.rows[0]) * rest / details.max(1)) as u16,
+                (u32::from(preset.rows[1]) * rest / details.max(1)) as u16,
+                panels.charts,
+            ];
+        }
+        preset
+    }
+}
+
+/// Layouts from config, built-in ones if config has none
+pub fn presets(config: &[LayoutPreset]) -> Vec<LayoutPreset> {
+    if !config.is_empty() {
+        return config.to_vec();
+    }
+    vec![
+        LayoutPreset::default(),
+        LayoutPreset {
+            name: "charts".to_string(),
+            panels: vec![
+                Panel::Devices,
+                Panel::Signal,
+                Panel::Retries,
+                Panel::Latency,
+                Panel::Traffic,
+            ],
+            rows: [25, 0, 75],
+        },
+        LayoutPreset {
+            name: "details".to_string(),
+            panels: vec![
+                Panel::Devices,
+                Panel::Status,
+                Panel::Connectivity,
+                Panel::Ip,
+            ],
+            rows: [60, 40, 0],
+        },
+    ]
+}
+
+/// Splits `area` by weights of shown items, hidden ones leave no gap
+fn ratios(weights: &[u16]) -> Vec<Constraint> {
+    let total: u32 = weights.iter().map(|w| u32::from(*w)).sum();
+    weights
+        .iter()
+        .map(|w| Constraint::Ratio(u32::from(*w), total.max(1)))
+        .collect()
+}
+
+/// Areas of panels shown by `preset`, columns go `across` and panels inside them `along`,
+/// `horizontal` puts charts row into left column with the other rows stacked on the right
+pub fn split(
+    preset: &LayoutPreset,
+    area: Rect,
+    across: Direction,
+    along: Direction,
+    horizontal: bool,
+) -> Vec<(Panel, Rect)> {
+    let shown = |panel: &Panel| preset.panels.contains(panel);
+    let mut rows: Vec<(u16, Vec<ShownColumn>)> = ROWS
+        .iter()
+        .zip(preset.rows)
+        .map(|(columns, weight)| {
+            let columns: Vec<ShownColumn> = columns
+                .iter()
+                .map(|(w, panels)| {
+                    let panels: Vec<(Panel, u16)> =
+                        panels.iter().filter(|(p, _)| shown(p)).copied().collect();
+                    (*w, panels)
+                })
+                .filter(|(_, panels)| !panels.is_empty())
+                .collect();
+            (weight, columns)
+        })
+        .filter(|(weight, columns)| *weight > 0 && !columns.is_empty())
+        .collect();
+
+    let mut areas = Vec::new();
+    // charts row is the last one when shown
+    let charts = preset.rows[2] > 0 && ROWS[2].iter().any(|(_, p)| p.iter().any(|(p, _)| shown(p)));
+    if horizontal && charts && rows.len() > 1 {
+        let charts = rows.split_off(rows.len() - 1);
+        let details: u16 = rows.iter().map(|(w, _)| *w).sum();
+        let sides = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(ratios(&[charts[0].0, details]))
+            .split(area);
+        // charts are stacked on top of each other, each one keeps full width of column
+        stack(
+            &charts,
+            sides[0],
+            Direction::Vertical,
+            Direction::Vertical,
+            &mut areas,
+        );
+        stack(&rows, sides[1], across, along, &mut areas);
+    } else {
+        stack(&rows, area, across, along, &mut areas);
+    }
+    areas
+}
+
+/// Splits `area` into rows from top to bottom and adds areas of their panels
+fn stack(
+    rows: &[(u16, Vec<ShownColumn>)],
+    area: Rect,
+    across: Direction,
+    along: Direction,
+    areas: &mut Vec<(Panel, Rect)>,
+) {
+    let row_areas = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(ratios(&rows.iter().map(|(w, _)| *w).collect::<Vec<_>>()))
+        .split(area);
+    for ((_, columns), row_area) in rows.iter().zip(row_areas.iter()) {
+        let column_areas = Layout::default()
+            .direction(across)
+            .constraints(ratios(&columns.iter().map(|(w, _)| *w).collect::<Vec<_>>()))
+            .split(*row_area);
+        for ((_, panels), column_area) in columns.iter().zip(column_areas.iter()) {
+            let panel_areas = Layout::default()
+                .direction(along)
+                .constraints(ratios(&panels.iter().map(|(_, w)| *w).collect::<Vec<_>>()))
+                .split(*column_area);
+            areas.extend(
+                panels
+                    .iter()
+                    .map(|(p, _)| *p)
+                    .zip(panel_areas.iter().copied()),
+            );
+        }
+    }
+}
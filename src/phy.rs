@@ -0,0 +1,333 @@
+use std::io;
+
+use neli::attr::Attribute;
+use neli_wifi::{Nl80211Attr, Nl80211Cmd};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, List, ListItem, ListState},
+};
+use tracing::warn;
+
+use crate::{
+    i18n,
+    nl80211::Nl80211,
+    regdomain::{self, Band},
+};
+
+pub const HINT: &str =
+    "Up/Down to select, Enter expands or collapses, Tab selects next interface, 'm' for monitoring";
+
+/// One line of capability tree
+#[derive(Clone, Debug)]
+pub struct Node {
+    pub label: String,
+    pub children: Vec<Node>,
+    pub collapsed: bool,
+}
+
+impl Node {
+    fn leaf(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            children: Vec::new(),
+            collapsed: false,
+        }
+    }
+
+    fn branch(label: impl Into<String>, children: Vec<Node>, collapsed: bool) -> Self {
+        Self {
+            label: label.into(),
+            children,
+            collapsed,
+        }
+    }
+}
+
+/// Visible line of flattened tree
+struct Line<'n> {
+    depth: usize,
+    node: &'n Node,
+    /// Child indices from root to node
+    path: Vec<usize>,
+}
+
+fn flatten<'n>(nodes: &'n [Node], depth: usize, prefix: &[usize], out: &mut Vec<Line<'n>>) {
+    for (i, node) in nodes.iter().enumerate() {
+        let mut path = prefix.to_vec();
+        path.push(i);
+        out.push(Line {
+            depth,
+            node,
+            path: path.clone(),
+        });
+        if !node.collapsed {
+            flatten(&node.children, depth + 1, &path, out);
+        }
+    }
+}
+
+/// Names of `enum nl80211_iftype`
+fn iftype_name(kind: u16) -> String {
+    match kind {
+        1 => "IBSS".to_string(),
+        2 => "managed".to_string(),
+        3 => "AP".to_string(),
+        4 => "AP/VLAN".to_string(),
+        5 => "WDS".to_string(),
+        6 => "monitor".to_string(),
+        7 => "mesh point".to_string(),
+        8 => "P2P-client".to_string(),
+        9 => "P2P-GO".to_string(),
+        10 => "P2P-device".to_string(),
+        11 => "outside context of a BSS".to_string(),
+        12 => "NAN".to_string(),
+        _ => format!("unknown ({})", kind),
+    }
+}
+
+/// Names of cipher suite selectors
+fn cipher_name(suite: u32) -> String {
+    match suite {
+        0x000f_ac01 => "WEP40".to_string(),
+        0x000f_ac02 => "TKIP".to_string(),
+        0x000f_ac04 => "CCMP-128".to_string(),
+        0x000f_ac05 => "WEP104".to_string(),
+        0x000f_ac06 => "BIP-CMAC-128".to_string(),
+        0x000f_ac08 => "GCMP-128".to_string(),
+        0x000f_ac09 => "GCMP-256".to_string(),
+        0x000f_ac0a => "CCMP-256".to_string(),
+        0x000f_ac0b => "BIP-GMAC-128".to_string(),
+        0x000f_ac0c => "BIP-GMAC-256".to_string(),
+        0x000f_ac0d => "BIP-CMAC-256".to_string(),
+        _ => format!("{:08x}", suite),
+    }
+}
+
+/// Dumps wiphy and builds capability tree of it
+pub fn capabilities(nl: &mut Nl80211, phy: u32) -> io::Result<Vec<Node>> {
+    let replies = nl.wiphy(phy)?;
+    let mut name = None;
+    let mut max_scan_ssids = None;
+    let mut iftypes = Vec::new();
+    let mut ciphers = Vec::new();
+    let mut commands = Vec::new();
+    let mut antennas = None;
+    for msg in &replies {
+        let attrs = msg.get_attr_handle();
+        if let Some(a) = attrs.get_attribute(Nl80211Attr::AttrWiphyName) {
+            name = Some(
+                String::from_utf8_lossy(a.nla_payload.as_ref())
+                    .trim_end_matches('\0')
+                    .to_string(),
+            );
+        }
+        if let Some(n) = attrs
+            .get_attribute(Nl80211Attr::AttrMaxNumScanSsids)
+            .and_then(|a| a.get_payload_as::<u8>().ok())
+        {
+            max_scan_ssids = Some(n);
+        }
+        if let Some(a) = attrs.get_attribute(Nl80211Attr::AttrSupportedIftypes) {
+            // flags, type of each attribute is interface type
+            let kinds = a.get_attr_handle::<u16>().map_err(io::Error::other)?;
+            for kind in kinds.iter() {
+                iftypes.push(Node::leaf(iftype_name(kind.nla_type.nla_type)));
+            }
+        }
+        if let Some(a) = attrs.get_attribute(Nl80211Attr::AttrCipherSuites) {
+            for suite in a.nla_payload.as_ref().chunks_exact(4) {
+                let suite = u32::from_ne_bytes([suite[0], suite[1], suite[2], suite[3]]);
+                ciphers.push(Node::leaf(cipher_name(suite)));
+            }
+        }
+        if let Some(a) = attrs.get_attribute(Nl80211Attr::AttrSupportedCommands) {
+            let cmds = a.get_attr_handle::<u16>().map_err(io::Error::other)?;
+            for cmd in cmds.iter() {
+                if let Ok(id) = cmd.get_payload_as::<u32>() {
+                    commands.push(Node::leaf(format!("{:?}", Nl80211Cmd::from(id as u8))));
+                }
+            }
+        }
+        let tx = attrs
+            .get_attribute(Nl80211Attr::AttrWiphyAntennaAvailTx)
+            .and_then(|a| a.get_payload_as::<u32>().ok());
+        let rx = attrs
+            .get_attribute(Nl80211Attr::AttrWiphyAntennaAvailRx)
+            .and_then(|a| a.get_payload_as::<u32>().ok());
+        if let (Some(tx), Some(rx)) = (tx, rx) {
+            antennas = Some(format!("antennas: TX {:#x} RX {:#x}", tx, rx));
+        }
+    }
+
+    let mut tree = vec![Node::leaf(format!(
+        "name: {}",
+        name.unwrap_or_else(|| format!("phy{}", phy))
+    ))];
+    if let Some(n) = max_scan_ssids {
+        tree.push(Node::leaf(format!("max scan SSIDs: {}", n)));
+    }
+    if let Some(a) = antennas {
+        tree.push(Node::leaf(a));
+    }
+    tree.push(Node::branch(
+        "bands",
+        regdomain::bands(&replies)?.iter().map(band).collect(),
+        false,
+    ));
+    tree.push(Node::branch("interface modes", iftypes, false));
+    tree.push(Node::branch("ciphers", ciphers, false));
+    tree.push(Node::branch(
+        format!("commands ({})", commands.len()),
+        commands,
+        true,
+    ));
+    Ok(tree)
+}
+
+fn band(band: &Band) -> Node {
+    let mut children = Vec::new();
+    if let Some(capa) = band.ht_capa {
+        children.push(Node::leaf(format!("HT capabilities: {:#06x}", capa)));
+    }
+    if let Some(capa) = band.vht_capa {
+        children.push(Node::leaf(format!("VHT capabilities: {:#010x}", capa)));
+    }
+    let channels = band
+        .channels
+        .iter()
+        .map(|c| {
+            let mut label = format!(
+                "{} MHz [{}]",
+                c.frequency,
+                regdomain::channel_number(c.frequency).map_or("-".to_string(), |n| n.to_string())
+            );
+            if c.disabled {
+                label.push_str(" disabled");
+            } else if let Some(p) = c.max_power {
+                label.push_str(&format!(" {:.1} dBm", f64::from(p) / 100.0));
+            }
+            if c.no_ir {
+                label.push_str(" no-IR");
+            }
+            if c.radar {
+                label.push_str(" radar");
+            }
+            Node::leaf(label)
+        })
+        .collect();
+    children.push(Node::branch(
+        format!("channels ({})", band.channels.len()),
+        channels,
+        true,
+    ));
+    Node::branch(band.name, children, false)
+}
+
+/// PHY browser state which lives between frames, tree is loaded once per wiphy
+pub struct PhyView {
+    nl: Option<Nl80211>,
+    phy: Option<u32>,
+    tree: Vec<Node>,
+    error: Option<String>,
+}
+
+impl PhyView {
+    pub fn new() -> Self {
+        Self {
+            nl: None,
+            phy: None,
+            tree: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub fn update(&mut self, phy: Option<u32>) {
+        if self.phy == phy {
+            return;
+        }
+        self.phy = phy;
+        self.tree.clear();
+        let Some(phy) = phy else {
+            return;
+        };
+        if self.nl.is_none() {
+            self.nl = Nl80211::connect()
+                .inspect_err(|e| warn!(error = %e, "failed to open nl80211 socket"))
+                .ok();
+        }
+        let Some(nl) = self.nl.as_mut() else {
+            self.error = Some("nl80211 is not available".to_string());
+            return;
+        };
+        match capabilities(nl, phy) {
+            Ok(tree) => {
+                self.tree = tree;
+                self.error = None;
+            }
+            Err(e) => {
+                warn!(error = %e, phy, "failed to dump wiphy");
+                self.error = Some(e.to_string());
+                self.nl = None;
+            }
+        }
+    }
+
+    /// Expands or collapses node on visible line
+    pub fn toggle(&mut self, selected: usize) {
+        let mut lines = Vec::new();
+        flatten(&self.tree, 0, &[], &mut lines);
+        let Some(path) = lines
+            .get(selected % lines.len().max(1))
+            .map(|l| l.path.clone())
+        else {
+            return;
+        };
+        let mut nodes = &mut self.tree;
+        for (depth, i) in path.iter().enumerate() {
+            if depth + 1 == path.len() {
+                let node = &mut nodes[*i];
+                node.collapsed = !node.collapsed;
+                return;
+            }
+            nodes = &mut nodes[*i].children;
+        }
+    }
+
+    pub fn draw(&self, f: &mut Frame, area: Rect, iface: &str, selected: usize) {
+        let mut lines = Vec::new();
+        flatten(&self.tree, 0, &[], &mut lines);
+        let items: Vec<ListItem> = match &self.error {
+            Some(e) => vec![ListItem::new(e.as_str()).style(Style::default().fg(Color::Yellow))],
+            None => lines
+                .iter()
+                .map(|l| {
+                    let marker = match (l.node.children.is_empty(), l.node.collapsed) {
+                        (true, _) => "  ",
+                        (false, true) => "+ ",
+                        (false, false) => "- ",
+                    };
+                    ListItem::new(format!(
+                        "{}{}{}",
+                        "  ".repeat(l.depth),
+                        marker,
+                        l.node.label
+                    ))
+                })
+                .collect(),
+        };
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(i18n::fill(i18n::tr("capabilities of {}"), &[iface]))
+                    .borders(Borders::ALL),
+            )
+            .highlight_style(Style::default().bg(Color::DarkGray));
+        let mut list_state = ListState::default();
+        if !lines.is_empty() && self.error.is_none() {
+            list_state.select(Some(selected % lines.len()));
+        }
+        f.render_stateful_widget(list, area, &mut list_state);
+    }
+}
@@ -0,0 +1,195 @@
+use std::{
+    collections::VecDeque,
+    io,
+    net::{Ipv4Addr, UdpSocket},
+    os::fd::FromRawFd,
+    sync::{Arc, RwLock},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    style::{Color, Style},
+    widgets::{Block, Borders, Sparkline},
+};
+use tracing::{info, warn};
+
+use crate::{
+    events::{self, Kind},
+    i18n, ipconfig,
+};
+
+/// One echo request per interval, reply which is later than interval counts as lost
+const INTERVAL: Duration = Duration::from_secs(1);
+/// Amount of replies used for loss and jitter
+const WINDOW: usize = 60;
+/// Amount of points kept for chart
+const CAPACITY: usize = 300;
+
+/// Latency of default gateway
+#[derive(Default)]
+pub struct PingStats {
+    pub gateway: Option<Ipv4Addr>,
+    /// Reason why gateway can't be pinged at all
+    pub error: Option<String>,
+    window: VecDeque<Option<Duration>>,
+    points: Vec<u64>,
+}
+
+impl PingStats {
+    fn record(&mut self, rtt: Option<Duration>) {
+        if self.window.len() == WINDOW {
+            self.window.pop_front();
+        }
+        self.window.push_back(rtt);
+        // lost replies are only counted in loss, chart shows replied ones
+        if let Some(rtt) = rtt {
+            if self.points.len() == CAPACITY {
+                self.points.remove(0);
+            }
+            self.points.push(rtt.as_micros() as u64);
+        }
+    }
+
+    /// Latest round trip time
+    pub fn latency(&self) -> Option<Duration> {
+        self.window.back().copied().flatten()
+    }
+
+    /// Mean difference between two consecutive replies
+    pub fn jitter(&self) -> Option<Duration> {
+        let replies: Vec<Duration> = self.window.iter().flatten().copied().collect();
+        if replies.len() < 2 {
+            return None;
+        }
+        let sum: Duration = replies.windows(2).map(|w| w[0].abs_diff(w[1])).sum();
+        Some(sum / (replies.len() - 1) as u32)
+    }
+
+    /// Lost requests in percents
+    pub fn loss(&self) -> Option<f64> {
+        if self.window.is_empty() {
+            return None;
+        }
+        let lost = self.window.iter().filter(|r| r.is_none()).count();
+        Some(lost as f64 * 100.0 / self.window.len() as f64)
+    }
+
+    /// Returns sparkline with the newest latencies which fit into `width`
+    pub fn widget(&self, width: u16) -> Sparkline<'_> {
+        let visible = usize::from(width.saturating_sub(2));
+        let start = self.points.len().saturating_sub(visible);
+        Sparkline::default()
+            .block(
+                Block::default()
+                    .title(i18n::tr("gateway latency"))
+                    .borders(Borders::ALL),
+            )
+            .data(&self.points[start..])
+            .style(Style::default().fg(Color::Cyan))
+    }
+}
+
+/// Starts thread which pings default gateway, returns shared statistics
+pub fn spawn() -> Arc<RwLock<PingStats>> {
+    let stats = Arc::new(RwLock::new(PingStats::default()));
+    let shared = stats.clone();
+    std::thread::spawn(move || {
+        let mut seq: u16 = 0;
+        loop {
+            let started = Instant::now();
+            let gateway = ipconfig::default_gateway(None);
+            if gateway != shared.read().unwrap().gateway {
+                info!(?gateway, "default gateway changed");
+                events::push(
+                    Kind::Network,
+                    match gateway {
+                        Some(g) => format!("default gateway {}", g),
+                        None => "no default gateway".to_string(),
+                    },
+                );
+                // latency of previous gateway says nothing about new one
+                *shared.write().unwrap() = PingStats {
+                    gateway,
+                    ..PingStats::default()
+                };
+            }
+            if let Some(gw) = gateway {
+                seq = seq.wrapping_add(1);
+                match ping(gw, seq) {
+                    Ok(rtt) => {
+                        let mut stats = shared.write().unwrap();
+                        stats.error = None;
+                        stats.record(rtt);
+                    }
+                    Err(e) => {
+                        warn!(error = %e, "gateway ping failed");
+                        shared.write().unwrap().error = Some(e.to_string());
+                    }
+                }
+            }
+            sleep(INTERVAL.saturating_sub(started.elapsed()));
+        }
+    });
+    stats
+}
+
+/// Sends one ICMP echo request, returns `None` if reply is not received in time
+pub fn ping(addr: Ipv4Addr, seq: u16) -> io::Result<Option<Duration>> {
+    // unprivileged ping socket, allowed by net.ipv4.ping_group_range
+    let fd = unsafe {
+        libc::socket(
+            libc::AF_INET,
+            libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+            libc::IPPROTO_ICMP,
+        )
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let socket = unsafe { UdpSocket::from_raw_fd(fd) };
+
+    // identifier is replaced by kernel
+    let mut request = [8, 0, 0, 0, 0, 0, (seq >> 8) as u8, seq as u8];
+    let sum = checksum(&request);
+    request[2..4].copy_from_slice(&sum.to_be_bytes());
+    let started = Instant::now();
+    socket.send_to(&request, (addr, 0))?;
+
+    let mut buf = [0; 64];
+    loop {
+        let left = INTERVAL.saturating_sub(started.elapsed());
+        if left.is_zero() {
+            return Ok(None);
+        }
+        socket.set_read_timeout(Some(left))?;
+        match socket.recv(&mut buf) {
+            // echo reply with our sequence number
+            Ok(n) if n >= 8 && buf[0] == 0 && buf[6..8] == request[6..8] => {
+                return Ok(Some(started.elapsed()));
+            }
+            Ok(_) => continue,
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                return Ok(None);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Internet checksum from RFC 1071
+fn checksum(data: &[u8]) -> u16 {
+    let mut sum: u32 = data
+        .chunks(2)
+        .map(|c| u32::from(u16::from_be_bytes([c[0], *c.get(1).unwrap_or(&0)])))
+        .sum();
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
@@ -0,0 +1,109 @@
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Write},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use tracing::{info, warn};
+
+use crate::{
+    config::Config,
+    connection::Connection,
+    display, logging,
+    sampler::{self, Sample},
+    wakeup::Wakeup,
+};
+
+/// Signal has to move this many dB before it is announced again, so reader is not flooded
+const SIGNAL_STEP: i32 = 5;
+
+/// What was announced last about one interface
+#[derive(PartialEq, Eq)]
+struct Announced {
+    bssid: Option<String>,
+    signal: i32,
+    quality: &'static str,
+}
+
+/// `wlan0 connected to aa:bb:cc:dd:ee:ff, signal -62 dBm, fair`
+fn line(sample: &Sample, privacy: bool) -> String {
+    let text = match (&sample.bssid, sample.connected) {
+        (Some(bssid), true) => format!(
+            "{} connected to {}, signal {} {}, {}",
+            sample.iface,
+            bssid,
+            display::signal_value(sample.signal),
+            display::unit(),
+            display::quality(sample.signal)
+        ),
+        _ => format!("{} not connected", sample.iface),
+    };
+    if privacy {
+        logging::redact(&text)
+    } else {
+        text
+    }
+}
+
+/// Prints state as plain lines to stdout only when it changes, for screen readers and braille
+/// displays, Enter repeats state of all interfaces
+pub fn run(config: &Config, connection: &mut Connection) -> io::Result<()> {
+    info!(interval = config.interval, "plain output started");
+    let repeat = Arc::new(AtomicBool::new(false));
+    let flag = repeat.clone();
+    std::thread::spawn(move || {
+        for _ in io::stdin().lock().lines().map_while(Result::ok) {
+            flag.store(true, Ordering::Relaxed);
+        }
+    });
+    let mut announced: HashMap<String, Announced> = HashMap::new();
+    let mut reconnecting = false;
+    let mut out = io::stdout();
+    let wakeup = Wakeup::spawn(config);
+    loop {
+        let Some(samples) = connection.with(|s| sampler::collect(s, &config.interfaces)) else {
+            if !reconnecting {
+                writeln!(out, "wifi interfaces are not available, retrying")?;
+                warn!(
+                    attempts = connection.attempts(),
+                    "netlink socket is not available"
+                );
+            }
+            reconnecting = true;
+            wakeup.sleep(wakeup.interval(config.interval));
+            continue;
+        };
+        reconnecting = false;
+        let all = repeat.swap(false, Ordering::Relaxed);
+        if samples.is_empty() && all {
+            writeln!(out, "no wifi interface found")?;
+        }
+        for sample in &samples {
+            let current = Announced {
+                bssid: sample.bssid.clone().filter(|_| sample.connected),
+                signal: sample.signal,
+                quality: display::quality(sample.signal),
+            };
+            let changed = announced.get(&sample.iface).is_none_or(|last| {
+                last.bssid != current.bssid
+                    || last.quality != current.quality
+                    || (last.signal - current.signal).abs() >= SIGNAL_STEP
+            });
+            if changed || all {
+                writeln!(out, "{}", line(sample, config.privacy))?;
+                announced.insert(sample.iface.clone(), current);
+            }
+        }
+        for iface in announced.keys() {
+            if !samples.iter().any(|s| &s.iface == iface) {
+                writeln!(out, "{} is gone", iface)?;
+            }
+        }
+        announced.retain(|iface, _| samples.iter().any(|s| &s.iface == iface));
+        out.flush()?;
+        wakeup.sleep(wakeup.interval(config.interval));
+    }
+}
@@ -0,0 +1,69 @@
+use serde::Deserialize;
+
+use crate::sampler::Sample;
+
+/// Expected quality of one network from `[[profile]]` section of config
+#[derive(Clone, Debug, Deserialize)]
+pub struct Profile {
+    pub ssid: String,
+    /// Signal in dBm which is usually reached at this location
+    pub min_signal: Option<i32>,
+    /// Band the network should be used on
+    pub band: Option<Band>,
+    /// Access points which should be used, any if empty
+    #[serde(default)]
+    pub bssids: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+pub enum Band {
+    #[serde(rename = "2.4")]
+    Band2,
+    #[serde(rename = "5")]
+    Band5,
+    #[serde(rename = "6")]
+    Band6,
+}
+
+impl Band {
+    pub fn of(frequency: u32) -> Self {
+        match frequency {
+            ..3000 => Band::Band2,
+            3000..5925 => Band::Band5,
+            _ => Band::Band6,
+        }
+    }
+}
+
+impl std::fmt::Display for Band {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Band::Band2 => write!(f, "2.4 GHz"),
+            Band::Band5 => write!(f, "5 GHz"),
+            Band::Band6 => write!(f, "6 GHz"),
+        }
+    }
+}
+
+/// Ways connected sample is worse than profile of its SSID, None if SSID has no profile
+pub fn deviations(profiles: &[Profile], ssid: &str, sample: &Sample) -> Option<Vec<String>> {
+    let profile = profiles.iter().find(|p| p.ssid == ssid)?;
+    let mut res = Vec::new();
+    if let Some(min) = profile.min_signal
+        && sample.signal < min
+    {
+        res.push(format!("signal {} dBm, usually {} dBm", sample.signal, min));
+    }
+    if let (Some(band), Some(frequency)) = (profile.band, sample.frequency)
+        && Band::of(frequency) != band
+    {
+        res.push(format!("on {}, expected {}", Band::of(frequency), band));
+    }
+    if let Some(bssid) = &sample.bssid
+        && !profile.bssids.is_empty()
+        && !profile.bssids.iter().any(|b| b.eq_ignore_ascii_case(bssid))
+    {
+        res.push(format!("{} is not a preferred BSSID", bssid));
+    }
+    Some(res)
+}
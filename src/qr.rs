@@ -0,0 +1,157 @@
+use qrcode::{QrCode, render::unicode::Dense1x2};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    widgets::{Block, Borders, Paragraph},
+};
+use tracing::{info, warn};
+
+use crate::{
+    control::ControlBackend,
+    i18n,
+    ie::{self, Security},
+};
+use neli_wifi::Bss;
+
+pub const HINT: &str = "Press 'p' to type password, 'm' for monitoring";
+
+/// QR view state which lives between frames
+pub struct QrView {
+    ssid: String,
+    password: Option<String>,
+    looked_up: bool,
+}
+
+impl QrView {
+    pub fn new() -> Self {
+        Self {
+            ssid: String::new(),
+            password: None,
+            looked_up: false,
+        }
+    }
+
+    /// Sets password typed by user for current network
+    pub fn set_password(&mut self, password: String) {
+        self.password = Some(password);
+    }
+
+    pub fn draw(
+        &mut self,
+        f: &mut Frame,
+        area: Rect,
+        connected: Option<&Bss>,
+        backend: Option<&dyn ControlBackend>,
+    ) {
+        let ies = connected.and_then(|b| b.information_elements.as_deref());
+        let Some(ssid) = ies.and_then(ie::ssid) else {
+            let text = Paragraph::new("Selected interface is not connected to a visible network")
+                .block(
+                    Block::default()
+                        .title(i18n::tr("share"))
+                        .borders(Borders::ALL),
+                );
+            f.render_widget(text, area);
+            return;
+        };
+        let security = ies.map_or(Security::Open, ie::security);
+        if self.ssid != ssid {
+            self.ssid = ssid.clone();
+            self.password = None;
+            self.looked_up = false;
+        }
+        if security != Security::Open && self.password.is_none() && !self.looked_up {
+            self.looked_up = true;
+            if let Some(backend) = backend {
+                match backend.psk(&ssid) {
+                    Ok(psk) => {
+                        info!(found = psk.is_some(), "password lookup");
+                        self.password = psk;
+                    }
+                    Err(e) => warn!(error = %e, "failed to read saved password"),
+                }
+            }
+        }
+
+        let title = i18n::fill(i18n::tr("share {} ({})"), &[&ssid, &security.to_string()]);
+        let text = match (&self.password, security) {
+            (None, s) if s != Security::Open => {
+                "Password is unknown, press 'p' to type it".to_string()
+            }
+            (password, _) => {
+                let uri = wifi_uri(&ssid, security, password.as_deref());
+                match QrCode::new(uri.as_bytes()) {
+                    // inverted colors, so code is readable on dark terminals
+                    Ok(code) => code
+                        .render::<Dense1x2>()
+                        .dark_color(Dense1x2::Light)
+                        .light_color(Dense1x2::Dark)
+                        .build(),
+                    Err(e) => format!("Failed to build QR code: {}", e),
+                }
+            }
+        };
+        let paragraph = Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .block(Block::default().title(title).borders(Borders::ALL));
+        f.render_widget(paragraph, area);
+    }
+}
+
+/// Returns `WIFI:` URI understood by phone cameras
+pub fn wifi_uri(ssid: &str, security: Security, password: Option<&str>) -> String {
+    let kind = match security {
+        Security::Open => "nopass",
+        // WPA covers WPA2 and transition mode WPA3 networks
+        Security::Wpa | Security::Wpa2 => "WPA",
+        Security::Wpa3 => "SAE",
+    };
+    let mut uri = format!("WIFI:T:{};S:{};", kind, escape(ssid));
+    if let Some(password) = password
+        && security != Security::Open
+    {
+        uri.push_str(&format!("P:{};", escape(password)));
+    }
+    uri.push(';');
+    uri
+}
+
+fn escape(value: &str) -> String {
+    let mut res = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if matches!(ch, '\\' | ';' | ',' | ':' | '"') {
+            res.push('\\');
+        }
+        res.push(ch);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_prefixes_special_characters() {
+        assert_eq!(escape(r#"a;b,c:d"e\f"#), r#"a\;b\,c\:d\"e\\f"#);
+        assert_eq!(escape("кафе"), "кафе");
+    }
+
+    #[test]
+    fn wifi_uri_follows_security() {
+        assert_eq!(
+            wifi_uri("home;1", Security::Wpa2, Some("pa:ss")),
+            r"WIFI:T:WPA;S:home\;1;P:pa\:ss;;"
+        );
+        assert_eq!(
+            wifi_uri("home", Security::Wpa3, Some("secret")),
+            "WIFI:T:SAE;S:home;P:secret;;"
+        );
+        // password of open network is never written
+        assert_eq!(
+            wifi_uri("cafe", Security::Open, Some("secret")),
+            "WIFI:T:nopass;S:cafe;;"
+        );
+        assert_eq!(wifi_uri("home", Security::Wpa, None), "WIFI:T:WPA;S:home;;");
+    }
+}
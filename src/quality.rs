@@ -0,0 +1,178 @@
+use std::sync::Mutex;
+
+use neli::attr::Attribute;
+use neli_wifi::{Nl80211Attr, Nl80211Cmd, Nl80211SurveyInfo, Station};
+use ratatui::style::Color;
+use tracing::debug;
+
+use crate::nl80211::{self, Nl80211};
+
+/// Socket for survey dumps, neli-wifi has no survey command
+static SURVEY: Mutex<Option<Nl80211>> = Mutex::new(None);
+
+/// Everything score is computed from, unknown parts are left out
+#[derive(Clone, Debug, Default)]
+pub struct Link {
+    /// dBm
+    pub signal: i32,
+    /// Noise floor of channel in dBm
+    pub noise: Option<i32>,
+    /// TX bitrate in Mbit/s
+    pub bitrate: Option<u32>,
+    /// Share of retried TX frames since association
+    pub retries: Option<f64>,
+    /// Share of time channel was busy
+    pub busy: Option<f64>,
+}
+
+/// Noise and busy time of channel interface is using
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Survey {
+    pub noise: Option<i32>,
+    pub busy: Option<f64>,
+}
+
+/// Reads survey of channel in use, drivers without survey give empty survey
+pub fn survey(index: i32) -> Survey {
+    let mut nl = SURVEY.lock().unwrap();
+    if nl.is_none() {
+        *nl = Nl80211::connect()
+            .inspect_err(|e| debug!(error = %e, "failed to open nl80211 socket for survey"))
+            .ok();
+    }
+    let Some(socket) = nl.as_mut() else {
+        return Survey::default();
+    };
+    let replies = nl80211::attrs(&[(Nl80211Attr::AttrIfindex, index as u32)])
+        .and_then(|attrs| socket.send(Nl80211Cmd::CmdGetSurvey, attrs, true));
+    let replies = match replies {
+        Ok(replies) => replies,
+        Err(e) => {
+            debug!(error = %e, "survey failed");
+            // socket may be broken, next survey opens new one
+            *nl = None;
+            return Survey::default();
+        }
+    };
+    for msg in &replies {
+        let attrs = msg.get_attr_handle();
+        let Some(info) = attrs
+            .get_attribute(Nl80211Attr::AttrSurveyInfo)
+            .and_then(|a| a.get_attr_handle::<Nl80211SurveyInfo>().ok())
+        else {
+            continue;
+        };
+        if info
+            .get_attribute(Nl80211SurveyInfo::SurveyInfoInUse)
+            .is_none()
+        {
+            continue;
+        }
+        let noise = info
+            .get_attribute(Nl80211SurveyInfo::SurveyInfoNoise)
+            .and_then(|a| a.get_payload_as::<u8>().ok())
+            .map(|n| i32::from(n as i8));
+        let time = info
+            .get_attribute(Nl80211SurveyInfo::SurveyInfoTime)
+            .and_then(|a| a.get_payload_as::<u64>().ok());
+        let busy = info
+            .get_attribute(Nl80211SurveyInfo::SurveyInfoTimeBusy)
+            .and_then(|a| a.get_payload_as::<u64>().ok());
+        return Survey {
+            noise,
+            busy: match (time, busy) {
+                (Some(time), Some(busy)) if time > 0 => Some(busy as f64 / time as f64),
+                _ => None,
+            },
+        };
+    }
+    Survey::default()
+}
+
+/// Share of retried frames from station counters
+pub fn retries(station: &Station) -> Option<f64> {
+    let packets = station.tx_packets.filter(|p| *p > 0)?;
+    Some(f64::from(station.tx_retries?) / f64::from(packets))
+}
+
+/// 0–100, weighted average of parts which are known
+pub fn score(link: &Link) -> u8 {
+    let scale =
+        |value: f64, worst: f64, best: f64| ((value - worst) / (best - worst)).clamp(0.0, 1.0);
+    let mut parts = vec![(0.35, scale(f64::from(link.signal), -90.0, -50.0))];
+    if let Some(noise) = link.noise {
+        parts.push((0.2, scale(f64::from(link.signal - noise), 10.0, 40.0)));
+    }
+    if let Some(bitrate) = link.bitrate.filter(|b| *b > 0) {
+        // logarithmic, so 54 Mbit/s is half way from 6 to 600
+        parts.push((0.2, scale(f64::from(bitrate).ln(), 6f64.ln(), 600f64.ln())));
+    }
+    if let Some(retries) = link.retries {
+        parts.push((0.15, 1.0 - scale(retries, 0.05, 0.4)));
+    }
+    if let Some(busy) = link.busy {
+        parts.push((0.1, 1.0 - scale(busy, 0.3, 0.9)));
+    }
+    let weights: f64 = parts.iter().map(|(w, _)| w).sum();
+    let sum: f64 = parts.iter().map(|(w, v)| w * v).sum();
+    (sum / weights * 100.0).round() as u8
+}
+
+/// Own scale of score, independent of signal thresholds
+pub fn color(score: u8) -> Color {
+    match score {
+        70.. => Color::Green,
+        40..70 => Color::Yellow,
+        _ => Color::Red,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_spans_whole_range() {
+        let best = Link {
+            signal: -40,
+            noise: Some(-95),
+            bitrate: Some(866),
+            retries: Some(0.0),
+            busy: Some(0.1),
+        };
+        let worst = Link {
+            signal: -95,
+            noise: Some(-90),
+            bitrate: Some(1),
+            retries: Some(0.6),
+            busy: Some(1.0),
+        };
+        assert_eq!(score(&best), 100);
+        assert_eq!(score(&worst), 0);
+    }
+
+    #[test]
+    fn unknown_parts_are_left_out() {
+        // only signal is known, half way from -90 to -50 dBm
+        let link = Link {
+            signal: -70,
+            ..Link::default()
+        };
+        assert_eq!(score(&link), 50);
+        // zero bitrate is unknown too
+        let link = Link {
+            bitrate: Some(0),
+            ..link
+        };
+        assert_eq!(score(&link), 50);
+    }
+
+    #[test]
+    fn color_has_own_thresholds() {
+        assert_eq!(color(100), Color::Green);
+        assert_eq!(color(70), Color::Green);
+        assert_eq!(color(69), Color::Yellow);
+        assert_eq!(color(40), Color::Yellow);
+        assert_eq!(color(39), Color::Red);
+    }
+}
@@ -0,0 +1,144 @@
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use neli::{attr::Attribute, genl::Nlattr, types::Buffer};
+use neli_wifi::{Nl80211Attr, Nl80211AttrCqm, Nl80211Cmd};
+
+use crate::nl80211::{self, Nl80211};
+
+/// Power save state is read again after this time
+const REFRESH: Duration = Duration::from_secs(2);
+
+/// Sets fixed TX power in dBm, `None` returns power control to driver
+pub fn set_tx_power(index: i32, dbm: Option<f64>) -> io::Result<()> {
+    // nl80211_tx_power_setting: automatic and fixed
+    const AUTOMATIC: u32 = 0;
+    const FIXED: u32 = 2;
+    let mut attrs = vec![(Nl80211Attr::AttrIfindex, index as u32)];
+    match dbm {
+        Some(dbm) => attrs.extend([
+            (Nl80211Attr::AttrWiphyTxPowerSetting, FIXED),
+            // level is in mBm
+            (
+                Nl80211Attr::AttrWiphyTxPowerLevel,
+                (dbm * 100.0).round() as u32,
+            ),
+        ]),
+        None => attrs.push((Nl80211Attr::AttrWiphyTxPowerSetting, AUTOMATIC)),
+    }
+    Nl80211::connect()?.send(Nl80211Cmd::CmdSetWiphy, nl80211::attrs(&attrs)?, false)?;
+    Ok(())
+}
+
+/// Asks kernel to send CQM event when signal crosses one of `thresholds` in dBm,
+/// more than one threshold needs driver support
+pub fn set_cqm_rssi(index: u32, thresholds: &[i32], hysteresis: u32) -> io::Result<()> {
+    let list: Vec<u8> = thresholds.iter().flat_map(|t| t.to_ne_bytes()).collect();
+    let mut cqm =
+        Nlattr::new(true, false, Nl80211Attr::AttrCqm, Buffer::new()).map_err(io::Error::other)?;
+    cqm.add_nested_attribute(
+        &Nlattr::new(
+            false,
+            false,
+            Nl80211AttrCqm::AttrCqmRssiThold,
+            Buffer::from(list),
+        )
+        .map_err(io::Error::other)?,
+    )
+    .map_err(io::Error::other)?;
+    cqm.add_nested_attribute(
+        &Nlattr::new(false, false, Nl80211AttrCqm::AttrCqmRssiHyst, hysteresis)
+            .map_err(io::Error::other)?,
+    )
+    .map_err(io::Error::other)?;
+    let mut attrs = nl80211::attrs(&[(Nl80211Attr::AttrIfindex, index)])?;
+    attrs.push(cqm);
+    Nl80211::connect()?.send(Nl80211Cmd::CmdSetCqm, attrs, false)?;
+    Ok(())
+}
+
+/// Asks driver to start scan on interface, results show up in the next BSS dump
+pub fn trigger_scan(index: u32) -> io::Result<()> {
+    Nl80211::connect()?.send(
+        Nl80211Cmd::CmdTriggerScan,
+        nl80211::attrs(&[(Nl80211Attr::AttrIfindex, index)])?,
+        false,
+    )?;
+    Ok(())
+}
+
+/// Parses typed TX power, empty text and `auto` mean automatic
+pub fn parse_tx_power(text: &str) -> Option<Option<f64>> {
+    let text = text.trim().trim_end_matches("dBm").trim();
+    if text.is_empty() || text.eq_ignore_ascii_case("auto") {
+        return Some(None);
+    }
+    let dbm: f64 = text.parse().ok()?;
+    (0.0..=40.0).contains(&dbm).then_some(Some(dbm))
+}
+
+/// Returns true if 802.11 power save is enabled
+pub fn power_save(index: i32) -> io::Result<bool> {
+    let replies = Nl80211::connect()?.send(
+        Nl80211Cmd::CmdGetPowerSave,
+        nl80211::attrs(&[(Nl80211Attr::AttrIfindex, index as u32)])?,
+        false,
+    )?;
+    let state = replies
+        .iter()
+        .find_map(|msg| {
+            msg.get_attr_handle()
+                .get_attribute(Nl80211Attr::AttrPsState)?
+                .get_payload_as::<u32>()
+                .ok()
+        })
+        .ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "no power save state in reply")
+        })?;
+    Ok(state != 0)
+}
+
+pub fn set_power_save(index: i32, enabled: bool) -> io::Result<()> {
+    Nl80211::connect()?.send(
+        Nl80211Cmd::CmdSetPowerSave,
+        nl80211::attrs(&[
+            (Nl80211Attr::AttrIfindex, index as u32),
+            (Nl80211Attr::AttrPsState, u32::from(enabled)),
+        ])?,
+        false,
+    )?;
+    Ok(())
+}
+
+/// Keeps power save state of selected interface, so nl80211 is not asked every frame
+pub struct PowerSaveMonitor {
+    index: Option<i32>,
+    read_at: Option<Instant>,
+    state: Option<bool>,
+}
+
+impl PowerSaveMonitor {
+    pub fn new() -> Self {
+        Self {
+            index: None,
+            read_at: None,
+            state: None,
+        }
+    }
+
+    pub fn update(&mut self, index: Option<i32>) -> Option<bool> {
+        if self.index != index || self.read_at.is_none_or(|t| t.elapsed() >= REFRESH) {
+            self.index = index;
+            self.read_at = Some(Instant::now());
+            self.state = index.and_then(|i| power_save(i).ok());
+        }
+        self.state
+    }
+
+    /// Forces reading of state on the next frame
+    pub fn invalidate(&mut self) {
+        self.read_at = None;
+    }
+}
@@ -0,0 +1,604 @@
+use std::{
+    collections::BTreeMap,
+    io,
+    time::{Duration, Instant},
+};
+
+use neli::{attr::Attribute, genl::Nlattr};
+use neli_wifi::{
+    Nl80211Attr, Nl80211BandAttr, Nl80211Cmd, Nl80211FrequencyAttr, Nl80211RegRuleAttr,
+};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+};
+use tracing::warn;
+
+use crate::{
+    i18n,
+    nl80211::{Attrs, Message, Nl80211},
+};
+
+pub const HINT: &str = "Tab selects next interface, 'm' for monitoring";
+
+/// Regulatory data changes only on country change, no need to ask kernel every frame
+const REFRESH: Duration = Duration::from_secs(5);
+
+/// Rule flags from `enum nl80211_reg_rule_flags`
+const RULE_FLAGS: [(u32, &str); 6] = [
+    (1 << 2, "no-indoor"),
+    (1 << 3, "no-outdoor"),
+    (1 << 4, "DFS"),
+    (1 << 7, "no-IR"),
+    (1 << 11, "auto-bw"),
+    (1 << 1, "no-CCK"),
+];
+
+/// One frequency range of regulatory domain
+#[derive(Clone, Debug, Default)]
+pub struct Rule {
+    /// Frequencies in kHz
+    pub start: u32,
+    pub end: u32,
+    pub max_bandwidth: u32,
+    /// Max EIRP in mBm
+    pub max_eirp: u32,
+    pub flags: u32,
+}
+
+impl Rule {
+    pub fn flag_list(&self) -> String {
+        RULE_FLAGS
+            .iter()
+            .filter(|(bit, _)| self.flags & bit != 0)
+            .map(|(_, name)| *name)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Current regulatory domain of the system
+#[derive(Clone, Debug, Default)]
+pub struct RegDomain {
+    /// Country code like `DE`, `00` is the world domain
+    pub country: String,
+    pub dfs_region: Option<u8>,
+    pub rules: Vec<Rule>,
+}
+
+impl RegDomain {
+    pub fn dfs_region_name(&self) -> &'static str {
+        match self.dfs_region {
+            Some(1) => "FCC",
+            Some(2) => "ETSI",
+            Some(3) => "JP",
+            _ => "unset",
+        }
+    }
+}
+
+/// Channel of wiphy with restrictions applied by regulatory domain
+#[derive(Clone, Debug, Default)]
+pub struct Channel {
+    /// MHz
+    pub frequency: u32,
+    pub disabled: bool,
+    /// Initiating radiation is not allowed, i.e. no active scan and no AP
+    pub no_ir: bool,
+    /// Radar detection is required, i.e. DFS channel
+    pub radar: bool,
+    /// mBm
+    pub max_power: Option<u32>,
+}
+
+/// Channels of one band
+#[derive(Clone, Debug)]
+pub struct Band {
+    pub name: &'static str,
+    pub channels: Vec<Channel>,
+    /// HT and VHT capability fields, present if band supports 802.11n or 802.11ac
+    pub ht_capa: Option<u16>,
+    pub vht_capa: Option<u32>,
+}
+
+/// Asks kernel for global regulatory domain
+pub fn regdomain(nl: &mut Nl80211) -> io::Result<RegDomain> {
+    let replies = nl.send(Nl80211Cmd::CmdGetReg, Attrs::new(), false)?;
+    let msg = replies
+        .first()
+        .ok_or_else(|| io::Error::other("empty regulatory reply"))?;
+    let attrs = msg.get_attr_handle();
+    let country = attrs
+        .get_attribute(Nl80211Attr::AttrRegAlpha2)
+        .map(|a| {
+            String::from_utf8_lossy(a.nla_payload.as_ref())
+                .trim_end_matches('\0')
+                .to_string()
+        })
+        .unwrap_or_default();
+    let dfs_region = attrs
+        .get_attribute(Nl80211Attr::AttrDfsRegion)
+        .and_then(|a| a.get_payload_as().ok());
+    let mut rules = Vec::new();
+    if let Some(list) = attrs.get_attribute(Nl80211Attr::AttrRegRules) {
+        // nested array, types of entries are indices
+        for entry in list
+            .get_attr_handle::<u16>()
+            .map_err(io::Error::other)?
+            .iter()
+        {
+            rules.push(rule(entry).map_err(io::Error::other)?);
+        }
+    }
+    Ok(RegDomain {
+        country,
+        dfs_region,
+        rules,
+    })
+}
+
+fn rule(entry: &Nlattr<u16, neli::types::Buffer>) -> Result<Rule, neli::err::DeError> {
+    let mut rule = Rule::default();
+    for attr in entry.get_attr_handle::<Nl80211RegRuleAttr>()?.iter() {
+        let value = attr.get_payload_as::<u32>().unwrap_or_default();
+        match attr.nla_type.nla_type {
+            Nl80211RegRuleAttr::AttrRegRuleFlags => rule.flags = value,
+            Nl80211RegRuleAttr::AttrFreqRangeStart => rule.start = value,
+            Nl80211RegRuleAttr::AttrFreqRangeEnd => rule.end = value,
+            Nl80211RegRuleAttr::AttrFreqRangeMaxBw => rule.max_bandwidth = value,
+            Nl80211RegRuleAttr::AttrPowerRuleMaxEirp => rule.max_eirp = value,
+            _ => {}
+        }
+    }
+    Ok(rule)
+}
+
+/// Channels of wiphy per band
+pub fn channels(nl: &mut Nl80211, phy: u32) -> io::Result<Vec<Band>> {
+    bands(&nl.wiphy(phy)?)
+}
+
+/// Collects bands from messages of wiphy dump, one band can be spread over several messages
+pub fn bands(replies: &[Message]) -> io::Result<Vec<Band>> {
+    let mut bands: BTreeMap<u16, Band> = BTreeMap::new();
+    for msg in replies {
+        let handle = msg.get_attr_handle();
+        let Some(list) = handle.get_attribute(Nl80211Attr::AttrWiphyBands) else {
+            continue;
+        };
+        for entry in list
+            .get_attr_handle::<u16>()
+            .map_err(io::Error::other)?
+            .iter()
+        {
+            let index = entry.nla_type.nla_type;
+            let band = bands.entry(index).or_insert_with(|| Band {
+                name: band_name(index),
+                channels: Vec::new(),
+                ht_capa: None,
+                vht_capa: None,
+            });
+            let band_attrs = entry
+                .get_attr_handle::<Nl80211BandAttr>()
+                .map_err(io::Error::other)?;
+            if let Some(capa) = band_attrs.get_attribute(Nl80211BandAttr::BandAttrHtCapa) {
+                band.ht_capa = capa.get_payload_as().ok();
+            }
+            if let Some(capa) = band_attrs.get_attribute(Nl80211BandAttr::BandAttrVhtCapa) {
+                band.vht_capa = capa.get_payload_as().ok();
+            }
+            let Some(freqs) = band_attrs.get_attribute(Nl80211BandAttr::BandAttrFreqs) else {
+                continue;
+            };
+            for freq in freqs
+                .get_attr_handle::<u16>()
+                .map_err(io::Error::other)?
+                .iter()
+            {
+                band.channels.push(channel(freq).map_err(io::Error::other)?);
+            }
+        }
+    }
+    Ok(bands.into_values().collect())
+}
+
+fn channel(entry: &Nlattr<u16, neli::types::Buffer>) -> Result<Channel, neli::err::DeError> {
+    let mut channel = Channel::default();
+    for attr in entry.get_attr_handle::<Nl80211FrequencyAttr>()?.iter() {
+        match attr.nla_type.nla_type {
+            Nl80211FrequencyAttr::FrequencyAttrFreq => {
+                channel.frequency = attr.get_payload_as().unwrap_or_default()
+            }
+            Nl80211FrequencyAttr::FrequencyAttrDisabled => channel.disabled = true,
+            Nl80211FrequencyAttr::FrequencyAttrNoIr => channel.no_ir = true,
+            Nl80211FrequencyAttr::FrequencyAttrRadar => channel.radar = true,
+            Nl80211FrequencyAttr::FrequencyAttrMaxTxPower => {
+                channel.max_power = attr.get_payload_as().ok()
+            }
+            _ => {}
+        }
+    }
+    Ok(channel)
+}
+
+/// Names of `enum nl80211_band`
+fn band_name(index: u16) -> &'static str {
+    match index {
+        0 => "2.4 GHz",
+        1 => "5 GHz",
+        2 => "60 GHz",
+        3 => "6 GHz",
+        4 => "900 MHz",
+        _ => "other",
+    }
+}
+
+/// Channel number of frequency in MHz
+pub fn channel_number(frequency: u32) -> Option<u32> {
+    match frequency {
+        2484 => Some(14),
+        2412..=2472 => Some((frequency - 2407) / 5),
+        5935 => Some(2),
+        5955..=7115 => Some((frequency - 5950) / 5),
+        4910..=4980 => Some((frequency - 4000) / 5),
+        5000..=5925 => Some((frequency - 5000) / 5),
+        58320..=70200 => Some((frequency - 56160) / 2160),
+        _ => None,
+    }
+}
+
+/// true for 5 GHz channels 52-144 where most regulatory domains require DFS
+pub fn is_dfs(frequency: u32) -> bool {
+    (5250..=5730).contains(&frequency)
+}
+
+/// Warning when country advertised by AP differs from local regulatory domain
+pub fn conflict(local: &str, ap: &str) -> Option<String> {
+    match local {
+        _ if local == ap => None,
+        "00" => Some(format!(
+            "local domain is world (00), setting country {} may enable more channels",
+            ap
+        )),
+        // 98 and 99 are intersections built from AP hints, they follow AP
+        "98" | "99" => None,
+        _ => Some(format!(
+            "AP advertises {} but local domain is {}, some channels may be unusable",
+            ap, local
+        )),
+    }
+}
+
+/// Global country code, refreshed not more often than `REFRESH`
+pub struct CountryMonitor {
+    nl: Option<Nl80211>,
+    updated: Option<Instant>,
+    country: Option<String>,
+}
+
+impl CountryMonitor {
+    pub fn new() -> Self {
+        Self {
+            nl: None,
+            updated: None,
+            country: None,
+        }
+    }
+
+    pub fn update(&mut self) -> Option<&str> {
+        if self.updated.is_none_or(|t| t.elapsed() >= REFRESH) {
+            self.updated = Some(Instant::now());
+            if self.nl.is_none() {
+                self.nl = Nl80211::connect()
+                    .inspect_err(|e| warn!(error = %e, "failed to open nl80211 socket"))
+                    .ok();
+            }
+            if let Some(nl) = self.nl.as_mut() {
+                match regdomain(nl) {
+                    Ok(domain) => self.country = Some(domain.country),
+                    Err(e) => {
+                        warn!(error = %e, "failed to query regulatory domain");
+                        self.country = None;
+                        self.nl = None;
+                    }
+                }
+            }
+        }
+        self.country.as_deref()
+    }
+}
+
+/// Cached channel list of one wiphy
+pub struct ChannelMonitor {
+    nl: Option<Nl80211>,
+    phy: Option<u32>,
+    updated: Option<Instant>,
+    bands: Vec<Band>,
+}
+
+impl ChannelMonitor {
+    pub fn new() -> Self {
+        Self {
+            nl: None,
+            phy: None,
+            updated: None,
+            bands: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, phy: Option<u32>) {
+        if self.phy == phy && self.updated.is_some_and(|t| t.elapsed() < REFRESH) {
+            return;
+        }
+        self.phy = phy;
+        self.updated = Some(Instant::now());
+        self.bands.clear();
+        let Some(phy) = phy else {
+            return;
+        };
+        if self.nl.is_none() {
+            self.nl = Nl80211::connect()
+                .inspect_err(|e| warn!(error = %e, "failed to open nl80211 socket"))
+                .ok();
+        }
+        let Some(nl) = self.nl.as_mut() else {
+            return;
+        };
+        match channels(nl, phy) {
+            Ok(bands) => self.bands = bands,
+            Err(e) => {
+                warn!(error = %e, phy, "failed to dump channels");
+                self.nl = None;
+            }
+        }
+    }
+
+    /// Whether local regulatory data requires radar detection on frequency, `None` if channel is unknown
+    pub fn radar(&self, frequency: u32) -> Option<bool> {
+        self.bands
+            .iter()
+            .flat_map(|b| &b.channels)
+            .find(|c| c.frequency == frequency)
+            .map(|c| c.radar)
+    }
+}
+
+/// Regulatory view state which lives between frames
+pub struct RegdomainView {
+    nl: Option<Nl80211>,
+    phy: Option<u32>,
+    updated: Option<Instant>,
+    domain: Option<RegDomain>,
+    bands: Vec<Band>,
+    error: Option<String>,
+}
+
+impl RegdomainView {
+    pub fn new() -> Self {
+        Self {
+            nl: None,
+            phy: None,
+            updated: None,
+            domain: None,
+            bands: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Refreshes regulatory domain and channels of wiphy when they are too old or wiphy changed
+    pub fn update(&mut self, phy: Option<u32>) {
+        if self.phy == phy && self.updated.is_some_and(|t| t.elapsed() < REFRESH) {
+            return;
+        }
+        self.phy = phy;
+        self.updated = Some(Instant::now());
+        if self.nl.is_none() {
+            self.nl = Nl80211::connect()
+                .inspect_err(|e| warn!(error = %e, "failed to open nl80211 socket"))
+                .ok();
+        }
+        let Some(nl) = self.nl.as_mut() else {
+            self.error = Some("nl80211 is not available".to_string());
+            return;
+        };
+        let res = regdomain(nl).and_then(|domain| {
+            let bands = match phy {
+                Some(phy) => channels(nl, phy)?,
+                None => Vec::new(),
+            };
+            Ok((domain, bands))
+        });
+        match res {
+            Ok((domain, bands)) => {
+                self.domain = Some(domain);
+                self.bands = bands;
+                self.error = None;
+            }
+            Err(e) => {
+                warn!(error = %e, "failed to query regulatory domain");
+                self.error = Some(e.to_string());
+                self.nl = None;
+            }
+        }
+    }
+
+    /// `ap_country` is advertised by BSS interface is connected to
+    pub fn draw(&self, f: &mut Frame, area: Rect, iface: &str, ap_country: Option<&str>) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(5)])
+            .split(area);
+        let mut warning = None;
+        let summary = match (&self.error, &self.domain) {
+            (Some(e), _) => e.clone(),
+            (None, Some(d)) => {
+                let mut summary =
+                    format!("country {}, DFS region {}", d.country, d.dfs_region_name());
+                if let Some(ap) = ap_country {
+                    summary.push_str(&format!(", AP advertises {}", ap));
+                    warning = conflict(&d.country, ap);
+                }
+                summary
+            }
+            (None, None) => "unknown".to_string(),
+        };
+        let summary = match warning {
+            Some(w) => Line::from(vec![
+                Span::raw(summary),
+                Span::styled(format!(", {}", w), Style::default().fg(Color::Yellow)),
+            ]),
+            None => Line::from(summary),
+        };
+        f.render_widget(
+            Paragraph::new(summary).block(
+                Block::default()
+                    .title(i18n::tr("regulatory domain"))
+                    .borders(Borders::ALL),
+            ),
+            chunks[0],
+        );
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+        let header_style = Style::default().add_modifier(Modifier::ITALIC);
+        let rules = self.domain.iter().flat_map(|d| &d.rules).map(|r| {
+            Row::new(vec![
+                format!("{}-{}", r.start / 1000, r.end / 1000),
+                format!("{}", r.max_bandwidth / 1000),
+                format!("{}", r.max_eirp / 100),
+                r.flag_list(),
+            ])
+        });
+        let rules = Table::new(
+            rules,
+            [
+                Constraint::Percentage(30),
+                Constraint::Percentage(15),
+                Constraint::Percentage(15),
+                Constraint::Percentage(40),
+            ],
+        )
+        .header(
+            Row::new(vec!["MHz", i18n::tr("width"), "dBm", i18n::tr("flags")]).style(header_style),
+        )
+        .block(
+            Block::default()
+                .title(i18n::tr("rules"))
+                .borders(Borders::ALL),
+        );
+        f.render_widget(rules, columns[0]);
+
+        let channels = self.bands.iter().flat_map(|band| {
+            band.channels.iter().map(|c| {
+                let mut flags = Vec::new();
+                if c.disabled {
+                    flags.push("disabled");
+                }
+                if c.no_ir {
+                    flags.push("no-IR");
+                }
+                if c.radar {
+                    flags.push("radar");
+                }
+                let style = if c.disabled {
+                    Style::default().fg(Color::DarkGray)
+                } else if c.radar {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                };
+                Row::new(vec![
+                    Cell::from(band.name),
+                    Cell::from(
+                        channel_number(c.frequency).map_or("-".to_string(), |n| n.to_string()),
+                    ),
+                    Cell::from(c.frequency.to_string()),
+                    Cell::from(
+                        c.max_power
+                            .map_or("-".to_string(), |p| (p / 100).to_string()),
+                    ),
+                    Cell::from(flags.join(" ")),
+                ])
+                .style(style)
+            })
+        });
+        let channels = Table::new(
+            channels,
+            [
+                Constraint::Percentage(20),
+                Constraint::Percentage(10),
+                Constraint::Percentage(15),
+                Constraint::Percentage(15),
+                Constraint::Percentage(40),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                i18n::tr("band"),
+                i18n::tr("ch"),
+                "MHz",
+                "dBm",
+                i18n::tr("flags"),
+            ])
+            .style(header_style),
+        )
+        .block(
+            Block::default()
+                .title(i18n::fill(i18n::tr("channels of {}"), &[iface]))
+                .borders(Borders::ALL),
+        );
+        f.render_widget(channels, columns[1]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flag_list_names_known_bits() {
+        let rule = Rule {
+            flags: (1 << 4) | (1 << 7) | (1 << 30),
+            ..Rule::default()
+        };
+        assert_eq!(rule.flag_list(), "DFS no-IR");
+        assert_eq!(Rule::default().flag_list(), "");
+    }
+
+    #[test]
+    fn dfs_region_names() {
+        let domain = |dfs_region| RegDomain {
+            dfs_region,
+            ..RegDomain::default()
+        };
+        assert_eq!(domain(Some(2)).dfs_region_name(), "ETSI");
+        assert_eq!(domain(Some(9)).dfs_region_name(), "unset");
+        assert_eq!(domain(None).dfs_region_name(), "unset");
+    }
+
+    #[test]
+    fn channel_numbers_of_every_band() {
+        assert_eq!(channel_number(2412), Some(1));
+        assert_eq!(channel_number(2484), Some(14));
+        assert_eq!(channel_number(5180), Some(36));
+        assert_eq!(channel_number(5935), Some(2));
+        assert_eq!(channel_number(5955), Some(1));
+        assert_eq!(channel_number(60480), Some(2));
+        assert_eq!(channel_number(3000), None);
+        assert!(is_dfs(5260));
+        assert!(!is_dfs(5180));
+    }
+
+    #[test]
+    fn conflict_ignores_same_and_intersected_domains() {
+        assert_eq!(conflict("DE", "DE"), None);
+        assert_eq!(conflict("98", "DE"), None);
+        assert!(conflict("00", "DE").is_some_and(|w| w.contains("world")));
+        assert!(conflict("US", "DE").is_some_and(|w| w.contains("AP advertises DE")));
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    io::{self, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    path::Path,
+    sync::{Arc, RwLock},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Row, Table},
+};
+use tracing::{debug, info};
+
+use crate::{api, display, get_security_info, i18n, sampler::Sample};
+
+pub const HINT: &str = "Agents are daemons started with --http, 'm' for monitoring";
+
+/// Connect and read timeout of one agent, slow agent must not delay the others too much
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Latest state of one agent
+#[derive(Clone, Debug)]
+pub struct Agent {
+    /// `host:port` as given on command line
+    pub addr: String,
+    pub samples: Vec<Sample>,
+    pub error: Option<String>,
+    pub updated: Option<Instant>,
+}
+
+/// Starts thread which polls `/status` of every agent each `interval` seconds,
+/// `token` is sent to agents which need one
+pub fn spawn(addrs: &[String], interval: u64, token: Option<String>) -> Arc<RwLock<Vec<Agent>>> {
+    let agents = Arc::new(RwLock::new(
+        addrs
+            .iter()
+            .map(|addr| Agent {
+                addr: addr.clone(),
+                samples: Vec::new(),
+                error: Some("connecting…".to_string()),
+                updated: None,
+            })
+            .collect::<Vec<_>>(),
+    ));
+    if addrs.is_empty() {
+        return agents;
+    }
+    info!(?addrs, "polling remote agents");
+    let shared = agents.clone();
+    let addrs = addrs.to_vec();
+    std::thread::spawn(move || {
+        loop {
+            for (i, addr) in addrs.iter().enumerate() {
+                let res = fetch(addr, token.as_deref());
+                let mut agents = shared.write().unwrap();
+                let agent = &mut agents[i];
+                match res {
+                    Ok(samples) => {
+                        agent.samples = samples;
+                        agent.error = None;
+                        agent.updated = Some(Instant::now());
+                    }
+                    Err(e) => {
+                        debug!(error = %e, addr, "agent is not available");
+                        agent.error = Some(e.to_string());
+                    }
+                }
+            }
+            sleep(Duration::from_secs(interval));
+        }
+    });
+    agents
+}
+
+/// Requests `/status` of agent, or `status` of local daemon if address is its socket path,
+/// and parses its samples
+fn fetch(addr: &str, token: Option<&str>) -> io::Result<Vec<Sample>> {
+    if addr.starts_with('/') {
+        let reply = api::query(Path::new(addr), r#"{"cmd":"status"}"#, TIMEOUT)?;
+        let value: serde_json::Value = serde_json::from_str(&reply).map_err(io::Error::other)?;
+        return Ok(samples(&value));
+    }
+    let socket = addr
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "address not resolved"))?;
+    let mut stream = TcpStream::connect_timeout(&socket, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    let authorization = token.map_or(String::new(), |t| {
+        format!("Authorization: Bearer {}\r\n", t)
+    });
+    write!(
+        stream,
+        "GET /status HTTP/1.0\r\nHost: {}\r\n{}Connection: close\r\n\r\n",
+        addr, authorization
+    )?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "not an http response"))?;
+    if !head.starts_with("HTTP/1.1 200") {
+        return Err(io::Error::other(
+            head.lines().next().unwrap_or_default().to_string(),
+        ));
+    }
+    let value: serde_json::Value = serde_json::from_str(body).map_err(io::Error::other)?;
+    Ok(samples(&value))
+}
+
+fn samples(value: &serde_json::Value) -> Vec<Sample> {
+    value["samples"]
+        .as_array()
+        .map(|a| a.iter().filter_map(Sample::from_json).collect())
+        .unwrap_or_default()
+}
+
+/// One row per remote interface, agents without samples show their error
+pub fn draw(f: &mut Frame, area: Rect, agents: &[Agent], hide_info: bool) {
+    let mut rows = Vec::new();
+    for agent in agents {
+        if let Some(e) = &agent.error {
+            warn_row(&mut rows, agent, e);
+            continue;
+        }
+        if agent.samples.is_empty() {
+            warn_row(&mut rows, agent, "no interfaces");
+        }
+        for s in &agent.samples {
+            let style = if s.connected {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            rows.push(
+                Row::new(vec![
+                    Span::raw(agent.addr.clone()),
+                    Span::raw(s.iface.clone()),
+                    Span::raw(get_security_info(
+                        s.bssid.as_deref().unwrap_or("-"),
+                        hide_info,
+                    )),
+                    Span::styled(
+                        display::signal_value(s.signal).to_string(),
+                        Style::default().fg(display::signal_color(s.signal)),
+                    ),
+                    Span::raw(s.frequency.map(|f| f.to_string()).unwrap_or_default()),
+                    Span::raw(
+                        agent
+                            .updated
+                            .map(|t| format!("{}s ago", t.elapsed().as_secs()))
+                            .unwrap_or_default(),
+                    ),
+                ])
+                .style(style),
+            );
+        }
+    }
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(25),
+            Constraint::Percentage(15),
+            Constraint::Percentage(25),
+            Constraint::Percentage(10),
+            Constraint::Percentage(10),
+            Constraint::Percentage(15),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            i18n::tr("agent").to_string(),
+            i18n::tr("iface").to_string(),
+            "BSSID".to_string(),
+            display::unit().to_string(),
+            "MHz".to_string(),
+            i18n::tr("updated").to_string(),
+        ])
+        .style(Style::default().add_modifier(Modifier::ITALIC)),
+    )
+    .block(
+        Block::default()
+            .title(i18n::tr("remote agents"))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(table, area);
+}
+
+fn warn_row(rows: &mut Vec<Row<'_>>, agent: &Agent, text: &str) {
+    rows.push(Row::new(vec![
+        Span::raw(agent.addr.clone()),
+        Span::styled(text.to_string(), Style::default().fg(Color::Yellow)),
+    ]));
+}
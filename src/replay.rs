@@ -0,0 +1,158 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use macaddr::MacAddr6;
+use neli_wifi::{Bss, Interface};
+use serde_json::{Value, json};
+use tracing::info;
+
+use crate::sampler;
+
+/// Answer of `sampler::devices` for one frame
+pub type Devices = Vec<(Interface, Vec<Bss>)>;
+
+/// Netlink answers recorded with `--record`, one frame per line, played back by `--replay`
+pub struct Replay {
+    frames: Vec<Devices>,
+    position: usize,
+}
+
+impl Replay {
+    /// Reads fixture, every line is JSON array of interfaces with their BSS lists
+    pub fn load(path: &Path) -> io::Result<Replay> {
+        let mut frames = Vec::new();
+        for (i, line) in fs::read_to_string(path)?.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let invalid = |what: &str| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}:{}: {}", path.display(), i + 1, what),
+                )
+            };
+            let value: Value = serde_json::from_str(line).map_err(|e| invalid(&e.to_string()))?;
+            let frame = value
+                .as_array()
+                .ok_or_else(|| invalid("frame is not an array"))?
+                .iter()
+                .map(device)
+                .collect::<Option<Devices>>()
+                .ok_or_else(|| invalid("broken interface"))?;
+            frames.push(frame);
+        }
+        if frames.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} has no frames", path.display()),
+            ));
+        }
+        info!(path = %path.display(), frames = frames.len(), "replay loaded");
+        Ok(Replay {
+            frames,
+            position: 0,
+        })
+    }
+
+    /// Devices of current frame
+    pub fn devices(&self) -> Devices {
+        self.frames[self.position].clone()
+    }
+
+    /// Moves to next frame, the last one stays forever
+    pub fn advance(&mut self) {
+        self.position = (self.position + 1).min(self.frames.len() - 1);
+    }
+}
+
+/// Appends one frame to fixture
+pub fn append(path: &Path, devices: &[(Interface, Vec<Bss>)]) -> io::Result<()> {
+    let frame: Vec<Value> = devices
+        .iter()
+        .map(|(interface, bss_list)| {
+            json!({
+                "index": interface.index,
+                "name": sampler::iface_name(interface),
+                "ssid": interface.ssid.as_deref().map(String::from_utf8_lossy),
+                "mac": interface.mac.as_deref().and_then(mac),
+                "frequency": interface.frequency,
+                "channel": interface.channel,
+                "power": interface.power,
+                "phy": interface.phy,
+                "device": interface.device,
+                "bss": bss_list.iter().map(bss_json).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", Value::Array(frame))
+}
+
+fn bss_json(bss: &Bss) -> Value {
+    json!({
+        "bssid": sampler::format_bssid(bss),
+        "frequency": bss.frequency,
+        "beacon_interval": bss.beacon_interval,
+        "seen_ms_ago": bss.seen_ms_ago,
+        "status": bss.status,
+        "signal": bss.signal,
+        "ies": bss.information_elements.as_deref().map(|ies| STANDARD.encode(ies)),
+    })
+}
+
+fn mac(bytes: &[u8]) -> Option<String> {
+    let addr: [u8; 6] = bytes.try_into().ok()?;
+    Some(MacAddr6::from(addr).to_string())
+}
+
+fn parse_mac(value: &Value) -> Option<Option<Vec<u8>>> {
+    match value.as_str() {
+        Some(text) => Some(Some(text.parse::<MacAddr6>().ok()?.as_bytes().to_vec())),
+        None => Some(None),
+    }
+}
+
+/// Reads optional number, None inside if it is missing and None outside if it is broken
+fn number<T: TryFrom<i64>>(value: &Value) -> Option<Option<T>> {
+    match value {
+        Value::Null => Some(None),
+        value => Some(Some(T::try_from(value.as_i64()?).ok()?)),
+    }
+}
+
+fn device(value: &Value) -> Option<(Interface, Vec<Bss>)> {
+    let mut interface = Interface::default();
+    interface.index = number(&value["index"])?;
+    interface.name = Some(value["name"].as_str()?.as_bytes().to_vec());
+    interface.ssid = value["ssid"].as_str().map(|s| s.as_bytes().to_vec());
+    interface.mac = parse_mac(&value["mac"])?;
+    interface.frequency = number(&value["frequency"])?;
+    interface.channel = number(&value["channel"])?;
+    interface.power = number(&value["power"])?;
+    interface.phy = number(&value["phy"])?;
+    interface.device = number(&value["device"])?;
+    let bss_list = match &value["bss"] {
+        Value::Null => Vec::new(),
+        list => list.as_array()?.iter().map(bss).collect::<Option<_>>()?,
+    };
+    Some((interface, bss_list))
+}
+
+fn bss(value: &Value) -> Option<Bss> {
+    let mut bss = Bss::default();
+    bss.bssid = parse_mac(&value["bssid"])?;
+    bss.frequency = number(&value["frequency"])?;
+    bss.beacon_interval = number(&value["beacon_interval"])?;
+    bss.seen_ms_ago = number(&value["seen_ms_ago"])?;
+    bss.status = number(&value["status"])?;
+    bss.signal = number(&value["signal"])?;
+    bss.information_elements = match value["ies"].as_str() {
+        Some(ies) => Some(STANDARD.decode(ies).ok()?),
+        None => None,
+    };
+    Some(bss)
+}
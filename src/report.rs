@@ -0,0 +1,355 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Local};
+use serde::Deserialize;
+
+use crate::{
+    events::{self, Event, Kind},
+    notes::Note,
+    sampler::Sample,
+    scan::ScanRow,
+};
+
+/// Levels of one-line ASCII chart from the weakest signal
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    #[default]
+    Markdown,
+    /// Single file with inline chart, no external resources
+    Html,
+}
+
+impl ReportFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+        }
+    }
+}
+
+/// `[report]` section of config
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ReportConfig {
+    /// Writes report when TUI quits, 'R' in monitoring writes it any time
+    pub on_exit: bool,
+    pub format: ReportFormat,
+}
+
+/// Signal statistics of one interface while it was connected
+#[derive(Clone, Debug, Default)]
+struct Stats {
+    min: i32,
+    max: i32,
+    sum: i64,
+    count: u64,
+    disconnects: u32,
+    connected: bool,
+}
+
+impl Stats {
+    fn average(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum as f64 / self.count as f64)
+    }
+}
+
+/// Everything seen since TUI started which ends up in report
+pub struct Session {
+    started: DateTime<Local>,
+    stats: BTreeMap<String, Stats>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self {
+            started: Local::now(),
+            stats: BTreeMap::new(),
+        }
+    }
+
+    pub fn observe(&mut self, sample: &Sample) {
+        let stats = self.stats.entry(sample.iface.clone()).or_default();
+        if stats.connected && !sample.connected {
+            stats.disconnects += 1;
+        }
+        stats.connected = sample.connected;
+        if !sample.connected {
+            return;
+        }
+        if stats.count == 0 {
+            stats.min = sample.signal;
+            stats.max = sample.signal;
+        }
+        stats.min = stats.min.min(sample.signal);
+        stats.max = stats.max.max(sample.signal);
+        stats.sum += i64::from(sample.signal);
+        stats.count += 1;
+    }
+}
+
+/// Snapshot of session taken when report is requested
+pub struct Report<'a> {
+    pub session: &'a Session,
+    /// Interface shown in signal chart
+    pub chart_iface: &'a str,
+    /// Chart points in dBm, the oldest first
+    pub chart: Vec<i32>,
+    /// Scan results of every interface
+    pub scan: Vec<(String, Vec<ScanRow>)>,
+    /// Notes of the whole history, only those of this session are shown
+    pub notes: Vec<Note>,
+}
+
+impl Report<'_> {
+    /// Roam, alert and error events of this session, the oldest first
+    fn events(&self) -> Vec<Event> {
+        let mut events: Vec<Event> = events::recent()
+            .into_iter()
+            .filter(|e| e.time >= self.session.started)
+            .filter(|e| matches!(e.kind, Kind::Roam | Kind::Alert | Kind::Error))
+            .collect();
+        events.reverse();
+        events
+    }
+
+    fn notes(&self) -> impl Iterator<Item = &Note> {
+        self.notes.iter().filter(|n| n.time >= self.session.started)
+    }
+
+    fn roams(&self) -> usize {
+        self.events()
+            .iter()
+            .filter(|e| e.kind == Kind::Roam && e.message.contains(" roamed "))
+            .count()
+    }
+
+    pub fn markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# Wi-Fi session report\n");
+        let _ = writeln!(
+            out,
+            "{} – {}, {} roams\n",
+            self.session.started.format("%Y-%m-%d %H:%M:%S"),
+            Local::now().format("%H:%M:%S"),
+            self.roams()
+        );
+        let _ = writeln!(out, "## Signal of {}\n", self.chart_iface);
+        if let (Some(min), Some(max)) = (self.chart.iter().min(), self.chart.iter().max()) {
+            let _ = writeln!(out, "```\n{}\n```", sparkline(&self.chart));
+            let _ = writeln!(out, "{} to {} dBm\n", min, max);
+        } else {
+            let _ = writeln!(out, "no samples\n");
+        }
+        let _ = writeln!(out, "## Statistics\n");
+        let _ = writeln!(
+            out,
+            "| iface | samples | min dBm | avg dBm | max dBm | disconnects |\n|---|---|---|---|---|---|"
+        );
+        for (iface, s) in &self.session.stats {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} | {} |",
+                iface,
+                s.count,
+                if s.count > 0 {
+                    s.min.to_string()
+                } else {
+                    "-".to_string()
+                },
+                s.average().map_or("-".to_string(), |a| format!("{:.1}", a)),
+                if s.count > 0 {
+                    s.max.to_string()
+                } else {
+                    "-".to_string()
+                },
+                s.disconnects
+            );
+        }
+        let _ = writeln!(out, "\n## Events\n");
+        let events = self.events();
+        if events.is_empty() {
+            let _ = writeln!(out, "none");
+        }
+        for e in events {
+            let _ = writeln!(
+                out,
+                "- {} {}: {}",
+                e.time.format("%H:%M:%S"),
+                e.kind,
+                e.message
+            );
+        }
+        if self.notes().next().is_some() {
+            let _ = writeln!(out, "\n## Notes\n");
+        }
+        for n in self.notes() {
+            let _ = writeln!(out, "- {} {}", n.time.format("%H:%M:%S"), n.text);
+        }
+        for (iface, rows) in &self.scan {
+            let _ = writeln!(out, "\n## Scan of {}\n", iface);
+            let _ = writeln!(
+                out,
+                "| SSID | BSSID | dBm | MHz | security |\n|---|---|---|---|---|"
+            );
+            for r in rows {
+                let ssid = r.ssid.as_deref().unwrap_or("hidden").replace('|', "\\|");
+                let _ = writeln!(
+                    out,
+                    "| {}{} | {} | {} | {} | {} |",
+                    ssid,
+                    if r.connected { " (connected)" } else { "" },
+                    r.bssid,
+                    r.signal,
+                    r.frequency.map(|f| f.to_string()).unwrap_or_default(),
+                    r.security
+                );
+            }
+        }
+        out
+    }
+
+    pub fn html(&self) -> String {
+        let mut out = String::new();
+        out.push_str("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Wi-Fi session report</title>\n<style>body{font-family:sans-serif;margin:2em}table{border-collapse:collapse}td,th{border:1px solid #ccc;padding:2px 8px}polyline{fill:none;stroke:#2a2;stroke-width:1.5}</style>\n</head><body>\n");
+        let _ = writeln!(
+            out,
+            "<h1>Wi-Fi session report</h1>\n<p>{} – {}, {} roams</p>",
+            self.session.started.format("%Y-%m-%d %H:%M:%S"),
+            Local::now().format("%H:%M:%S"),
+            self.roams()
+        );
+        let _ = writeln!(out, "<h2>Signal of {}</h2>", escape(self.chart_iface));
+        if self.chart.is_empty() {
+            out.push_str("<p>no samples</p>\n");
+        } else {
+            // y is 0 for 0 dBm and 100 for -100 dBm
+            let points: Vec<String> = self
+                .chart
+                .iter()
+                .enumerate()
+                .map(|(i, s)| format!("{},{}", i * 3, -(*s).clamp(-100, 0)))
+                .collect();
+            let _ = writeln!(
+                out,
+                "<svg width=\"{}\" height=\"200\" viewBox=\"0 0 {} 100\" preserveAspectRatio=\"none\"><polyline points=\"{}\"/></svg>",
+                self.chart.len().max(2) * 3,
+                self.chart.len().max(2) * 3,
+                points.join(" ")
+            );
+        }
+        out.push_str("<h2>Statistics</h2>\n<table><tr><th>iface</th><th>samples</th><th>min dBm</th><th>avg dBm</th><th>max dBm</th><th>disconnects</th></tr>\n");
+        for (iface, s) in &self.session.stats {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(iface),
+                s.count,
+                if s.count > 0 {
+                    s.min.to_string()
+                } else {
+                    "-".to_string()
+                },
+                s.average().map_or("-".to_string(), |a| format!("{:.1}", a)),
+                if s.count > 0 {
+                    s.max.to_string()
+                } else {
+                    "-".to_string()
+                },
+                s.disconnects
+            );
+        }
+        out.push_str("</table>\n<h2>Events</h2>\n<ul>\n");
+        for e in self.events() {
+            let _ = writeln!(
+                out,
+                "<li>{} {}: {}</li>",
+                e.time.format("%H:%M:%S"),
+                e.kind,
+                escape(&e.message)
+            );
+        }
+        out.push_str("</ul>\n");
+        if self.notes().next().is_some() {
+            out.push_str("<h2>Notes</h2>\n<ul>\n");
+            for n in self.notes() {
+                let _ = writeln!(
+                    out,
+                    "<li>{} {}</li>",
+                    n.time.format("%H:%M:%S"),
+                    escape(&n.text)
+                );
+            }
+            out.push_str("</ul>\n");
+        }
+        for (iface, rows) in &self.scan {
+            let _ = writeln!(
+                out,
+                "<h2>Scan of {}</h2>\n<table><tr><th>SSID</th><th>BSSID</th><th>dBm</th><th>MHz</th><th>security</th></tr>",
+                escape(iface)
+            );
+            for r in rows {
+                let _ = writeln!(
+                    out,
+                    "<tr{}><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    if r.connected {
+                        " style=\"font-weight:bold\""
+                    } else {
+                        ""
+                    },
+                    escape(r.ssid.as_deref().unwrap_or("hidden")),
+                    r.bssid,
+                    r.signal,
+                    r.frequency.map(|f| f.to_string()).unwrap_or_default(),
+                    r.security
+                );
+            }
+            out.push_str("</table>\n");
+        }
+        out.push_str("</body></html>\n");
+        out
+    }
+
+    /// Writes `report-<time>.<ext>` into `dir`, `redact` is applied to whole text
+    pub fn write(
+        &self,
+        dir: &Path,
+        format: ReportFormat,
+        redact: impl Fn(&str) -> String,
+    ) -> io::Result<PathBuf> {
+        let text = match format {
+            ReportFormat::Markdown => self.markdown(),
+            ReportFormat::Html => self.html(),
+        };
+        let path = dir.join(format!(
+            "report-{}.{}",
+            Local::now().format("%Y%m%d-%H%M%S"),
+            format.extension()
+        ));
+        fs::write(&path, redact(&text))?;
+        Ok(path)
+    }
+}
+
+/// Chart in one line, -100 dBm is the lowest bar and 0 dBm the highest
+fn sparkline(points: &[i32]) -> String {
+    points
+        .iter()
+        .map(|s| BARS[(((*s).clamp(-100, 0) + 100) as usize * (BARS.len() - 1)) / 100])
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
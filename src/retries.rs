@@ -0,0 +1,81 @@
+use std::time::{Duration, Instant};
+
+use neli_wifi::Station;
+use ratatui::{
+    style::{Color, Style},
+    widgets::{Block, Borders, Sparkline},
+};
+
+use crate::i18n;
+
+/// Amount of points kept for chart, same as signal chart so both cover the same time
+const CAPACITY: usize = 300;
+
+/// TX retries and failures of connected station per interval
+pub struct RetryChart {
+    last: Option<(Instant, u32, u32)>,
+    retries: Vec<u64>,
+    failed: Vec<u64>,
+}
+
+impl RetryChart {
+    pub fn new() -> Self {
+        Self {
+            last: None,
+            retries: Vec::with_capacity(CAPACITY),
+            failed: Vec::with_capacity(CAPACITY),
+        }
+    }
+
+    /// Adds counter deltas since previous call, but not more often than once per `interval`
+    pub fn push(&mut self, station: &Station, interval: Duration) {
+        if self.last.is_some_and(|(t, _, _)| t.elapsed() < interval) {
+            return;
+        }
+        let (Some(retries), Some(failed)) = (station.tx_retries, station.tx_failed) else {
+            self.last = None;
+            return;
+        };
+        if let Some((_, last_retries, last_failed)) = self.last {
+            // counters start again from zero after reassociation
+            push(&mut self.retries, retries.saturating_sub(last_retries));
+            push(&mut self.failed, failed.saturating_sub(last_failed));
+        }
+        self.last = Some((Instant::now(), retries, failed));
+    }
+
+    /// Removes all points together with signal chart
+    pub fn clear(&mut self) {
+        self.last = None;
+        self.retries.clear();
+        self.failed.clear();
+    }
+
+    /// Returns retry and failure sparklines with the newest deltas which fit into `width`
+    pub fn widgets(&self, width: u16) -> (Sparkline<'_>, Sparkline<'_>) {
+        (
+            sparkline("tx retries", &self.retries, width, Color::Yellow),
+            sparkline("tx failed", &self.failed, width, Color::Red),
+        )
+    }
+}
+
+fn push(points: &mut Vec<u64>, delta: u32) {
+    if points.len() == CAPACITY {
+        points.remove(0);
+    }
+    points.push(u64::from(delta));
+}
+
+fn sparkline<'a>(name: &str, points: &'a [u64], width: u16, color: Color) -> Sparkline<'a> {
+    let visible = usize::from(width.saturating_sub(2));
+    let start = points.len().saturating_sub(visible);
+    let title = match points.last() {
+        Some(delta) => format!("{} {}", i18n::tr(name), delta),
+        None => i18n::tr(name).to_string(),
+    };
+    Sparkline::default()
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .data(&points[start..])
+        .style(Style::default().fg(color))
+}
@@ -0,0 +1,57 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+};
+
+/// `RFKILL_OP_CHANGE` from linux/rfkill.h
+const RFKILL_OP_CHANGE: u8 = 2;
+
+/// rfkill switch of wifi phy
+#[derive(Clone, Copy, Debug)]
+pub struct Rfkill {
+    pub index: u32,
+    pub soft: bool,
+    pub hard: bool,
+}
+
+/// Returns rfkill switch of `phy<phy>`, if it has one
+pub fn for_phy(phy: u32) -> Option<Rfkill> {
+    let dir = format!("/sys/class/ieee80211/phy{}", phy);
+    let entry = fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .find(|e| e.file_name().to_string_lossy().starts_with("rfkill"))?;
+    let index = entry.file_name().to_string_lossy()["rfkill".len()..]
+        .parse()
+        .ok()?;
+    let read =
+        |name: &str| fs::read_to_string(entry.path().join(name)).is_ok_and(|v| v.trim() == "1");
+    Some(Rfkill {
+        index,
+        soft: read("soft"),
+        hard: read("hard"),
+    })
+}
+
+/// Changes soft block of rfkill switch, needs write access to `/dev/rfkill`
+pub fn set_soft_block(index: u32, block: bool) -> io::Result<()> {
+    // struct rfkill_event { idx: u32, type: u8, op: u8, soft: u8, hard: u8 }
+    let mut event = [0u8; 8];
+    event[..4].copy_from_slice(&index.to_ne_bytes());
+    event[5] = RFKILL_OP_CHANGE;
+    event[6] = block as u8;
+    OpenOptions::new()
+        .write(true)
+        .open("/dev/rfkill")?
+        .write_all(&event)
+}
+
+impl std::fmt::Display for Rfkill {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.soft, self.hard) {
+            (_, true) => write!(f, "hard blocked"),
+            (true, false) => write!(f, "soft blocked"),
+            (false, false) => write!(f, "not blocked"),
+        }
+    }
+}
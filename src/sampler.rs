@@ -0,0 +1,223 @@
+use std::fs;
+
+use chrono::{DateTime, Local};
+use macaddr::MacAddr6;
+use neli_wifi::{Bss, Interface, Socket};
+
+use crate::{
+    config::InterfacesConfig,
+    ie,
+    quality::{self, Link},
+};
+
+/// One measurement of wifi interface
+#[derive(Clone, Debug)]
+pub struct Sample {
+    pub time: DateTime<Local>,
+    pub iface: String,
+    pub bssid: Option<String>,
+    /// SSID from information elements of BSS, None if hidden or not known
+    pub ssid: Option<String>,
+    /// Signal level in dBm
+    pub signal: i32,
+    /// Frequency in MHz
+    pub frequency: Option<u32>,
+    /// true if interface is associated with this BSS
+    pub connected: bool,
+    /// TX bitrate in Mbit/s, known only if station info was read together with sample
+    pub bitrate: Option<u32>,
+    /// Link quality 0–100 from `quality::score`, known together with bitrate
+    pub score: Option<u8>,
+}
+
+impl Sample {
+    /// Sample as JSON object, time in RFC 3339
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "time": self.time.to_rfc3339(),
+            "iface": self.iface,
+            "bssid": self.bssid,
+            "ssid": self.ssid,
+            "signal": self.signal,
+            "frequency": self.frequency,
+            "connected": self.connected,
+            "bitrate": self.bitrate,
+            "score": self.score,
+        })
+    }
+
+    /// Parses object written by `json`, None if a field is missing
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let time = DateTime::parse_from_rfc3339(value["time"].as_str()?).ok()?;
+        Some(Self {
+            time: time.with_timezone(&Local),
+            iface: value["iface"].as_str()?.to_string(),
+            bssid: value["bssid"].as_str().map(str::to_string),
+            ssid: value["ssid"].as_str().map(str::to_string),
+            signal: i32::try_from(value["signal"].as_i64()?).ok()?,
+            frequency: value["frequency"]
+                .as_u64()
+                .and_then(|f| u32::try_from(f).ok()),
+            connected: value["connected"].as_bool()?,
+            bitrate: value["bitrate"]
+                .as_u64()
+                .and_then(|b| u32::try_from(b).ok()),
+            score: value["score"].as_u64().and_then(|s| u8::try_from(s).ok()),
+        })
+    }
+}
+
+/// Returns every wifi interface allowed by `filter` with BSS list seen by it
+pub fn devices(
+    socket: &mut Socket,
+    filter: &InterfacesConfig,
+) -> Result<Vec<(Interface, Vec<Bss>)>, neli::err::NlError> {
+    let interfaces = interfaces(socket, filter)?;
+    bss_lists(socket, interfaces)
+}
+
+/// Wifi interfaces allowed by `filter`
+pub fn interfaces(
+    socket: &mut Socket,
+    filter: &InterfacesConfig,
+) -> Result<Vec<Interface>, neli::err::NlError> {
+    let mut interfaces = socket.get_interfaces_info()?;
+    interfaces.retain(|interface| is_shown(interface, filter));
+    Ok(interfaces)
+}
+
+/// Reads BSS list of every interface
+pub fn bss_lists(
+    socket: &mut Socket,
+    interfaces: Vec<Interface>,
+) -> Result<Vec<(Interface, Vec<Bss>)>, neli::err::NlError> {
+    let mut devices = Vec::with_capacity(interfaces.len());
+    for interface in interfaces {
+        let bss_list = match interface.index {
+            Some(index) => socket.get_bss_info(index)?,
+            None => Vec::new(),
+        };
+        devices.push((interface, bss_list));
+    }
+    Ok(devices)
+}
+
+/// false if interface info is older than its BSS list, e.g. it roamed to another channel.
+/// Only associated stations are checked, AP, monitor or mesh interfaces have frequency
+/// without associated BSS
+pub fn is_current(interface: &Interface, bss_list: &[Bss]) -> bool {
+    let Some(associated) = bss_list.iter().find(|b| b.status.is_some()) else {
+        return true;
+    };
+    associated.frequency == interface.frequency
+}
+
+/// Returns one sample for each wifi interface which has BSS information
+pub fn collect(
+    socket: &mut Socket,
+    filter: &InterfacesConfig,
+) -> Result<Vec<Sample>, neli::err::NlError> {
+    let mut samples = Vec::new();
+    for (interface, bss_list) in devices(socket, filter)? {
+        let Some(mut sample) = sample(&interface, &bss_list) else {
+            continue;
+        };
+        if let (true, Some(index)) = (sample.connected, interface.index) {
+            link(socket, index, &mut sample)?;
+        }
+        samples.push(sample);
+    }
+    Ok(samples)
+}
+
+/// Returns false for interfaces which are not bound to wifi PHY or are hidden by config
+pub fn is_shown(interface: &Interface, filter: &InterfacesConfig) -> bool {
+    if interface.phy.is_none() || interface.name.is_none() {
+        return false;
+    }
+    let name = iface_name(interface);
+    // p2p device is wdev without netdev, so it has no index
+    if filter.hide_p2p && (interface.index.is_none() || name.starts_with("p2p-dev-")) {
+        return false;
+    }
+    !(filter.hide_monitor && is_monitor(&name))
+}
+
+/// Monitor interfaces have radiotap link type
+pub fn is_monitor(iface: &str) -> bool {
+    const ARPHRD_IEEE80211_RADIOTAP: &str = "803";
+    fs::read_to_string(format!("/sys/class/net/{}/type", iface))
+        .is_ok_and(|t| t.trim() == ARPHRD_IEEE80211_RADIOTAP)
+}
+
+/// Returns sample of interface, `None` if it has no name. Sample of interface without
+/// associated BSS has no link fields, scanned neighbours are not its link
+pub fn sample(interface: &Interface, bss_list: &[Bss]) -> Option<Sample> {
+    interface.name.as_ref()?;
+    let Some(bss) = bss_list.iter().find(|b| b.status.is_some()) else {
+        return Some(Sample {
+            time: Local::now(),
+            iface: iface_name(interface),
+            bssid: None,
+            ssid: None,
+            signal: 0,
+            frequency: None,
+            connected: false,
+            bitrate: None,
+            score: None,
+        });
+    };
+    Some(Sample {
+        time: Local::now(),
+        iface: iface_name(interface),
+        bssid: format_bssid(bss),
+        ssid: bss.information_elements.as_deref().and_then(ie::ssid),
+        signal: bss.signal.map(|s| s / 100).unwrap_or_default(),
+        frequency: bss.frequency,
+        connected: true,
+        bitrate: None,
+        score: None,
+    })
+}
+
+/// Fills bitrate and quality score of associated sample from station info of its access point
+pub fn link(
+    socket: &mut Socket,
+    index: i32,
+    sample: &mut Sample,
+) -> Result<(), neli::err::NlError> {
+    let stations = socket.get_station_info(index)?;
+    let station = stations.first();
+    // station info is in 100 kbit/s
+    sample.bitrate = station.and_then(|s| s.tx_bitrate).map(|b| b / 10);
+    let survey = quality::survey(index);
+    sample.score = Some(quality::score(&Link {
+        signal: sample.signal,
+        noise: survey.noise,
+        bitrate: sample.bitrate,
+        retries: station.and_then(quality::retries),
+        busy: survey.busy,
+    }));
+    Ok(())
+}
+
+/// Raw interface name without trailing nul byte, empty if it is not UTF-8
+pub fn trimmed(name: &[u8]) -> &str {
+    std::str::from_utf8(name)
+        .unwrap_or_default()
+        .trim_end_matches('\0')
+}
+
+/// Returns interface name without trailing nul byte
+pub fn iface_name(interface: &Interface) -> String {
+    let name = interface.name.as_deref().unwrap_or_default();
+    String::from_utf8_lossy(name)
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+/// Returns BSSID in `aa:bb:cc:dd:ee:ff` format
+pub fn format_bssid(bss: &Bss) -> Option<String> {
+    let addr: [u8; 6] = bss.bssid.as_deref()?.try_into().ok()?;
+    Some(MacAddr6::from(addr).to_string())
+}
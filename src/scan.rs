@@ -0,0 +1,814 @@
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+use neli_wifi::Bss;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, List, ListItem, Row, Table, TableState},
+};
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::{
+    control::{self, ControlBackend},
+    display::{self, Icon},
+    get_security_info, i18n,
+    ie::{self, Pmf, Security},
+    profile::Band,
+    regdomain::{self, ChannelMonitor},
+    sampler, steering,
+    twin::Fingerprint,
+};
+
+/// How often known networks are requested from control backend
+const KNOWN_REFRESH: Duration = Duration::from_secs(10);
+
+/// `[scan]` section of config
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ScanConfig {
+    /// Lists Wi-Fi Direct devices and phone hotspots, false hides them from scan table
+    pub devices: bool,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self { devices: true }
+    }
+}
+
+/// What kind of device BSS belongs to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Category {
+    Network,
+    /// Wi-Fi Direct group owner like TV or printer, or SSID starting with `DIRECT-`
+    P2p,
+    /// Phone sharing its mobile connection
+    Hotspot,
+}
+
+impl Category {
+    fn of(ssid: Option<&str>, ies: Option<&[u8]>) -> Self {
+        if ies.is_some_and(ie::p2p) || ssid.is_some_and(|s| s.starts_with("DIRECT-")) {
+            Category::P2p
+        } else if ies.is_some_and(ie::hotspot) {
+            Category::Hotspot
+        } else {
+            Category::Network
+        }
+    }
+
+    fn icon(&self, security: Security) -> Icon {
+        match (self, security) {
+            (Category::P2p, _) => Icon::Cast,
+            (Category::Hotspot, _) => Icon::Phone,
+            (Category::Network, Security::Open) => Icon::Unlock,
+            (Category::Network, _) => Icon::Lock,
+        }
+    }
+}
+
+/// One BSS in scan table
+#[derive(Clone, Debug)]
+pub struct ScanRow {
+    pub ssid: Option<String>,
+    pub bssid: String,
+    /// Signal level in dBm
+    pub signal: i32,
+    pub frequency: Option<u32>,
+    pub connected: bool,
+    /// Operates on DFS channel
+    pub dfs: bool,
+    pub security: Security,
+    pub pmf: Pmf,
+    /// Advertises Wi-Fi Protected Setup
+    pub wps: bool,
+    pub category: Category,
+    /// Chipset vendor from vendor specific elements
+    pub vendor: Option<&'static str>,
+    /// Time since BSS was last heard
+    pub seen: Option<Duration>,
+}
+
+impl ScanRow {
+    /// `WPA3, PMF required, WPS`
+    pub fn flags(&self) -> String {
+        let mut flags = vec![self.security.to_string()];
+        match self.category {
+            Category::P2p => flags.push("P2P".to_string()),
+            Category::Hotspot => flags.push("hotspot".to_string()),
+            Category::Network => {}
+        }
+        if self.pmf != Pmf::Disabled {
+            flags.push(self.pmf.to_string());
+        }
+        if self.wps {
+            flags.push("WPS".to_string());
+        }
+        flags.join(", ")
+    }
+}
+
+/// Returns scan rows sorted from the strongest signal
+pub fn rows(bss_list: &[Bss]) -> Vec<ScanRow> {
+    let mut rows: Vec<ScanRow> = bss_list
+        .iter()
+        .map(|bss| {
+            let ies = bss.information_elements.as_deref();
+            let ssid = ies.and_then(ie::ssid);
+            ScanRow {
+                category: Category::of(ssid.as_deref(), ies),
+                ssid,
+                bssid: sampler::format_bssid(bss).unwrap_or_default(),
+                signal: bss.signal.map(|s| s / 100).unwrap_or_default(),
+                frequency: bss.frequency,
+                connected: bss.status.is_some(),
+                dfs: bss.frequency.is_some_and(regdomain::is_dfs),
+                security: ies.map_or(Security::Open, ie::security),
+                pmf: ies.map_or(Pmf::Disabled, ie::pmf),
+                wps: ies.is_some_and(ie::wps),
+                vendor: ies.and_then(ie::vendor),
+                seen: bss
+                    .seen_ms_ago
+                    .map(|ms| Duration::from_millis(u64::from(ms))),
+            }
+        })
+        .collect();
+    rows.sort_by_key(|r| -r.signal);
+    rows
+}
+
+/// Column scan table is sorted by, 'S' steps through them
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortColumn {
+    #[default]
+    Signal,
+    /// By frequency, so 2.4 GHz comes first
+    Channel,
+    Ssid,
+    Seen,
+}
+
+/// Order of scan table, groups follow their first row
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Sort {
+    pub column: SortColumn,
+    pub descending: bool,
+}
+
+impl Default for Sort {
+    fn default() -> Self {
+        Self {
+            column: SortColumn::Signal,
+            descending: true,
+        }
+    }
+}
+
+impl Sort {
+    /// Selects next column in its natural order, the strongest signal and the newest BSS first
+    pub fn next_column(&mut self) {
+        self.column = match self.column {
+            SortColumn::Signal => SortColumn::Channel,
+            SortColumn::Channel => SortColumn::Ssid,
+            SortColumn::Ssid => SortColumn::Seen,
+            SortColumn::Seen => SortColumn::Signal,
+        };
+        self.descending = self.column == SortColumn::Signal;
+    }
+
+    /// 'R' flips order of current column
+    pub fn reverse(&mut self) {
+        self.descending = !self.descending;
+    }
+
+    /// Sorts `rows` which come sorted by signal, so it breaks ties
+    pub fn apply(&self, rows: &mut [ScanRow]) {
+        match self.column {
+            SortColumn::Signal => rows.sort_by_key(|r| r.signal),
+            SortColumn::Channel => rows.sort_by_key(|r| r.frequency),
+            // hidden networks go last
+            SortColumn::Ssid => {
+                rows.sort_by_key(|r| (r.ssid.is_none(), r.ssid.as_deref().map(str::to_lowercase)))
+            }
+            SortColumn::Seen => rows.sort_by_key(|r| r.seen),
+        }
+        if self.descending {
+            rows.reverse();
+        }
+    }
+
+    /// Arrow put after header of sorted column
+    fn mark(&self, column: SortColumn) -> &'static str {
+        match (self.column == column, self.descending) {
+            (false, _) => "",
+            (true, true) => " ↓",
+            (true, false) => " ↑",
+        }
+    }
+}
+
+/// Weakest signals '3' steps through, in dBm
+const MIN_SIGNALS: [i32; 3] = [-80, -70, -60];
+
+/// Filters of scan table, '/' types text, '1', '2' and '3' step through band, security and signal
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Filter {
+    /// Part of SSID, BSSID or vendor, case is ignored
+    pub text: String,
+    pub band: Option<Band>,
+    pub security: Option<Security>,
+    /// Weakest signal in dBm which is shown
+    pub min_signal: Option<i32>,
+}
+
+impl Filter {
+    pub fn matches(&self, row: &ScanRow) -> bool {
+        let text = self.text.to_lowercase();
+        let found = |s: &str| s.to_lowercase().contains(&text);
+        (text.is_empty()
+            || row.ssid.as_deref().is_some_and(found)
+            || found(&row.bssid)
+            || row.vendor.is_some_and(found))
+            && self
+                .band
+                .is_none_or(|b| row.frequency.is_some_and(|f| Band::of(f) == b))
+            && self.security.is_none_or(|s| row.security == s)
+            && self.min_signal.is_none_or(|s| row.signal >= s)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Filter::default()
+    }
+
+    pub fn next_band(&mut self) {
+        self.band = match self.band {
+            None => Some(Band::Band2),
+            Some(Band::Band2) => Some(Band::Band5),
+            Some(Band::Band5) => Some(Band::Band6),
+            Some(Band::Band6) => None,
+        };
+    }
+
+    pub fn next_security(&mut self) {
+        self.security = match self.security {
+            None => Some(Security::Open),
+            Some(Security::Open) => Some(Security::Wpa),
+            Some(Security::Wpa) => Some(Security::Wpa2),
+            Some(Security::Wpa2) => Some(Security::Wpa3),
+            Some(Security::Wpa3) => None,
+        };
+    }
+
+    pub fn next_signal(&mut self) {
+        self.min_signal = match self.min_signal {
+            None => Some(MIN_SIGNALS[0]),
+            Some(s) => MIN_SIGNALS.iter().find(|m| **m > s).copied(),
+        };
+    }
+}
+
+/// `"home", 5 GHz, WPA3, -70 dBm or better`
+impl std::fmt::Display for Filter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if !self.text.is_empty() {
+            parts.push(format!("\"{}\"", self.text));
+        }
+        if let Some(band) = self.band {
+            parts.push(band.to_string());
+        }
+        if let Some(security) = self.security {
+            parts.push(security.to_string());
+        }
+        if let Some(signal) = self.min_signal {
+            parts.push(format!("{} dBm or better", signal));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Line of scan table, SSID seen from more BSSIDs is collapsed into one group
+pub enum Line<'a> {
+    /// Mesh or multi-AP network, `rows` are sorted from the strongest signal
+    Group {
+        ssid: &'a str,
+        rows: Vec<&'a ScanRow>,
+        /// Physical APs, dual-band AP counts once
+        aps: usize,
+        expanded: bool,
+    },
+    Bss {
+        row: &'a ScanRow,
+        /// Shown under expanded group
+        nested: bool,
+        /// Number of AP in group when AP has more radios
+        ap: Option<usize>,
+    },
+}
+
+impl Line<'_> {
+    pub fn ssid(&self) -> Option<&str> {
+        match self {
+            Line::Group { ssid, .. } => Some(ssid),
+            Line::Bss { row, .. } => row.ssid.as_deref(),
+        }
+    }
+}
+
+/// Groups rows by SSID in order of their first row, hidden networks are never grouped
+pub fn lines<'a>(rows: &'a [ScanRow], expanded: &HashSet<String>) -> Vec<Line<'a>> {
+    let mut groups: Vec<(Option<&str>, Vec<&ScanRow>)> = Vec::new();
+    for row in rows {
+        match row.ssid.as_deref() {
+            Some(ssid) => match groups.iter_mut().find(|(s, _)| *s == Some(ssid)) {
+                Some((_, members)) => members.push(row),
+                None => groups.push((Some(ssid), vec![row])),
+            },
+            None => groups.push((None, vec![row])),
+        }
+    }
+    let mut lines = Vec::with_capacity(rows.len());
+    for (ssid, mut members) in groups {
+        // table may be sorted by other column, group still shows its strongest BSSID
+        members.sort_by_key(|r| -r.signal);
+        match (ssid, members.as_slice()) {
+            (Some(ssid), [_, _, ..]) => {
+                let open = expanded.contains(ssid);
+                let aps = steering::aps(&members);
+                lines.push(Line::Group {
+                    ssid,
+                    rows: members,
+                    aps: aps.len(),
+                    expanded: open,
+                });
+                if !open {
+                    continue;
+                }
+                for (i, ap) in aps.iter().enumerate() {
+                    let number = (ap.radios.len() > 1).then_some(i + 1);
+                    lines.extend(ap.radios.iter().map(|row| Line::Bss {
+                        row,
+                        nested: true,
+                        ap: number,
+                    }));
+                }
+            }
+            _ => lines.extend(members.into_iter().map(|row| Line::Bss {
+                row,
+                nested: false,
+                ap: None,
+            })),
+        }
+    }
+    lines
+}
+
+/// `36, 5 GHz`
+fn channel(frequency: Option<u32>) -> String {
+    let Some(frequency) = frequency else {
+        return String::new();
+    };
+    match regdomain::channel_number(frequency) {
+        Some(channel) => format!("{}, {}", channel, Band::of(frequency)),
+        None => Band::of(frequency).to_string(),
+    }
+}
+
+/// `12s` since BSS was heard
+fn seen(seen: Option<Duration>) -> String {
+    seen.map(|d| format!("{}s", d.as_secs()))
+        .unwrap_or_default()
+}
+
+/// Scan view state which lives between frames
+pub struct ScanView {
+    backend: Option<Box<dyn ControlBackend>>,
+    known: Vec<String>,
+    known_at: Option<Instant>,
+    channels: ChannelMonitor,
+    /// SSIDs of groups which show their BSSIDs
+    expanded: HashSet<String>,
+    /// Title of table with active filter
+    title: String,
+    /// Keys go into filter text instead of commands
+    typing: bool,
+    sort: Sort,
+}
+
+impl ScanView {
+    pub fn new() -> Self {
+        Self {
+            backend: control::backend(),
+            known: Vec::new(),
+            known_at: None,
+            channels: ChannelMonitor::new(),
+            expanded: HashSet::new(),
+            title: i18n::tr("scan").to_string(),
+            typing: false,
+            sort: Sort::default(),
+        }
+    }
+
+    pub fn backend(&self) -> Option<&dyn ControlBackend> {
+        self.backend.as_deref()
+    }
+
+    /// Loads regulatory flags of channels of selected wiphy
+    pub fn update_channels(&mut self, phy: Option<u32>) {
+        self.channels.update(phy);
+    }
+
+    pub fn expanded(&self) -> &HashSet<String> {
+        &self.expanded
+    }
+
+    /// Expands collapsed group or collapses expanded one, BSS lines are ignored
+    pub fn toggle(&mut self, line: Option<&Line>) {
+        if let Some(Line::Group { ssid, .. }) = line
+            && !self.expanded.remove(*ssid)
+        {
+            self.expanded.insert(ssid.to_string());
+        }
+    }
+
+    /// Shows filter and amount of rows it left out in title
+    pub fn set_filter(&mut self, filter: &Filter, typing: bool, shown: usize, total: usize) {
+        self.typing = typing;
+        self.title = if filter.is_empty() && !typing {
+            i18n::tr("scan").to_string()
+        } else {
+            i18n::fill(
+                i18n::tr("scan, {} of {} shown, filter {}{}"),
+                &[
+                    &shown.to_string(),
+                    &total.to_string(),
+                    &filter.to_string(),
+                    if typing { "_" } else { "" },
+                ],
+            )
+        };
+    }
+
+    /// Order shown in table header
+    pub fn set_sort(&mut self, sort: Sort) {
+        self.sort = sort;
+    }
+
+    /// Forces reload of known networks on the next frame
+    pub fn invalidate(&mut self) {
+        self.known_at = None;
+    }
+
+    fn refresh_known(&mut self) {
+        let Some(backend) = &self.backend else {
+            return;
+        };
+        if self.known_at.is_some_and(|t| t.elapsed() < KNOWN_REFRESH) {
+            return;
+        }
+        self.known_at = Some(Instant::now());
+        match backend.known_networks() {
+            Ok(known) => self.known = known,
+            Err(e) => warn!(error = %e, backend = backend.name(), "failed to list known networks"),
+        }
+    }
+
+    pub fn draw(
+        &mut self,
+        f: &mut Frame,
+        area: Rect,
+        lines: &[Line],
+        selected: usize,
+        suspicious: &[Fingerprint],
+        hide_info: bool,
+    ) {
+        self.refresh_known();
+        let main = if suspicious.is_empty() {
+            area
+        } else {
+            let parts = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .split(area);
+            draw_suspicious(f, parts[1], suspicious, hide_info);
+            parts[0]
+        };
+        let top = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+            .split(main);
+
+        let table_rows = lines.iter().map(|line| match line {
+            Line::Group {
+                ssid,
+                rows,
+                aps,
+                expanded,
+            } => {
+                let style = if rows.iter().any(|r| r.connected) {
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                // rows are never empty, group has at least two
+                let best = rows[0];
+                let mut bands: Vec<Band> = Vec::new();
+                for band in rows.iter().filter_map(|r| r.frequency).map(Band::of) {
+                    if !bands.contains(&band) {
+                        bands.push(band);
+                    }
+                }
+                Row::new(vec![
+                    Span::raw(format!(
+                        "{} {}{}",
+                        if *expanded { "▾" } else { "▸" },
+                        display::icon(best.category.icon(best.security)),
+                        ssid
+                    )),
+                    Span::raw(if *aps < rows.len() {
+                        format!("{} BSSIDs, {} APs", rows.len(), aps)
+                    } else {
+                        format!("{} BSSIDs", rows.len())
+                    }),
+                    Span::styled(
+                        display::signal_value(best.signal).to_string(),
+                        Style::default().fg(display::signal_color(best.signal)),
+                    ),
+                    Span::raw(""),
+                    Span::raw(
+                        bands
+                            .iter()
+                            .map(Band::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    ),
+                    Span::raw(""),
+                    Span::raw(best.flags()),
+                    Span::raw(seen(rows.iter().filter_map(|r| r.seen).min())),
+                ])
+                .style(style)
+            }
+            Line::Bss { row: r, nested, ap } => {
+                let style = if r.connected {
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                let name = if *nested {
+                    match ap {
+                        Some(ap) => format!("  └ AP {}", ap),
+                        None => "  └".to_string(),
+                    }
+                } else {
+                    format!(
+                        "{}{}",
+                        display::icon(r.category.icon(r.security)),
+                        r.ssid.as_deref().unwrap_or("<hidden>")
+                    )
+                };
+                Row::new(vec![
+                    Span::raw(name),
+                    Span::raw(get_security_info(&r.bssid, hide_info)),
+                    Span::styled(
+                        display::signal_value(r.signal).to_string(),
+                        Style::default().fg(display::signal_color(r.signal)),
+                    ),
+                    Span::raw(r.frequency.map(|f| f.to_string()).unwrap_or_default()),
+                    Span::raw(channel(r.frequency)),
+                    self.dfs_cell(r),
+                    Span::styled(
+                        r.flags(),
+                        // without PMF deauth frames can be spoofed
+                        match r.pmf {
+                            Pmf::Disabled if r.security != Security::Open => {
+                                Style::default().fg(Color::Yellow)
+                            }
+                            _ => Style::default(),
+                        },
+                    ),
+                    Span::raw(seen(r.seen)),
+                ])
+                .style(style)
+            }
+        });
+        let table = Table::new(
+            table_rows,
+            [
+                Constraint::Percentage(20),
+                Constraint::Percentage(18),
+                Constraint::Percentage(8),
+                Constraint::Percentage(7),
+                Constraint::Percentage(13),
+                Constraint::Percentage(9),
+                Constraint::Percentage(17),
+                Constraint::Percentage(8),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                format!("SSID{}", self.sort.mark(SortColumn::Ssid)),
+                "BSSID".to_string(),
+                format!("{}{}", display::unit(), self.sort.mark(SortColumn::Signal)),
+                "MHz".to_string(),
+                format!(
+                    "{}{}",
+                    i18n::tr("Channel"),
+                    self.sort.mark(SortColumn::Channel)
+                ),
+                "DFS".to_string(),
+                i18n::tr("Flags").to_string(),
+                format!("{}{}", i18n::tr("Seen"), self.sort.mark(SortColumn::Seen)),
+            ])
+            .style(Style::default().add_modifier(Modifier::ITALIC)),
+        )
+        .block(
+            Block::default()
+                .title(self.title.as_str())
+                .borders(Borders::ALL),
+        )
+        .row_highlight_style(Style::default().bg(Color::DarkGray));
+        let mut table_state = TableState::default();
+        if !lines.is_empty() {
+            table_state.select(Some(selected % lines.len()));
+        }
+        f.render_stateful_widget(table, top[0], &mut table_state);
+
+        let known_title = match &self.backend {
+            Some(b) => i18n::fill(i18n::tr("known ({})"), &[b.name()]),
+            None => i18n::tr("known").to_string(),
+        };
+        let known = List::new(
+            self.known
+                .iter()
+                .map(|k| ListItem::new(k.as_str()))
+                .collect::<Vec<_>>(),
+        )
+        .block(Block::default().title(known_title).borders(Borders::ALL));
+        f.render_widget(known, top[1]);
+    }
+
+    pub fn hint(&self) -> &'static str {
+        if self.typing {
+            return "Type part of SSID, BSSID or vendor, Enter keeps filter, Esc clears it";
+        }
+        match &self.backend {
+            Some(_) => {
+                "Up/Down to select, Enter expands network, 't' to scan, 'c' to connect, 'd' to disconnect, 'm' for monitoring\n'/' filters by text, '1' band, '2' security, '3' signal, '0' clears filters, 'S' sorts by next column, 'R' reverses order, 'y' copies BSSID or SSID"
+            }
+            None => {
+                "Up/Down to select, Enter expands network, 'm' for monitoring\n'/' filters by text, '1' band, '2' security, '3' signal, '0' clears filters, 'S' sorts by next column, 'R' reverses order, 'y' copies BSSID or SSID\nConnect needs build with networkmanager or iwd feature and running service"
+            }
+        }
+    }
+
+    /// DFS mark of row, radar flag comes from regulatory data of local wiphy
+    fn dfs_cell(&self, row: &ScanRow) -> Span<'static> {
+        let radar = row.frequency.and_then(|f| self.channels.radar(f));
+        match (radar, row.dfs) {
+            (Some(true), _) => Span::styled("radar", Style::default().fg(Color::Yellow)),
+            (Some(false), true) => Span::raw("DFS, no radar"),
+            (None, true) => Span::raw("DFS"),
+            (_, false) => Span::raw(""),
+        }
+    }
+}
+
+/// Connected BSS next to BSSes which advertise the same SSID differently
+fn draw_suspicious(f: &mut Frame, area: Rect, suspicious: &[Fingerprint], hide_info: bool) {
+    let rows = suspicious.iter().map(|s| {
+        let style = if s.connected {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Red)
+        };
+        Row::new(vec![
+            get_security_info(&s.bssid, hide_info),
+            s.security.to_string(),
+            s.vendor_list(),
+            s.signal.to_string(),
+            s.frequency.map(|f| f.to_string()).unwrap_or_default(),
+        ])
+        .style(style)
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(25),
+            Constraint::Percentage(10),
+            Constraint::Percentage(45),
+            Constraint::Percentage(10),
+            Constraint::Percentage(10),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "BSSID",
+            i18n::tr("security"),
+            i18n::tr("vendors"),
+            "dBm",
+            "MHz",
+        ])
+        .style(Style::default().add_modifier(Modifier::ITALIC)),
+    )
+    .block(
+        Block::default()
+            .title(Span::styled(
+                format!(
+                    "{}{}",
+                    display::icon(Icon::Warning),
+                    i18n::tr("possible evil twin of current network")
+                ),
+                Style::default().fg(Color::Red),
+            ))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(table, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(ssid: Option<&str>, signal: i32, frequency: u32, seen: u64) -> ScanRow {
+        ScanRow {
+            ssid: ssid.map(str::to_string),
+            bssid: format!("02:00:00:00:00:{:02x}", seen),
+            signal,
+            frequency: Some(frequency),
+            connected: false,
+            dfs: false,
+            security: Security::Wpa2,
+            pmf: Pmf::Disabled,
+            wps: false,
+            category: Category::Network,
+            vendor: Some("Broadcom"),
+            seen: Some(Duration::from_secs(seen)),
+        }
+    }
+
+    fn ssids(rows: &[ScanRow]) -> Vec<Option<&str>> {
+        rows.iter().map(|r| r.ssid.as_deref()).collect()
+    }
+
+    #[test]
+    fn filter_combines_text_band_and_signal() {
+        let home = row(Some("Home"), -50, 5180, 1);
+        let cafe = row(Some("cafe"), -75, 2412, 2);
+        let filter = Filter {
+            text: "HOME".to_string(),
+            ..Filter::default()
+        };
+        assert!(filter.matches(&home));
+        assert!(!filter.matches(&cafe));
+        // vendor and BSSID are searched too
+        let filter = Filter {
+            text: "broad".to_string(),
+            ..Filter::default()
+        };
+        assert!(filter.matches(&cafe));
+        let filter = Filter {
+            band: Some(Band::Band2),
+            ..Filter::default()
+        };
+        assert!(!filter.matches(&home));
+        assert!(filter.matches(&cafe));
+        let mut filter = Filter::default();
+        filter.next_signal();
+        filter.next_signal();
+        assert_eq!(filter.min_signal, Some(-70));
+        assert!(filter.matches(&home));
+        assert!(!filter.matches(&cafe));
+        filter.next_signal();
+        filter.next_signal();
+        assert!(filter.is_empty());
+    }
+
+    #[test]
+    fn sort_puts_hidden_networks_last() {
+        let mut rows = vec![
+            row(None, -40, 2412, 3),
+            row(Some("b"), -60, 5180, 1),
+            row(Some("A"), -50, 2437, 2),
+        ];
+        let mut sort = Sort::default();
+        sort.apply(&mut rows);
+        assert_eq!(ssids(&rows), [None, Some("A"), Some("b")]);
+        sort.next_column();
+        assert_eq!(sort.column, SortColumn::Channel);
+        assert!(!sort.descending);
+        sort.apply(&mut rows);
+        assert_eq!(ssids(&rows), [None, Some("A"), Some("b")]);
+        sort.next_column();
+        sort.apply(&mut rows);
+        assert_eq!(ssids(&rows), [Some("A"), Some("b"), None]);
+        sort.reverse();
+        sort.apply(&mut rows);
+        assert_eq!(ssids(&rows), [None, Some("b"), Some("A")]);
+    }
+}
@@ -0,0 +1,118 @@
+use std::path::Path;
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+use tracing::{info, warn};
+
+use crate::{config::Config, display, i18n, logging};
+
+pub const HINT: &str =
+    "Up/Down to select, Left/Right or Enter to change and save, 'm' for monitoring";
+
+/// Editable settings in order of lines
+const FIELDS: [&str; 6] = [
+    "interval",
+    "good signal",
+    "fair signal",
+    "units",
+    "theme",
+    "privacy",
+];
+
+/// Settings view state which lives between frames
+pub struct SettingsView {
+    /// Result of last save shown under the list
+    status: Option<Result<String, String>>,
+}
+
+impl SettingsView {
+    pub fn new() -> Self {
+        Self { status: None }
+    }
+
+    /// Changes selected setting by one step, applies it and saves config into `dir`
+    pub fn change(&mut self, config: &mut Config, dir: &Path, selected: usize, up: bool) {
+        let step = if up { 1 } else { -1 };
+        let display = &mut config.display;
+        match selected % FIELDS.len() {
+            0 => config.interval = config.interval.saturating_add_signed(step).clamp(1, 60),
+            1 => {
+                display.good_signal = (display.good_signal + step as i32).clamp(-99, -20);
+                display.fair_signal = display.fair_signal.min(display.good_signal);
+            }
+            2 => {
+                display.fair_signal =
+                    (display.fair_signal + step as i32).clamp(-100, display.good_signal)
+            }
+            3 => display.units = display.units.next(),
+            4 => display.theme = display.theme.next(),
+            _ => config.privacy = !config.privacy,
+        }
+        display::set(&config.display);
+        logging::set_privacy(config.privacy);
+        self.status = Some(match config.save_settings(dir) {
+            Ok(()) => {
+                info!(field = FIELDS[selected % FIELDS.len()], "settings saved");
+                Ok(format!("saved to {}", dir.join("config.toml").display()))
+            }
+            Err(e) => {
+                warn!(error = %e, "failed to save settings");
+                Err(format!("not saved: {}", e))
+            }
+        });
+    }
+
+    pub fn draw(&self, f: &mut Frame, area: Rect, config: &Config, selected: usize) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(3)])
+            .split(area);
+        let display = &config.display;
+        let values = [
+            format!("{} s", config.interval),
+            format!("{} dBm", display.good_signal),
+            format!("{} dBm", display.fair_signal),
+            display.units.to_string(),
+            display.theme.key().to_string(),
+            if config.privacy { "on" } else { "off" }.to_string(),
+        ];
+        let items: Vec<ListItem> = FIELDS
+            .iter()
+            .zip(values)
+            .map(|(name, value)| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<14}", name),
+                        Style::default().add_modifier(Modifier::ITALIC),
+                    ),
+                    Span::raw(value),
+                ]))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(i18n::tr("settings"))
+                    .borders(Borders::ALL),
+            )
+            .highlight_style(Style::default().bg(Color::DarkGray));
+        let mut list_state = ListState::default();
+        list_state.select(Some(selected % FIELDS.len()));
+        f.render_stateful_widget(list, chunks[0], &mut list_state);
+
+        let status = match &self.status {
+            Some(Ok(s)) => Span::raw(s.as_str()),
+            Some(Err(e)) => Span::styled(e.as_str(), Style::default().fg(Color::Red)),
+            None => Span::raw("privacy hides mac addresses in log, 'h' hides them on screen"),
+        };
+        f.render_widget(
+            Paragraph::new(status).block(Block::default().borders(Borders::ALL)),
+            chunks[1],
+        );
+    }
+}
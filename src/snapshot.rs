@@ -0,0 +1,68 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use chrono::Local;
+use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+use tracing::{info, warn};
+
+use crate::{
+    CONFIGURATION,
+    events::{self, Kind},
+};
+
+/// Set by 'P', the next drawn frame is written to file
+static REQUESTED: AtomicBool = AtomicBool::new(false);
+
+pub fn request() {
+    REQUESTED.store(true, Ordering::Relaxed);
+}
+
+/// Rendered last over whole frame like `AsciiOnly`, writes frame as text when it was requested
+pub struct Snapshot;
+
+impl Widget for Snapshot {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if !REQUESTED.swap(false, Ordering::Relaxed) {
+            return;
+        }
+        match write(Path::new(CONFIGURATION.as_str()), &text(area, buf)) {
+            Ok(path) => {
+                info!(path = %path.display(), "snapshot written");
+                events::push(
+                    Kind::Config,
+                    format!("snapshot written to {}", path.display()),
+                );
+            }
+            Err(e) => {
+                warn!(error = %e, "failed to write snapshot");
+                events::push(Kind::Error, format!("failed to write snapshot: {}", e));
+            }
+        }
+    }
+}
+
+/// Lines of frame without trailing spaces, colors are lost
+pub fn text(area: Rect, buf: &Buffer) -> String {
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        let line: String = (area.left()..area.right())
+            .map(|x| buf[(x, y)].symbol())
+            .collect();
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+fn write(dir: &Path, text: &str) -> io::Result<PathBuf> {
+    let path = dir.join(format!(
+        "snapshot-{}.txt",
+        Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    fs::create_dir_all(dir)?;
+    fs::write(&path, text)?;
+    Ok(path)
+}
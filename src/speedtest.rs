@@ -0,0 +1,198 @@
+use std::{
+    fs::OpenOptions,
+    io::{self, Write},
+    net::{TcpStream, ToSocketAddrs},
+    path::Path,
+    process::Command,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::{CONFIGURATION, history, sampler::Sample};
+
+const HEADER: &str = "time,iface,bssid,signal,frequency,connected,mode,bits_per_second";
+
+/// `[speedtest]` section of config
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct SpeedTestConfig {
+    /// Host of iperf3 server or of any TCP sink for built-in test, test is disabled if not set
+    pub server: Option<String>,
+    pub port: u16,
+    pub mode: SpeedTestMode,
+    /// Seconds of transfer
+    pub duration: u64,
+}
+
+impl Default for SpeedTestConfig {
+    fn default() -> Self {
+        Self {
+            server: None,
+            port: 5201,
+            mode: SpeedTestMode::Iperf3,
+            duration: 5,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SpeedTestMode {
+    /// Runs `iperf3 -c` and reads its json report
+    Iperf3,
+    /// Uploads zeros over plain TCP connection, server only has to read them
+    Tcp,
+}
+
+/// State of the latest speed test
+#[derive(Clone, Debug, PartialEq)]
+pub enum SpeedTestStatus {
+    Idle,
+    Running,
+    /// Measured bits per second
+    Done(f64),
+    Failed(String),
+}
+
+impl std::fmt::Display for SpeedTestStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpeedTestStatus::Idle => write!(f, "press 'i' to run"),
+            SpeedTestStatus::Running => write!(f, "running…"),
+            SpeedTestStatus::Done(bps) => write!(f, "{:.1} Mbit/s", bps / 1_000_000.0),
+            SpeedTestStatus::Failed(e) => write!(f, "failed: {}", e),
+        }
+    }
+}
+
+/// On-demand throughput test which runs in background
+pub struct SpeedTest {
+    config: SpeedTestConfig,
+    status: Arc<RwLock<SpeedTestStatus>>,
+}
+
+impl SpeedTest {
+    pub fn new(config: SpeedTestConfig) -> Self {
+        Self {
+            config,
+            status: Arc::new(RwLock::new(SpeedTestStatus::Idle)),
+        }
+    }
+
+    pub fn status(&self) -> SpeedTestStatus {
+        self.status.read().unwrap().clone()
+    }
+
+    /// Starts test if none is running, result is written to `speedtest.csv` together with `sample`
+    pub fn start(&self, sample: Option<Sample>) {
+        let mut status = self.status.write().unwrap();
+        if *status == SpeedTestStatus::Running {
+            return;
+        }
+        let Some(server) = self.config.server.clone() else {
+            *status = SpeedTestStatus::Failed("server is not configured".to_string());
+            return;
+        };
+        *status = SpeedTestStatus::Running;
+        drop(status);
+
+        let config = self.config.clone();
+        let shared = self.status.clone();
+        std::thread::spawn(move || {
+            info!(server, mode = ?config.mode, "starting speed test");
+            let res = match run(&server, &config) {
+                Ok(bps) => {
+                    info!(bits_per_second = bps, "speed test finished");
+                    if let Some(sample) = sample
+                        && let Err(e) = record(&sample, config.mode, bps)
+                    {
+                        warn!(error = %e, "failed to write speed test result");
+                    }
+                    SpeedTestStatus::Done(bps)
+                }
+                Err(e) => {
+                    warn!(error = %e, "speed test failed");
+                    SpeedTestStatus::Failed(e.to_string())
+                }
+            };
+            *shared.write().unwrap() = res;
+        });
+    }
+}
+
+/// Saturates link for configured duration, returns bits per second
+pub fn run(server: &str, config: &SpeedTestConfig) -> io::Result<f64> {
+    match config.mode {
+        SpeedTestMode::Iperf3 => iperf3(server, config),
+        SpeedTestMode::Tcp => tcp(server, config),
+    }
+}
+
+/// Part of `iperf3 -J` report which is needed
+#[derive(Deserialize)]
+struct Report {
+    end: ReportEnd,
+}
+
+#[derive(Deserialize)]
+struct ReportEnd {
+    sum_received: ReportSum,
+}
+
+#[derive(Deserialize)]
+struct ReportSum {
+    bits_per_second: f64,
+}
+
+fn iperf3(server: &str, config: &SpeedTestConfig) -> io::Result<f64> {
+    let output = Command::new("iperf3")
+        .args(["-c", server, "-J"])
+        .args(["-p", &config.port.to_string()])
+        .args(["-t", &config.duration.to_string()])
+        .output()?;
+    if !output.status.success() {
+        // iperf3 reports errors in json too, but exit code is enough here
+        return Err(io::Error::other(format!(
+            "iperf3 exited with {}",
+            output.status
+        )));
+    }
+    let report: Report = serde_json::from_slice(&output.stdout)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(report.end.sum_received.bits_per_second)
+}
+
+fn tcp(server: &str, config: &SpeedTestConfig) -> io::Result<f64> {
+    let addr = (server, config.port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "host is not resolved"))?;
+    let mut stream = TcpStream::connect_timeout(&addr, Duration::from_secs(5))?;
+    stream.set_write_timeout(Some(Duration::from_secs(5)))?;
+    let buf = [0u8; 64 * 1024];
+    let duration = Duration::from_secs(config.duration);
+    let started = Instant::now();
+    let mut sent: u64 = 0;
+    while started.elapsed() < duration {
+        stream.write_all(&buf)?;
+        sent += buf.len() as u64;
+    }
+    Ok(sent as f64 * 8.0 / started.elapsed().as_secs_f64())
+}
+
+fn record(sample: &Sample, mode: SpeedTestMode, bps: f64) -> io::Result<()> {
+    let path = Path::new(CONFIGURATION.as_str()).join("speedtest.csv");
+    let new = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if new {
+        writeln!(file, "{}", HEADER)?;
+    }
+    let mode = match mode {
+        SpeedTestMode::Iperf3 => "iperf3",
+        SpeedTestMode::Tcp => "tcp",
+    };
+    writeln!(file, "{},{},{:.0}", history::row(sample), mode, bps)
+}
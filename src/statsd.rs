@@ -0,0 +1,135 @@
+use std::{
+    fmt::Write as _,
+    io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket},
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+use tracing::{debug, info};
+
+use crate::sampler::Sample;
+
+/// `[statsd]` section of config, used in daemon mode
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct StatsdConfig {
+    /// Sends samples as UDP datagrams
+    pub enabled: bool,
+    /// `host:port` of StatsD daemon or Graphite UDP listener
+    pub address: String,
+    pub protocol: Protocol,
+    /// First part of every metric name
+    pub prefix: String,
+    /// Seconds between two sends, 0 sends every sample
+    pub interval: u64,
+}
+
+impl Default for StatsdConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: "127.0.0.1:8125".to_string(),
+            protocol: Protocol::Statsd,
+            prefix: "wifi".to_string(),
+            interval: 0,
+        }
+    }
+}
+
+/// Line format of datagrams
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Protocol {
+    /// `name:value|g` gauges
+    Statsd,
+    /// `name value timestamp` plaintext lines
+    Graphite,
+}
+
+/// Emits gauges of samples, UDP so a missing receiver never blocks the daemon
+pub struct Statsd {
+    socket: UdpSocket,
+    config: StatsdConfig,
+    last: Option<Instant>,
+}
+
+impl Statsd {
+    pub fn new(config: StatsdConfig) -> io::Result<Self> {
+        let target = config
+            .address
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "address not resolved"))?;
+        // socket has to be of the same family as target
+        let local: SocketAddr = if target.is_ipv6() {
+            (Ipv6Addr::UNSPECIFIED, 0).into()
+        } else {
+            (Ipv4Addr::UNSPECIFIED, 0).into()
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.connect(target)?;
+        info!(address = config.address, protocol = ?config.protocol, "sending metrics over udp");
+        Ok(Self {
+            socket,
+            config,
+            last: None,
+        })
+    }
+
+    /// Sends samples in one datagram unless interval has not passed yet
+    pub fn emit(&mut self, samples: &[Sample]) {
+        let interval = Duration::from_secs(self.config.interval);
+        if self.last.is_some_and(|last| last.elapsed() < interval) {
+            return;
+        }
+        self.last = Some(Instant::now());
+        let mut lines = String::new();
+        for s in samples {
+            let iface = name(&s.iface);
+            let mut gauge = |metric: &str, value: i64| {
+                let key = format!("{}.{}.{}", self.config.prefix, iface, metric);
+                let _ = match self.config.protocol {
+                    // signed value changes gauge by it, so zero sets it first
+                    Protocol::Statsd if value < 0 => {
+                        writeln!(lines, "{}:0|g\n{}:{}|g", key, key, value)
+                    }
+                    Protocol::Statsd => writeln!(lines, "{}:{}|g", key, value),
+                    Protocol::Graphite => {
+                        writeln!(lines, "{} {} {}", key, value, s.time.timestamp())
+                    }
+                };
+            };
+            if s.connected {
+                gauge("signal", s.signal.into());
+            }
+            gauge("connected", s.connected.into());
+            if let Some(frequency) = s.frequency {
+                gauge("frequency", frequency.into());
+            }
+            if let Some(bitrate) = s.bitrate {
+                gauge("bitrate", bitrate.into());
+            }
+            if let Some(score) = s.score {
+                gauge("quality", score.into());
+            }
+        }
+        if lines.is_empty() {
+            return;
+        }
+        // nobody listening is only seen as ICMP error on the next send
+        if let Err(e) = self.socket.send(lines.as_bytes()) {
+            debug!(error = %e, "failed to send metrics");
+        }
+    }
+}
+
+/// Dots separate levels of metric name, so they can't be in interface name
+fn name(part: &str) -> String {
+    part.chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+            _ => '_',
+        })
+        .collect()
+}
@@ -0,0 +1,255 @@
+use std::time::{Duration, Instant};
+
+use chrono::Local;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+use tracing::warn;
+
+use crate::{
+    ap,
+    appstate::AppState,
+    config::InterfacesConfig,
+    connection::Connection,
+    display::{self, Icon},
+    i18n,
+    nl80211::Nl80211,
+    sampler,
+    snapshot::Snapshot,
+};
+
+/// Interface and uptime are refreshed once per second, clock every frame
+const REFRESH: Duration = Duration::from_secs(1);
+
+/// Smaller terminal gets only a request to enlarge it, widgets would be clipped
+const MIN_WIDTH: u16 = 60;
+const MIN_HEIGHT: u16 = 16;
+
+/// Views narrower than this stack their panels in one column
+const NARROW_WIDTH: u16 = 100;
+
+/// true if area is below minimal size and views can't be drawn
+pub fn too_small(area: Rect) -> bool {
+    area.width < MIN_WIDTH || area.height < MIN_HEIGHT
+}
+
+/// true if area is too narrow for panels side by side
+pub fn narrow(area: Rect) -> bool {
+    area.width < NARROW_WIDTH
+}
+
+/// Bottom line with state, selected interface, connection uptime and clock, key hints are shown above it
+pub struct StatusBar {
+    nl: Option<Nl80211>,
+    updated: Option<Instant>,
+    iface: Option<String>,
+    /// Time since last association of selected interface
+    uptime: Option<Duration>,
+    state: &'static str,
+    /// Header and description of error shown as popup
+    popup: Option<(String, String)>,
+    /// Key hints are shown above status line
+    hint: bool,
+}
+
+impl StatusBar {
+    pub fn new() -> Self {
+        Self {
+            nl: None,
+            updated: None,
+            iface: None,
+            uptime: None,
+            state: "",
+            popup: None,
+            hint: true,
+        }
+    }
+
+    /// Shows or hides key hints, '?' toggles them
+    pub fn set_hint(&mut self, hint: bool) {
+        self.hint = hint;
+    }
+
+    pub fn update(
+        &mut self,
+        connection: &mut Connection,
+        filter: &InterfacesConfig,
+        selected: usize,
+        state: &AppState,
+    ) {
+        self.state = state.name();
+        self.popup = match state {
+            AppState::Error(e) => Some((e.header().to_string(), e.to_string())),
+            _ => None,
+        };
+        if self.updated.is_some_and(|t| t.elapsed() < REFRESH) {
+            return;
+        }
+        self.updated = Some(Instant::now());
+        let Some(devices) = connection.devices(filter) else {
+            self.iface = None;
+            self.uptime = None;
+            return;
+        };
+        let Some((interface, bss_list)) = devices.get(selected % devices.len().max(1)) else {
+            self.iface = None;
+            self.uptime = None;
+            return;
+        };
+        self.iface = Some(sampler::iface_name(interface));
+        self.uptime = None;
+        let (Some(index), true) = (interface.index, bss_list.iter().any(|b| b.status.is_some()))
+        else {
+            return;
+        };
+        if self.nl.is_none() {
+            self.nl = Nl80211::connect()
+                .inspect_err(|e| warn!(error = %e, "failed to open nl80211 socket"))
+                .ok();
+        }
+        let Some(nl) = self.nl.as_mut() else {
+            return;
+        };
+        // in managed mode the only station is the access point
+        match ap::stations(nl, index) {
+            Ok(stations) => self.uptime = stations.first().and_then(|s| s.connected),
+            Err(e) => {
+                warn!(error = %e, "failed to read connected time");
+                self.nl = None;
+            }
+        }
+    }
+
+    /// Header of error popup if one is open
+    pub fn error(&self) -> Option<&str> {
+        self.popup.as_ref().map(|(h, _)| h.as_str())
+    }
+
+    /// Draws view into area left by hint and status line, error popup goes on top of it
+    pub fn frame(&self, f: &mut Frame, hint: &str, view: impl FnOnce(&mut Frame, Rect)) {
+        let size = f.area();
+        if too_small(size) {
+            draw_too_small(f, size);
+            f.render_widget(display::AsciiOnly, size);
+            f.render_widget(Snapshot, size);
+            return;
+        }
+        let area = self.draw(f, i18n::tr(hint));
+        view(f, area);
+        if let Some((h, d)) = &self.popup {
+            draw_popup(f, area, h, d);
+        }
+        f.render_widget(display::AsciiOnly, f.area());
+        f.render_widget(Snapshot, f.area());
+    }
+
+    /// Draws hint and status line at the bottom of frame, returns area left for view
+    fn draw(&self, f: &mut Frame, hint: &str) -> Rect {
+        let area = f.area();
+        let width = usize::from(area.width.max(1));
+        let hint = if self.hint { hint } else { "" };
+        let hint_lines = hint
+            .lines()
+            .map(|l| l.chars().count().div_ceil(width).max(1))
+            .sum::<usize>();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(hint_lines as u16),
+                Constraint::Length(1),
+            ])
+            .split(area);
+        f.render_widget(
+            Paragraph::new(hint)
+                .style(Style::default().fg(Color::Gray))
+                .wrap(Wrap { trim: false }),
+            chunks[1],
+        );
+
+        let uptime = self.uptime.map_or("-".to_string(), |d| {
+            let secs = d.as_secs();
+            format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+        });
+        let text = format!(
+            " {} │ {} │ up {} │ {}",
+            i18n::tr(self.state),
+            self.iface.as_deref().unwrap_or("-"),
+            uptime,
+            Local::now().format("%H:%M:%S")
+        );
+        f.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                text,
+                Style::default().add_modifier(Modifier::BOLD),
+            )))
+            .style(display::bar_style()),
+            chunks[2],
+        );
+        chunks[0]
+    }
+}
+
+/// Replaces whole frame when terminal is below minimal size
+fn draw_too_small(f: &mut Frame, size: Rect) {
+    let text = vec![
+        Line::from(format!("terminal is {}x{}", size.width, size.height)),
+        Line::from(format!(
+            "please enlarge to at least {}x{}",
+            MIN_WIDTH, MIN_HEIGHT
+        )),
+        Line::from("'q' quits"),
+    ];
+    let height = (text.len() as u16).min(size.height);
+    let area = Rect {
+        y: size.y + (size.height - height) / 2,
+        height,
+        ..size
+    };
+    f.render_widget(
+        Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(Color::Yellow))
+            .wrap(Wrap { trim: true }),
+        area,
+    );
+}
+
+/// Centered box with error, half of area wide
+fn draw_popup(f: &mut Frame, area: Rect, h: &str, d: &str) {
+    let width = (area.width / 2).max(area.width.min(40));
+    let inner = usize::from(width.saturating_sub(2).max(1));
+    // description lines, empty line and hint inside borders
+    let height = (d.chars().count().div_ceil(inner) as u16 + 4).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+    let text = vec![
+        Line::from(Span::styled(d, Style::default().fg(Color::Red))),
+        Line::from(""),
+        Line::from(Span::styled(
+            i18n::tr("Press Esc or Enter to close"),
+            Style::default().fg(Color::Gray),
+        )),
+    ];
+    f.render_widget(Clear, popup);
+    f.render_widget(
+        Paragraph::new(text).wrap(Wrap { trim: true }).block(
+            Block::default()
+                .title(Span::styled(
+                    format!("{}{}", display::icon(Icon::Warning), i18n::tr(h)),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red)),
+        ),
+        popup,
+    );
+}
@@ -0,0 +1,86 @@
+use crate::{profile::Band, scan::ScanRow};
+
+/// Most BSSIDs vendors put between radios of one AP
+const MAX_MAC_DISTANCE: u64 = 8;
+
+/// Signal in dBm above which higher band usually beats 2.4 GHz
+const FASTER_SIGNAL: i32 = -70;
+
+/// Radios of one physical AP, the strongest first
+pub struct Ap<'a> {
+    pub radios: Vec<&'a ScanRow>,
+}
+
+/// `aa:bb:cc:dd:ee:ff` as number, locally administered bit is cleared,
+/// vendors often set it for the second radio
+fn mac(bssid: &str) -> Option<u64> {
+    let bytes: Vec<u8> = bssid
+        .split(':')
+        .map(|b| u8::from_str_radix(b, 16).ok())
+        .collect::<Option<_>>()?;
+    let bytes: [u8; 6] = bytes.try_into().ok()?;
+    let mut value = 0u64;
+    for (i, b) in bytes.iter().enumerate() {
+        value = value << 8 | u64::from(if i == 0 { b & !0x02 } else { *b });
+    }
+    Some(value)
+}
+
+/// Same SSID, same OUI and nearly the same address on another band
+fn same_ap(a: &ScanRow, b: &ScanRow) -> bool {
+    let (Some(fa), Some(fb)) = (a.frequency, b.frequency) else {
+        return false;
+    };
+    let (Some(ma), Some(mb)) = (mac(&a.bssid), mac(&b.bssid)) else {
+        return false;
+    };
+    a.ssid.is_some()
+        && a.ssid == b.ssid
+        && Band::of(fa) != Band::of(fb)
+        && ma >> 24 == mb >> 24
+        && ma.abs_diff(mb) <= MAX_MAC_DISTANCE
+}
+
+/// Joins radios of dual-band APs, `rows` should be sorted from the strongest signal
+pub fn aps<'a>(rows: &[&'a ScanRow]) -> Vec<Ap<'a>> {
+    let mut aps: Vec<Ap> = Vec::new();
+    for row in rows {
+        let band = row.frequency.map(Band::of);
+        match aps.iter_mut().find(|ap| {
+            ap.radios.iter().all(|r| r.frequency.map(Band::of) != band)
+                && ap.radios.iter().any(|r| same_ap(r, row))
+        }) {
+            Some(ap) => ap.radios.push(row),
+            None => aps.push(Ap { radios: vec![row] }),
+        }
+    }
+    aps
+}
+
+/// Which band connected row uses and how strong other radios of its AP are
+pub fn hint(rows: &[ScanRow]) -> Option<String> {
+    let connected = rows.iter().find(|r| r.connected)?;
+    let band = Band::of(connected.frequency?);
+    let others: Vec<String> = rows
+        .iter()
+        .filter(|r| !r.connected && same_ap(connected, r))
+        .filter_map(|r| Some(format!("{} at {} dBm", Band::of(r.frequency?), r.signal)))
+        .collect();
+    if others.is_empty() {
+        return None;
+    }
+    let faster = band == Band::Band2
+        && rows
+            .iter()
+            .any(|r| !r.connected && same_ap(connected, r) && r.signal >= FASTER_SIGNAL);
+    Some(format!(
+        "on {}, same AP also has {}{}",
+        band,
+        others.join(", "),
+        if faster {
+            ", higher band would likely be faster"
+        } else {
+            ""
+        }
+    ))
+}
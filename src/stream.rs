@@ -0,0 +1,32 @@
+use std::io::{self, Write};
+
+use tracing::{info, warn};
+
+use crate::{config::Config, connection::Connection, sampler, wakeup::Wakeup};
+
+/// Writes one JSON object per sample to stdout every interval, until reader closes the pipe
+pub fn run(config: &Config, connection: &mut Connection) -> io::Result<()> {
+    info!(interval = config.interval, "streaming samples");
+    let stdout = io::stdout();
+    let wakeup = Wakeup::spawn(config);
+    loop {
+        match connection.with(|s| sampler::collect(s, &config.interfaces)) {
+            Some(samples) => {
+                let mut out = stdout.lock();
+                for s in samples {
+                    match writeln!(out, "{}", s.json()).and_then(|_| out.flush()) {
+                        Ok(()) => {}
+                        // `| head` and friends are done reading
+                        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+                        Err(e) => return Err(e),
+                    }
+                }
+            }
+            None => warn!(
+                attempts = connection.attempts(),
+                "netlink socket is not available, reconnecting"
+            ),
+        }
+        wakeup.sleep(wakeup.interval(config.interval));
+    }
+}
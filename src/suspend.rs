@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+/// Smallest jump which is treated as suspend and not as scheduling delay
+const MIN_SUSPEND: Duration = Duration::from_secs(2);
+
+/// Detects system suspend by growth of difference between boottime and monotonic clocks,
+/// monotonic clock stops while system sleeps and boottime does not
+pub struct SuspendDetector {
+    slept: Duration,
+}
+
+impl SuspendDetector {
+    pub fn new() -> Self {
+        Self { slept: slept() }
+    }
+
+    /// Returns time spent in suspend since previous check, if system was suspended
+    pub fn check(&mut self) -> Option<Duration> {
+        let now = slept();
+        let diff = now.saturating_sub(self.slept);
+        self.slept = now;
+        (diff >= MIN_SUSPEND).then_some(diff)
+    }
+}
+
+fn slept() -> Duration {
+    clock(libc::CLOCK_BOOTTIME).saturating_sub(clock(libc::CLOCK_MONOTONIC))
+}
+
+fn clock(id: libc::clockid_t) -> Duration {
+    let mut ts = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // SAFETY: ts is valid pointer to timespec, both clocks exist on linux
+    unsafe { libc::clock_gettime(id, &mut ts) };
+    Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32)
+}
@@ -0,0 +1,111 @@
+use std::{
+    fs,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    style::{Color, Style},
+    widgets::{Block, Borders, Sparkline},
+};
+
+use crate::i18n;
+
+/// Rates are computed not more often than once per interval
+const INTERVAL: Duration = Duration::from_secs(1);
+/// Amount of points kept for chart
+const CAPACITY: usize = 300;
+
+/// Traffic of selected interface computed from kernel counters
+pub struct Throughput {
+    iface: String,
+    last: Option<(Instant, u64, u64)>,
+    rx: Vec<u64>,
+    tx: Vec<u64>,
+}
+
+impl Throughput {
+    pub fn new() -> Self {
+        Self {
+            iface: String::new(),
+            last: None,
+            rx: Vec::with_capacity(CAPACITY),
+            tx: Vec::with_capacity(CAPACITY),
+        }
+    }
+
+    /// Reads counters of `iface`, starts from scratch when interface is changed
+    pub fn update(&mut self, iface: &str) {
+        if self.iface != iface {
+            self.iface = iface.to_string();
+            self.last = None;
+            self.rx.clear();
+            self.tx.clear();
+        }
+        if self.last.is_some_and(|(t, _, _)| t.elapsed() < INTERVAL) {
+            return;
+        }
+        let (Some(rx), Some(tx)) = (counter(iface, "rx_bytes"), counter(iface, "tx_bytes")) else {
+            self.last = None;
+            return;
+        };
+        let now = Instant::now();
+        if let Some((t, last_rx, last_tx)) = self.last {
+            let secs = now.duration_since(t).as_secs_f64();
+            // counters are reset when driver is reloaded
+            push(
+                &mut self.rx,
+                (rx.saturating_sub(last_rx) as f64 / secs) as u64,
+            );
+            push(
+                &mut self.tx,
+                (tx.saturating_sub(last_tx) as f64 / secs) as u64,
+            );
+        }
+        self.last = Some((now, rx, tx));
+    }
+
+    /// Returns receive and transmit sparklines with the newest rates which fit into `width`
+    pub fn widgets(&self, width: u16) -> (Sparkline<'_>, Sparkline<'_>) {
+        (
+            sparkline("rx", &self.rx, width, Color::Blue),
+            sparkline("tx", &self.tx, width, Color::Magenta),
+        )
+    }
+}
+
+fn push(points: &mut Vec<u64>, rate: u64) {
+    if points.len() == CAPACITY {
+        points.remove(0);
+    }
+    points.push(rate);
+}
+
+fn counter(iface: &str, name: &str) -> Option<u64> {
+    fs::read_to_string(format!("/sys/class/net/{}/statistics/{}", iface, name))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn sparkline<'a>(name: &str, points: &'a [u64], width: u16, color: Color) -> Sparkline<'a> {
+    let visible = usize::from(width.saturating_sub(2));
+    let start = points.len().saturating_sub(visible);
+    let title = match points.last() {
+        Some(rate) => format!("{} {}", i18n::tr(name), format_rate(*rate)),
+        None => i18n::tr(name).to_string(),
+    };
+    Sparkline::default()
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .data(&points[start..])
+        .style(Style::default().fg(color))
+}
+
+/// Formats bytes per second with decimal units
+fn format_rate(rate: u64) -> String {
+    match rate {
+        r if r >= 1_000_000 => format!("{:.1} MB/s", r as f64 / 1_000_000.0),
+        r if r >= 1_000 => format!("{:.1} kB/s", r as f64 / 1_000.0),
+        r => format!("{} B/s", r),
+    }
+}
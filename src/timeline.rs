@@ -0,0 +1,268 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Local, TimeDelta};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    symbols::Marker,
+    text::Span,
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, List, ListItem, Paragraph},
+};
+use tracing::warn;
+
+use crate::{
+    history, i18n,
+    notes::{self, Note},
+    sampler::Sample,
+};
+
+pub const HINT: &str =
+    "Left/Right pans, '+'/'-' zooms, End returns to now, 'N' adds note, 'm' for monitoring";
+
+/// Time ranges chart can show, `ProgramState::history_zoom` is index into it
+pub const SPANS: [TimeDelta; 6] = [
+    TimeDelta::minutes(10),
+    TimeDelta::hours(1),
+    TimeDelta::hours(6),
+    TimeDelta::days(1),
+    TimeDelta::days(7),
+    TimeDelta::days(30),
+];
+
+/// History file is read again after this time, daemon may be appending to it
+const RELOAD: Duration = Duration::from_secs(10);
+
+/// Colors of interfaces in order of their first sample
+const COLORS: [Color; 4] = [Color::Green, Color::Cyan, Color::Magenta, Color::Blue];
+
+/// Disconnect and roam markers are drawn at this level
+const MARKER_LEVEL: f64 = -25.0;
+
+/// Notes are drawn above other markers
+const NOTE_LEVEL: f64 = -21.0;
+
+/// Most notes listed under chart
+const NOTE_LINES: usize = 5;
+
+/// Stored signal history, loaded from `history.csv`
+pub struct TimelineView {
+    path: Option<PathBuf>,
+    samples: Vec<Sample>,
+    notes_path: PathBuf,
+    notes: Vec<Note>,
+    loaded: Option<Instant>,
+    error: Option<String>,
+}
+
+impl TimelineView {
+    /// `path` is None when history is not recorded
+    pub fn new(path: Option<PathBuf>, notes_path: PathBuf) -> Self {
+        Self {
+            path,
+            samples: Vec::new(),
+            notes_path,
+            notes: Vec::new(),
+            loaded: None,
+            error: None,
+        }
+    }
+
+    /// Attaches note to current time, chart shows it right away
+    pub fn add_note(&mut self, text: &str) -> std::io::Result<()> {
+        notes::add(&self.notes_path, text)?;
+        self.loaded = None;
+        Ok(())
+    }
+
+    pub fn update(&mut self) {
+        if self.loaded.is_some_and(|l| l.elapsed() < RELOAD) {
+            return;
+        }
+        self.loaded = Some(Instant::now());
+        match notes::load(&self.notes_path) {
+            Ok(notes) => self.notes = notes,
+            Err(e) => warn!(error = %e, "failed to load notes"),
+        }
+        let Some(path) = &self.path else {
+            self.error = Some("history is disabled in config".to_string());
+            return;
+        };
+        match history::tail(path, usize::MAX) {
+            Ok(samples) => {
+                self.samples = samples;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    /// Chart of `SPANS[zoom]` which ends `pan` half-spans before now
+    pub fn draw(&self, f: &mut Frame, area: Rect, zoom: usize, pan: u32) {
+        if let Some(e) = &self.error {
+            let error = Paragraph::new(Span::styled(e.clone(), Style::default().fg(Color::Red)))
+                .block(
+                    Block::default()
+                        .title(i18n::tr("history"))
+                        .borders(Borders::ALL),
+                );
+            f.render_widget(error, area);
+            return;
+        }
+        let span = SPANS[zoom.min(SPANS.len() - 1)];
+        let end = Local::now() - span / 2 * pan as i32;
+        let start = end - span;
+        let x = |time: DateTime<Local>| (time - start).num_milliseconds() as f64 / 1000.0;
+
+        let mut ifaces: Vec<(String, Vec<(f64, f64)>)> = Vec::new();
+        let mut roams = Vec::new();
+        let mut disconnects = Vec::new();
+        // previous connected BSSID of each interface, samples before range count too
+        let mut last: HashMap<&str, Option<&str>> = HashMap::new();
+        for s in &self.samples {
+            if s.time > end {
+                break;
+            }
+            let bssid = s.bssid.as_deref().filter(|_| s.connected);
+            let previous = last.insert(&s.iface, bssid);
+            if s.time < start {
+                continue;
+            }
+            match (previous, bssid) {
+                (Some(Some(old)), Some(new)) if old != new => roams.push((x(s.time), MARKER_LEVEL)),
+                (Some(Some(_)), None) => disconnects.push((x(s.time), MARKER_LEVEL)),
+                _ => {}
+            }
+            if !s.connected {
+                continue;
+            }
+            let i = match ifaces.iter().position(|(name, _)| *name == s.iface) {
+                Some(i) => i,
+                None => {
+                    ifaces.push((s.iface.clone(), Vec::new()));
+                    ifaces.len() - 1
+                }
+            };
+            ifaces[i].1.push((x(s.time), f64::from(s.signal)));
+        }
+
+        let notes: Vec<&Note> = self
+            .notes
+            .iter()
+            .filter(|n| n.time >= start && n.time <= end)
+            .collect();
+        let note_points: Vec<(f64, f64)> = notes.iter().map(|n| (x(n.time), NOTE_LEVEL)).collect();
+
+        let mut datasets: Vec<Dataset> = ifaces
+            .iter()
+            .enumerate()
+            .map(|(i, (name, points))| {
+                Dataset::default()
+                    .name(name.clone())
+                    .marker(Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(COLORS[i % COLORS.len()]))
+                    .data(points)
+            })
+            .collect();
+        datasets.push(
+            Dataset::default()
+                .name("roam")
+                .marker(Marker::Dot)
+                .graph_type(GraphType::Scatter)
+                .style(Style::default().fg(Color::Yellow))
+                .data(&roams),
+        );
+        datasets.push(
+            Dataset::default()
+                .name("disconnect")
+                .marker(Marker::Dot)
+                .graph_type(GraphType::Scatter)
+                .style(Style::default().fg(Color::Red))
+                .data(&disconnects),
+        );
+        datasets.push(
+            Dataset::default()
+                .name("note")
+                .marker(Marker::Dot)
+                .graph_type(GraphType::Scatter)
+                .style(Style::default().fg(Color::White))
+                .data(&note_points),
+        );
+
+        let format = if span >= TimeDelta::days(1) {
+            "%m-%d %H:%M"
+        } else {
+            "%H:%M"
+        };
+        let width = span.num_seconds() as f64;
+        let chart = Chart::new(datasets)
+            .block(
+                Block::default()
+                    .title(i18n::fill(
+                        i18n::tr("history, {} up to {}"),
+                        &[
+                            &describe(span),
+                            &if pan == 0 {
+                                i18n::tr("now").to_string()
+                            } else {
+                                end.format(format).to_string()
+                            },
+                        ],
+                    ))
+                    .borders(Borders::ALL),
+            )
+            .x_axis(Axis::default().bounds([0.0, width]).labels(vec![
+                Span::raw(start.format(format).to_string()),
+                Span::raw((start + span / 2).format(format).to_string()),
+                Span::raw(end.format(format).to_string()),
+            ]))
+            .y_axis(
+                Axis::default()
+                    .title("dBm")
+                    .bounds([-100.0, -20.0])
+                    .labels(vec![Span::raw("-100"), Span::raw("-60"), Span::raw("-20")]),
+            );
+        if notes.is_empty() {
+            f.render_widget(chart, area);
+            return;
+        }
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(notes.len().min(NOTE_LINES) as u16 + 2),
+            ])
+            .split(area);
+        f.render_widget(chart, chunks[0]);
+        // the newest notes when they don't fit
+        let items: Vec<ListItem> = notes
+            .iter()
+            .rev()
+            .take(NOTE_LINES)
+            .rev()
+            .map(|n| ListItem::new(format!("{} {}", n.time.format(format), n.text)))
+            .collect();
+        let list = List::new(items).block(
+            Block::default()
+                .title(i18n::tr("notes"))
+                .borders(Borders::ALL),
+        );
+        f.render_widget(list, chunks[1]);
+    }
+}
+
+/// `10m`, `6h` or `7d`
+fn describe(span: TimeDelta) -> String {
+    if span >= TimeDelta::days(1) {
+        format!("{}d", span.num_days())
+    } else if span >= TimeDelta::hours(1) {
+        format!("{}h", span.num_hours())
+    } else {
+        format!("{}m", span.num_minutes())
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::BTreeSet;
+
+use neli_wifi::Bss;
+
+use crate::{
+    ie::{self, Security},
+    sampler,
+};
+
+/// OUIs of Microsoft (WPA, WMM) and Wi-Fi Alliance elements which every AP sends
+const COMMON_OUIS: [[u8; 3]; 2] = [[0x00, 0x50, 0xf2], [0x50, 0x6f, 0x9a]];
+
+/// What one BSS tells about itself
+#[derive(Clone, Debug)]
+pub struct Fingerprint {
+    pub bssid: String,
+    pub security: Security,
+    /// OUIs of vendor specific elements, identify chipset or firmware vendor
+    pub vendors: BTreeSet<[u8; 3]>,
+    pub signal: i32,
+    pub frequency: Option<u32>,
+    pub connected: bool,
+}
+
+impl Fingerprint {
+    fn new(bss: &Bss, ies: &[u8]) -> Self {
+        Self {
+            bssid: sampler::format_bssid(bss).unwrap_or_default(),
+            security: ie::security(ies),
+            vendors: ie::elements(ies)
+                .filter(|(id, _)| *id == 221)
+                .filter_map(|(_, data)| data.get(..3)?.try_into().ok())
+                .filter(|oui| !COMMON_OUIS.contains(oui))
+                .collect(),
+            signal: bss.signal.map(|s| s / 100).unwrap_or_default(),
+            frequency: bss.frequency,
+            connected: bss.status.is_some(),
+        }
+    }
+
+    /// Vendors are compared only when both sides send vendor elements
+    fn conflicts_with(&self, other: &Fingerprint) -> bool {
+        self.security != other.security
+            || (!self.vendors.is_empty()
+                && !other.vendors.is_empty()
+                && self.vendors.is_disjoint(&other.vendors))
+    }
+
+    /// Vendor OUIs as `aa:bb:cc` list
+    pub fn vendor_list(&self) -> String {
+        self.vendors
+            .iter()
+            .map(|o| format!("{:02x}:{:02x}:{:02x}", o[0], o[1], o[2]))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Returns connected BSS followed by BSSes with the same SSID which look different,
+/// empty list if nothing suspicious is found
+pub fn suspicious(bss_list: &[Bss]) -> Vec<Fingerprint> {
+    let Some((connected, ssid)) = bss_list.iter().find_map(|b| {
+        let ies = b.information_elements.as_deref()?;
+        b.status?;
+        Some((Fingerprint::new(b, ies), ie::ssid(ies)?))
+    }) else {
+        return Vec::new();
+    };
+    let others: Vec<Fingerprint> = bss_list
+        .iter()
+        .filter(|b| b.status.is_none())
+        .filter_map(|b| {
+            let ies = b.information_elements.as_deref()?;
+            (ie::ssid(ies)? == ssid).then(|| Fingerprint::new(b, ies))
+        })
+        .filter(|f| f.conflicts_with(&connected))
+        .collect();
+    if others.is_empty() {
+        return Vec::new();
+    }
+    std::iter::once(connected).chain(others).collect()
+}
@@ -0,0 +1,310 @@
+use std::{
+    collections::HashSet,
+    fs, io,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError},
+    },
+    thread::sleep,
+    time::Duration,
+};
+
+use neli::attr::Attribute;
+use neli_wifi::{Nl80211Attr, Nl80211AttrCqm, Nl80211Cmd};
+use serde::Deserialize;
+use signal_hook::{consts::SIGTERM, iterator::Signals};
+use tracing::{debug, info, warn};
+
+use crate::{config::Config, nl80211::Nl80211, radio};
+
+/// `[events]` section of config
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct EventsConfig {
+    /// Listens for nl80211 scan, connect and signal events, sample is taken right after them
+    pub enabled: bool,
+    /// Seconds between samples while kernel reports nothing, 0 keeps `interval`
+    pub idle_interval: u64,
+    /// Registers `thresholds` with kernel on every connect. Kernel keeps one set per interface,
+    /// so this replaces thresholds iwd or wpa_supplicant bgscan roam by and their roaming stops
+    /// working while the app runs. Without it only thresholds set by others are reported.
+    pub register_thresholds: bool,
+    /// Signal levels in dBm kernel reports crossings of, `below` of alert rules are added
+    pub thresholds: Vec<i32>,
+    /// dB signal has to move back before crossing of the same threshold is reported again
+    pub hysteresis: u32,
+    /// Thresholds of connection manager set back on exit, kernel can't tell them,
+    /// empty turns monitoring off until the next connect
+    pub restore_thresholds: Vec<i32>,
+}
+
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            idle_interval: 0,
+            register_thresholds: false,
+            thresholds: Vec::new(),
+            hysteresis: 2,
+            restore_thresholds: Vec::new(),
+        }
+    }
+}
+
+/// Change announced by kernel
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Wake {
+    ScanResults,
+    Connected,
+    Disconnected,
+    /// Connection quality monitor crossed RSSI threshold
+    Signal,
+}
+
+/// Sleep of sampling loops which ends early on nl80211 events
+pub struct Wakeup {
+    events: Option<Receiver<(Wake, Option<u32>)>>,
+    /// Cleared when listener thread stops, loops fall back to plain interval
+    alive: Arc<AtomicBool>,
+    idle_interval: u64,
+    /// Thresholds registered by us, set back when sleep is dropped
+    cqm: Option<Arc<Cqm>>,
+}
+
+impl Wakeup {
+    /// Subscribes to `scan` and `mlme` groups and registers signal thresholds if it is enabled,
+    /// sleeps are not interrupted if it fails
+    pub fn spawn(config: &Config) -> Self {
+        let alive = Arc::new(AtomicBool::new(false));
+        let cqm = (config.events.enabled && config.events.register_thresholds)
+            .then(|| Arc::new(Cqm::new(config)));
+        if let Some(cqm) = &cqm {
+            warn!(
+                "registering signal thresholds replaces roaming thresholds of connection manager"
+            );
+            restore_on_term(cqm.clone());
+        }
+        let events = config
+            .events
+            .enabled
+            .then(|| listen(alive.clone(), cqm.clone()))
+            .and_then(|r| {
+                r.inspect_err(|e| warn!(error = %e, "nl80211 events are not used"))
+                    .ok()
+            });
+        Self {
+            events,
+            alive,
+            idle_interval: config.events.idle_interval,
+            cqm,
+        }
+    }
+
+    /// Plain sleep without events
+    pub fn none() -> Self {
+        Self {
+            events: None,
+            alive: Arc::new(AtomicBool::new(false)),
+            idle_interval: 0,
+            cqm: None,
+        }
+    }
+
+    /// Time until next sample, idle interval while events wake the loop up
+    pub fn interval(&self, interval: u64) -> Duration {
+        if self.alive.load(Ordering::Relaxed) && self.idle_interval > interval {
+            Duration::from_secs(self.idle_interval)
+        } else {
+            Duration::from_secs(interval)
+        }
+    }
+
+    /// Sleeps `duration`, returns sooner with event which came meanwhile
+    pub fn sleep(&self, duration: Duration) -> Option<Wake> {
+        let Some(events) = self
+            .events
+            .as_ref()
+            .filter(|_| self.alive.load(Ordering::Relaxed))
+        else {
+            sleep(duration);
+            return None;
+        };
+        match events.recv_timeout(duration) {
+            Ok((wake, ifindex)) => {
+                // burst of events needs one sample only
+                while events.try_recv().is_ok() {}
+                debug!(?wake, ifindex, "woken up by nl80211 event");
+                Some(wake)
+            }
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => {
+                sleep(duration);
+                None
+            }
+        }
+    }
+}
+
+impl Drop for Wakeup {
+    fn drop(&mut self) {
+        if let Some(cqm) = &self.cqm {
+            cqm.restore();
+        }
+    }
+}
+
+/// Daemon is stopped with SIGTERM, which skips drop
+fn restore_on_term(cqm: Arc<Cqm>) {
+    let mut signals = match Signals::new([SIGTERM]) {
+        Ok(signals) => signals,
+        Err(e) => {
+            warn!(error = %e, "signal thresholds are not restored on SIGTERM");
+            return;
+        }
+    };
+    std::thread::spawn(move || {
+        if signals.forever().next().is_some() {
+            cqm.restore();
+            std::process::exit(0);
+        }
+    });
+}
+
+/// Connection quality monitor thresholds, kept by kernel per connection
+struct Cqm {
+    thresholds: Vec<i32>,
+    hysteresis: u32,
+    restore: Vec<i32>,
+    /// Interfaces our thresholds were registered on
+    registered: Mutex<HashSet<u32>>,
+}
+
+impl Cqm {
+    fn new(config: &Config) -> Self {
+        let mut thresholds = config.events.thresholds.clone();
+        thresholds.extend(config.alert.iter().filter_map(|rule| rule.below));
+        // kernel wants them sorted without duplicates
+        thresholds.sort_unstable();
+        thresholds.dedup();
+        Self {
+            thresholds,
+            hysteresis: config.events.hysteresis,
+            restore: config.events.restore_thresholds.clone(),
+            registered: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Registers thresholds on interface, only the highest one if driver takes a single one
+    fn register(&self, index: u32) {
+        let Some(highest) = self.thresholds.last() else {
+            return;
+        };
+        let result = radio::set_cqm_rssi(index, &self.thresholds, self.hysteresis).or_else(|e| {
+            if self.thresholds.len() == 1 {
+                return Err(e);
+            }
+            debug!(index, error = %e, "threshold list refused, registering one");
+            radio::set_cqm_rssi(index, &[*highest], self.hysteresis)
+        });
+        match result {
+            Ok(()) => {
+                info!(index, thresholds = ?self.thresholds, "signal thresholds registered");
+                self.registered.lock().unwrap().insert(index);
+            }
+            // not connected yet or driver has no CQM, connect event tries again
+            Err(e) => debug!(index, error = %e, "failed to register signal thresholds"),
+        }
+    }
+
+    /// Sets thresholds of connection manager back, single 0 turns monitoring off
+    fn restore(&self) {
+        let thresholds = if self.restore.is_empty() {
+            &[0][..]
+        } else {
+            &self.restore
+        };
+        for index in self.registered.lock().unwrap().drain() {
+            match radio::set_cqm_rssi(index, thresholds, self.hysteresis) {
+                Ok(()) => info!(index, ?thresholds, "signal thresholds restored"),
+                Err(e) => debug!(index, error = %e, "failed to restore signal thresholds"),
+            }
+        }
+    }
+}
+
+/// Indexes of wifi interfaces from sysfs
+fn wifi_indexes() -> Vec<u32> {
+    let Ok(dir) = fs::read_dir("/sys/class/net") else {
+        return Vec::new();
+    };
+    dir.flatten()
+        .filter(|entry| entry.path().join("phy80211").exists())
+        .filter_map(|entry| fs::read_to_string(entry.path().join("ifindex")).ok())
+        .filter_map(|index| index.trim().parse().ok())
+        .collect()
+}
+
+fn listen(
+    alive: Arc<AtomicBool>,
+    cqm: Option<Arc<Cqm>>,
+) -> io::Result<Receiver<(Wake, Option<u32>)>> {
+    let mut nl = Nl80211::connect()?;
+    nl.subscribe("scan")?;
+    nl.subscribe("mlme")?;
+    let (tx, rx) = mpsc::channel();
+    alive.store(true, Ordering::Relaxed);
+    info!("listening for nl80211 events");
+    std::thread::spawn(move || {
+        if let Some(cqm) = &cqm {
+            for index in wifi_indexes() {
+                cqm.register(index);
+            }
+        }
+        loop {
+            let msg = match nl.event() {
+                Ok(msg) => msg,
+                Err(e) => {
+                    warn!(error = %e, "failed to read nl80211 event");
+                    break;
+                }
+            };
+            let wake = match msg.cmd {
+                Nl80211Cmd::CmdNewScanResults => Wake::ScanResults,
+                Nl80211Cmd::CmdConnect | Nl80211Cmd::CmdRoam => Wake::Connected,
+                Nl80211Cmd::CmdDisconnect => Wake::Disconnected,
+                Nl80211Cmd::CmdNotifyCqm => Wake::Signal,
+                _ => continue,
+            };
+            let attrs = msg.get_attr_handle();
+            let ifindex: Option<u32> = attrs
+                .get_attribute(Nl80211Attr::AttrIfindex)
+                .and_then(|a| a.get_payload_as().ok());
+            match (wake, ifindex) {
+                // thresholds belong to connection
+                (Wake::Connected, Some(index)) => {
+                    if let Some(cqm) = &cqm {
+                        cqm.register(index);
+                    }
+                }
+                (Wake::Signal, _) => {
+                    // 0 is low, 1 is high
+                    let event: Option<u32> = attrs
+                        .get_attribute(Nl80211Attr::AttrCqm)
+                        .and_then(|a| a.get_attr_handle::<Nl80211AttrCqm>().ok())
+                        .and_then(|cqm| {
+                            cqm.get_attribute(Nl80211AttrCqm::AttrCqmRssiThresholdEvent)
+                                .and_then(|a| a.get_payload_as().ok())
+                        });
+                    info!(ifindex, event, "signal crossed threshold");
+                }
+                _ => {}
+            }
+            if tx.send((wake, ifindex)).is_err() {
+                break;
+            }
+        }
+        alive.store(false, Ordering::Relaxed);
+    });
+    Ok(rx)
+}
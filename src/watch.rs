@@ -0,0 +1,68 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tracing::{debug, warn};
+
+/// Watches `config.toml` for changes made outside of the app
+pub struct ConfigWatcher {
+    path: PathBuf,
+    changed: Arc<AtomicBool>,
+    /// Content seen last time, editors touch files without changing them
+    last: Option<String>,
+    _watcher: RecommendedWatcher,
+}
+
+impl ConfigWatcher {
+    /// Starts watching config of `dir`, directory is watched because editors replace files
+    pub fn new(dir: &Path) -> io::Result<Self> {
+        let path = dir.join("config.toml");
+        let changed = Arc::new(AtomicBool::new(false));
+        let flag = changed.clone();
+        let file = path.clone();
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+                Ok(event) if event.paths.contains(&file) => {
+                    debug!(kind = ?event.kind, "config file changed");
+                    flag.store(true, Ordering::Relaxed);
+                }
+                Ok(_) => {}
+                Err(e) => warn!(error = %e, "config watch failed"),
+            })
+            .map_err(io::Error::other)?;
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(io::Error::other)?;
+        Ok(Self {
+            last: fs::read_to_string(&path).ok(),
+            path,
+            changed,
+            _watcher: watcher,
+        })
+    }
+
+    /// true once after content of config file changed
+    pub fn changed(&mut self) -> bool {
+        if !self.changed.swap(false, Ordering::Relaxed) {
+            return false;
+        }
+        let content = fs::read_to_string(&self.path).ok();
+        if content == self.last {
+            return false;
+        }
+        self.last = content;
+        true
+    }
+
+    /// Remembers current content, so own writes are not reported as change
+    pub fn sync(&mut self) {
+        self.changed.store(false, Ordering::Relaxed);
+        self.last = fs::read_to_string(&self.path).ok();
+    }
+}
@@ -0,0 +1,132 @@
+use std::{io, path::Path};
+
+use crossterm::event::{self, KeyCode};
+use ratatui::{
+    Terminal,
+    backend::Backend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+use tracing::info;
+
+use crate::{config::Config, i18n};
+
+const INTERVALS: [u64; 4] = [1, 2, 5, 10];
+
+/// One question of wizard with its answers
+struct Step {
+    question: &'static str,
+    answers: Vec<String>,
+    selected: usize,
+}
+
+/// Asks for interface, refresh rate, privacy and history on first launch and writes config
+/// into `dir`. Esc skips remaining questions and keeps their defaults
+pub fn run<B: Backend<Error = io::Error>>(
+    terminal: &mut Terminal<B>,
+    dir: &Path,
+    interfaces: &[String],
+) -> io::Result<Config> {
+    let mut interface_answers = vec!["first found".to_string()];
+    interface_answers.extend(interfaces.iter().cloned());
+    let mut steps = [
+        Step {
+            question: "Which interface should be selected at start?",
+            answers: interface_answers,
+            selected: 0,
+        },
+        Step {
+            question: "How often should signal be sampled?",
+            answers: INTERVALS.iter().map(|i| format!("every {} s", i)).collect(),
+            selected: 0,
+        },
+        Step {
+            question: "Hide mac addresses in log file?",
+            answers: vec!["no".to_string(), "yes".to_string()],
+            selected: 0,
+        },
+        Step {
+            question: "Record signal history into history.csv?",
+            answers: vec!["no".to_string(), "yes".to_string()],
+            selected: 0,
+        },
+    ];
+    let mut current = 0;
+    while current < steps.len() {
+        let step = &steps[current];
+        let title = i18n::fill(
+            i18n::tr("first run {}/{}"),
+            &[&(current + 1).to_string(), &steps.len().to_string()],
+        );
+        terminal.draw(|f| {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(3),
+                    Constraint::Min(0),
+                    Constraint::Length(1),
+                ])
+                .split(f.area());
+            f.render_widget(
+                Paragraph::new(step.question)
+                    .wrap(Wrap { trim: true })
+                    .block(Block::default().title(title).borders(Borders::ALL)),
+                chunks[0],
+            );
+            let items: Vec<ListItem> = step
+                .answers
+                .iter()
+                .map(|a| ListItem::new(a.as_str()))
+                .collect();
+            let mut list_state = ListState::default();
+            list_state.select(Some(step.selected));
+            f.render_stateful_widget(
+                List::new(items)
+                    .block(Block::default().borders(Borders::ALL))
+                    .highlight_style(
+                        Style::default()
+                            .bg(Color::DarkGray)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                chunks[1],
+                &mut list_state,
+            );
+            f.render_widget(
+                Paragraph::new("Up/Down to choose, Enter to confirm, Esc keeps defaults")
+                    .style(Style::default().fg(Color::Gray)),
+                chunks[2],
+            );
+        })?;
+
+        let Some(key) = event::read()?.as_key_press_event() else {
+            continue;
+        };
+        let step = &mut steps[current];
+        match key.code {
+            KeyCode::Up => step.selected = step.selected.saturating_sub(1),
+            KeyCode::Down => step.selected = (step.selected + 1).min(step.answers.len() - 1),
+            KeyCode::Enter => current += 1,
+            KeyCode::Esc => break,
+            _ => {}
+        }
+    }
+
+    let mut config = Config::default();
+    config.interfaces.preferred = steps[0]
+        .selected
+        .checked_sub(1)
+        .map(|i| interfaces[i].clone());
+    config.interval = INTERVALS[steps[1].selected];
+    config.privacy = steps[2].selected == 1;
+    config.history = Some(steps[3].selected == 1);
+    info!(
+        preferred = config.interfaces.preferred,
+        interval = config.interval,
+        privacy = config.privacy,
+        history = config.tui_history(),
+        "first run wizard finished"
+    );
+    config.save_wizard(dir)?;
+    Ok(config)
+}
@@ -0,0 +1,172 @@
+use std::{
+    fs, io,
+    os::unix::net::UnixDatagram,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use tracing::{debug, info};
+
+/// Directories where wpa_supplicant creates control sockets
+const CTRL_DIRS: [&str; 2] = ["/run/wpa_supplicant", "/var/run/wpa_supplicant"];
+const STATUS_INTERVAL: Duration = Duration::from_secs(1);
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Attached client of wpa_supplicant control interface
+pub struct Supplicant {
+    socket: UnixDatagram,
+    local: PathBuf,
+    state: Option<String>,
+    last_failure: Option<String>,
+    polled: Option<Instant>,
+}
+
+impl Supplicant {
+    /// Connects to control socket of interface and subscribes to events
+    pub fn open(iface: &str) -> io::Result<Self> {
+        let remote = CTRL_DIRS
+            .iter()
+            .map(|d| Path::new(d).join(iface))
+            .find(|p| p.exists())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no control socket"))?;
+        // supplicant answers to the address of client, so client socket must be bound
+        let local =
+            std::env::temp_dir().join(format!("wifi-check-tui-{}-{}", std::process::id(), iface));
+        let _ = fs::remove_file(&local);
+        let socket = UnixDatagram::bind(&local)?;
+        let mut sup = Self {
+            socket,
+            local,
+            state: None,
+            last_failure: None,
+            polled: None,
+        };
+        sup.socket.connect(remote)?;
+        sup.socket
+            .set_read_timeout(Some(Duration::from_millis(200)))?;
+        sup.request("ATTACH")?;
+        Ok(sup)
+    }
+
+    /// Supplicant state machine state (SCANNING, 4WAY_HANDSHAKE, COMPLETED, ...)
+    pub fn state(&self) -> Option<&str> {
+        self.state.as_deref()
+    }
+
+    /// Reason of the last authentication or association failure
+    pub fn last_failure(&self) -> Option<&str> {
+        self.last_failure.as_deref()
+    }
+
+    /// Reads pending events and refreshes state once per second
+    pub fn poll(&mut self) -> io::Result<()> {
+        self.socket.set_nonblocking(true)?;
+        let mut buf = [0u8; 4096];
+        while let Ok(n) = self.socket.recv(&mut buf) {
+            let msg = String::from_utf8_lossy(&buf[..n]).to_string();
+            self.event(&msg);
+        }
+        self.socket.set_nonblocking(false)?;
+
+        if self.polled.is_none_or(|p| p.elapsed() >= STATUS_INTERVAL) {
+            self.polled = Some(Instant::now());
+            let status = self.request("STATUS")?;
+            self.state = status
+                .lines()
+                .find_map(|l| l.strip_prefix("wpa_state="))
+                .map(str::to_string);
+        }
+        Ok(())
+    }
+
+    /// Sends command and returns reply, events received meanwhile are processed
+    fn request(&mut self, cmd: &str) -> io::Result<String> {
+        self.socket.send(cmd.as_bytes())?;
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = self.socket.recv(&mut buf)?;
+            let msg = String::from_utf8_lossy(&buf[..n]).to_string();
+            // events start with priority like "<3>"
+            if msg.starts_with('<') {
+                self.event(&msg);
+            } else {
+                return Ok(msg);
+            }
+        }
+    }
+
+    fn event(&mut self, msg: &str) {
+        let msg = msg.split_once('>').map_or(msg, |(_, m)| m);
+        debug!(event = msg, "wpa_supplicant event");
+        let field = |name: &str| {
+            msg.split_whitespace()
+                .find_map(|w| w.strip_prefix(name))
+                .map(str::to_string)
+        };
+        let failure = if msg.starts_with("CTRL-EVENT-SSID-TEMP-DISABLED") {
+            field("reason=")
+        } else if msg.starts_with("CTRL-EVENT-AUTH-REJECT") {
+            field("status_code=").map(|c| format!("authentication rejected, status {}", c))
+        } else if msg.starts_with("CTRL-EVENT-ASSOC-REJECT") {
+            field("status_code=").map(|c| format!("association rejected, status {}", c))
+        } else if msg.starts_with("CTRL-EVENT-DISCONNECTED") {
+            field("reason=").map(|r| format!("disconnected, reason {}", r))
+        } else {
+            None
+        };
+        if let Some(failure) = failure {
+            info!(failure, "wpa_supplicant reported failure");
+            self.last_failure = Some(failure);
+        }
+    }
+}
+
+impl Drop for Supplicant {
+    fn drop(&mut self) {
+        let _ = self.socket.send(b"DETACH");
+        let _ = fs::remove_file(&self.local);
+    }
+}
+
+/// Keeps supplicant client for selected interface and reopens it when needed
+pub struct SupplicantMonitor {
+    iface: String,
+    client: Option<Supplicant>,
+    retry_at: Option<Instant>,
+}
+
+impl SupplicantMonitor {
+    pub fn new() -> Self {
+        Self {
+            iface: String::new(),
+            client: None,
+            retry_at: None,
+        }
+    }
+
+    /// Polls supplicant of interface, returns None if its control socket is not available
+    pub fn update(&mut self, iface: &str) -> Option<&Supplicant> {
+        if self.iface != iface {
+            self.iface = iface.to_string();
+            self.client = None;
+            self.retry_at = None;
+        }
+        if self.client.is_none() && self.retry_at.is_none_or(|r| Instant::now() >= r) {
+            match Supplicant::open(iface) {
+                Ok(c) => self.client = Some(c),
+                Err(e) => {
+                    debug!(error = %e, iface, "wpa_supplicant is not available");
+                    self.retry_at = Some(Instant::now() + RETRY_INTERVAL);
+                }
+            }
+        }
+        if let Some(client) = &mut self.client
+            && let Err(e) = client.poll()
+        {
+            debug!(error = %e, iface, "wpa_supplicant connection lost");
+            self.client = None;
+            self.retry_at = Some(Instant::now() + RETRY_INTERVAL);
+        }
+        self.client.as_ref()
+    }
+}
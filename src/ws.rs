@@ -0,0 +1,77 @@
+use std::{
+    io::{self, Write},
+    net::TcpStream,
+    sync::RwLock,
+    thread::sleep,
+    time::Duration,
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use chrono::{DateTime, Local};
+use sha1::{Digest, Sha1};
+use tracing::{debug, info};
+
+use crate::{http, sampler::Sample};
+
+/// GUID from RFC 6455 which is appended to client key
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// How often latest samples are checked for new ones
+const POLL: Duration = Duration::from_millis(200);
+
+/// Accepts upgrade and pushes every new sample as JSON text message until client leaves
+pub fn serve(
+    mut stream: TcpStream,
+    key: &str,
+    latest: &RwLock<Vec<Sample>>,
+    privacy: bool,
+) -> io::Result<()> {
+    let accept = STANDARD.encode(Sha1::digest(format!("{}{}", key.trim(), GUID)));
+    let response = format!(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        accept
+    );
+    stream.write_all(response.as_bytes())?;
+    let peer = stream.peer_addr()?;
+    info!(%peer, "websocket client connected");
+    // slow client is dropped by write timeout of http connection
+    if let Err(e) = push(stream, latest, privacy) {
+        debug!(error = %e, %peer, "websocket client left");
+    }
+    Ok(())
+}
+
+/// Sends samples whenever daemon replaces them, returns when client is gone
+fn push(mut stream: TcpStream, latest: &RwLock<Vec<Sample>>, privacy: bool) -> io::Result<()> {
+    let mut sent: Option<DateTime<Local>> = None;
+    loop {
+        let samples = latest.read().unwrap().clone();
+        let time = samples.first().map(|s| s.time);
+        if time.is_some() && time != sent {
+            sent = time;
+            for sample in &samples {
+                write_text(&mut stream, &http::sample_json(sample, privacy).to_string())?;
+            }
+        }
+        sleep(POLL);
+    }
+}
+
+/// Writes unfragmented text frame, server frames are not masked
+fn write_text(stream: &mut TcpStream, text: &str) -> io::Result<()> {
+    let len = text.len();
+    let mut frame = vec![0x81];
+    match len {
+        0..=125 => frame.push(len as u8),
+        126..=0xffff => {
+            frame.push(126);
+            frame.extend((len as u16).to_be_bytes());
+        }
+        _ => {
+            frame.push(127);
+            frame.extend((len as u64).to_be_bytes());
+        }
+    }
+    frame.extend(text.as_bytes());
+    stream.write_all(&frame)
+}
@@ -0,0 +1,127 @@
+use std::{
+    env, fs,
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+/// Runs TUI against recorded fixture with its own config directory
+fn run(name: &str, fixture: &str, args: &[&str]) -> Output {
+    let home = env::temp_dir().join(format!("wifi-replay-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&home);
+    fs::create_dir_all(&home).unwrap();
+    let fixture: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "fixtures", fixture]
+        .iter()
+        .collect();
+    let output = Command::new(env!("CARGO_BIN_EXE_wifi"))
+        .args(["--no-wizard", "--no-log", "--replay"])
+        .arg(&fixture)
+        .args(args)
+        .env("HOME", &home)
+        .env("LANG", "C")
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .output()
+        .unwrap();
+    let _ = fs::remove_dir_all(&home);
+    output
+}
+
+fn screen(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn main_view_is_shown_first() {
+    let screen = screen(&run("main", "connected.jsonl", &["--keys", ""]));
+    assert!(screen.contains("Built with neli_wifi"));
+    assert!(screen.contains("Press 'm' to change state"));
+    assert!(screen.contains(" Main "));
+}
+
+#[test]
+fn monitoring_shows_connected_interface() {
+    let screen = screen(&run("monitoring", "connected.jsonl", &["--keys", "m"]));
+    assert!(screen.contains("wlan0"));
+    // the last frame of fixture stays
+    assert!(screen.contains("Connection -54 dBm"));
+    assert!(screen.contains(" Monitoring "));
+    assert!(!screen.contains("Not associated"));
+}
+
+#[test]
+fn hidden_mac_is_shown_with_h() {
+    let hidden = screen(&run("hidden", "connected.jsonl", &["--keys", "m"]));
+    assert!(!hidden.contains("02:00:00:00:01:00"));
+    let shown = screen(&run("shown", "connected.jsonl", &["--keys", "mh"]));
+    assert!(shown.contains("02:00:00:00:01:00"));
+}
+
+#[test]
+fn missing_interface_raises_error() {
+    let screen = screen(&run("error", "no_interface.jsonl", &["--keys", "m"]));
+    assert!(screen.contains("wifi interface error"));
+    assert!(screen.contains("No wifi interface found"));
+    assert!(screen.contains(" Error "));
+}
+
+#[test]
+fn keys_after_quit_are_not_pressed() {
+    let screen = screen(&run("quit", "connected.jsonl", &["--keys", "qm"]));
+    assert!(!screen.contains(" Monitoring "));
+}
+
+#[test]
+fn unknown_key_is_rejected() {
+    let output = run("unknown", "connected.jsonl", &["--keys", "<Nope>"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("<Nope>"));
+}
+
+#[test]
+fn bench_reports_frame_rate() {
+    let report = screen(&run("bench", "connected.jsonl", &["--bench", "1"]));
+    assert!(report.contains("frames/s"));
+    if cfg!(feature = "bench") {
+        assert!(report.contains("allocations"));
+    }
+}
+
+#[test]
+fn quiet_exit_code_tells_health() {
+    let healthy = run("healthy", "connected.jsonl", &["--quiet"]);
+    assert_eq!(healthy.status.code(), Some(0));
+    assert!(healthy.stdout.is_empty());
+    let missing = run("missing", "no_interface.jsonl", &["--quiet"]);
+    assert_eq!(missing.status.code(), Some(3));
+    let broken = run("broken", "does_not_exist.jsonl", &["--quiet"]);
+    assert_eq!(broken.status.code(), Some(4));
+}
+
+#[test]
+fn check_prints_plugin_line() {
+    let ok = run("check-ok", "connected.jsonl", &["--check"]);
+    assert_eq!(ok.status.code(), Some(0));
+    let line = String::from_utf8(ok.stdout).unwrap();
+    assert!(line.starts_with("WIFI OK - home-net -60 dBm on wlan0 |"));
+    assert!(line.contains("signal=-60;-70:;-80:"));
+    let crit = run(
+        "check-crit",
+        "connected.jsonl",
+        &["--check", "--warn", "-40", "--crit", "-50"],
+    );
+    assert_eq!(crit.status.code(), Some(2));
+    let unknown = run("check-unknown", "no_interface.jsonl", &["--check"]);
+    assert_eq!(unknown.status.code(), Some(3));
+    let broken = run("check-broken", "does_not_exist.jsonl", &["--check"]);
+    assert_eq!(broken.status.code(), Some(3));
+    assert!(
+        String::from_utf8(broken.stdout)
+            .unwrap()
+            .starts_with("WIFI UNKNOWN - ")
+    );
+}